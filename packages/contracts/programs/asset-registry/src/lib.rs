@@ -1,11 +1,35 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+use anchor_lang::system_program::{self, CreateAccount};
 use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    onchain::invoke_transfer_checked,
+    extension::{
+        default_account_state,
+        metadata_pointer::{self, MetadataPointer},
+        permanent_delegate::PermanentDelegate,
+        transfer_hook::{self, TransferHook},
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    state::{Account as TokenAccountState, AccountState, Mint as MintState},
+};
 use anchor_spl::token_interface::{Mint, TokenAccount};
-use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::metadata::Metadata;
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken};
+use spl_token_metadata_interface::state::TokenMetadata;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
+/// Compliance program used as the default transfer hook for RWA mints
+pub mod compliance_program {
+    use super::*;
+    declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnV");
+}
+
 /// Asset Registry Program
 /// 
 /// Manages the registration and lifecycle of tokenized real-world assets.
@@ -18,20 +42,126 @@ pub mod asset_registry {
     use super::*;
 
     /// Initialize the asset registry configuration
+    /// The initializing authority is granted the registry admin role
     pub fn initialize(
         ctx: Context<Initialize>,
         platform_fee_bps: u16,
     ) -> Result<()> {
+        require!(platform_fee_bps <= MAX_FEE_BPS, AssetRegistryError::InvalidFeeBps);
+
         let config = &mut ctx.accounts.config;
         config.authority = ctx.accounts.authority.key();
         config.platform_fee_bps = platform_fee_bps;
         config.total_assets = 0;
         config.bump = ctx.bumps.config;
-        
+        config.version = ACCOUNT_VERSION;
+
+        let admin_role = &mut ctx.accounts.admin_role;
+        admin_role.scope = crate::ID;
+        admin_role.role = Role::RegistryAdmin;
+        admin_role.holder = config.authority;
+        admin_role.granted_by = config.authority;
+        admin_role.granted_at = Clock::get()?.unix_timestamp;
+        admin_role.bump = ctx.bumps.admin_role;
+
+        emit!(RoleGranted {
+            scope: admin_role.scope,
+            role: admin_role.role,
+            holder: admin_role.holder,
+            granted_by: admin_role.granted_by,
+        });
+
         msg!("Asset Registry initialized with authority: {}", config.authority);
         Ok(())
     }
 
+    /// Grant a registry-wide role (registry admin only)
+    pub fn grant_registry_role(
+        ctx: Context<GrantRegistryRole>,
+        role: Role,
+        holder: Pubkey,
+    ) -> Result<()> {
+        let role_assignment = &mut ctx.accounts.role_assignment;
+        role_assignment.scope = crate::ID;
+        role_assignment.role = role;
+        role_assignment.holder = holder;
+        role_assignment.granted_by = ctx.accounts.authority.key();
+        role_assignment.granted_at = Clock::get()?.unix_timestamp;
+        role_assignment.bump = ctx.bumps.role_assignment;
+
+        emit!(RoleGranted {
+            scope: role_assignment.scope,
+            role,
+            holder,
+            granted_by: role_assignment.granted_by,
+        });
+
+        msg!("Registry role granted to: {}", holder);
+        Ok(())
+    }
+
+    /// Revoke a registry-wide role (registry admin only)
+    pub fn revoke_registry_role(ctx: Context<RevokeRegistryRole>) -> Result<()> {
+        let role_assignment = &ctx.accounts.role_assignment;
+        require!(
+            !(role_assignment.role == Role::RegistryAdmin
+                && role_assignment.holder == ctx.accounts.authority.key()),
+            AssetRegistryError::CannotRevokeOwnAdminRole
+        );
+
+        emit!(RoleRevoked {
+            scope: role_assignment.scope,
+            role: role_assignment.role,
+            holder: role_assignment.holder,
+            revoked_by: ctx.accounts.authority.key(),
+        });
+
+        msg!("Registry role revoked from: {}", role_assignment.holder);
+        Ok(())
+    }
+
+    /// Grant an operational role scoped to a single asset (asset authority only)
+    pub fn grant_asset_role(
+        ctx: Context<GrantAssetRole>,
+        role: Role,
+        holder: Pubkey,
+    ) -> Result<()> {
+        require!(role.is_asset_scoped(), AssetRegistryError::InvalidRoleScope);
+
+        let role_assignment = &mut ctx.accounts.role_assignment;
+        role_assignment.scope = ctx.accounts.asset.mint;
+        role_assignment.role = role;
+        role_assignment.holder = holder;
+        role_assignment.granted_by = ctx.accounts.authority.key();
+        role_assignment.granted_at = Clock::get()?.unix_timestamp;
+        role_assignment.bump = ctx.bumps.role_assignment;
+
+        emit!(RoleGranted {
+            scope: role_assignment.scope,
+            role,
+            holder,
+            granted_by: role_assignment.granted_by,
+        });
+
+        msg!("Asset role granted to: {}", holder);
+        Ok(())
+    }
+
+    /// Revoke an operational role scoped to a single asset (asset authority only)
+    pub fn revoke_asset_role(ctx: Context<RevokeAssetRole>) -> Result<()> {
+        let role_assignment = &ctx.accounts.role_assignment;
+
+        emit!(RoleRevoked {
+            scope: role_assignment.scope,
+            role: role_assignment.role,
+            holder: role_assignment.holder,
+            revoked_by: ctx.accounts.authority.key(),
+        });
+
+        msg!("Asset role revoked from: {}", role_assignment.holder);
+        Ok(())
+    }
+
     /// Register a new asset for tokenization
     pub fn register_asset(
         ctx: Context<RegisterAsset>,
@@ -57,43 +187,72 @@ pub mod asset_registry {
         asset.status = AssetStatus::Pending;
         asset.created_at = Clock::get()?.unix_timestamp;
         asset.updated_at = Clock::get()?.unix_timestamp;
+        asset.status_changed_at = asset.created_at;
+        asset.status_reason = None;
+        asset.matured_at = None;
+        asset.defaulted_at = None;
+        asset.liquidation_started_at = None;
+        asset.redeemed_at = None;
         asset.bump = ctx.bumps.asset;
+        asset.version = ACCOUNT_VERSION;
+
+        // Link the mint configuration to its asset so issuance is capped by total_supply
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.asset = asset.key();
+
+        // Append to the global, issuer and asset type indexes for paged listing
+        let asset_key = asset.key();
+        let bumps = &ctx.bumps;
+        asset.issuer = asset.authority;
+        asset.sequence = append_to_index(
+            &mut ctx.accounts.global_index,
+            bumps.global_index,
+            &mut ctx.accounts.global_index_page,
+            bumps.global_index_page,
+            asset_key,
+        )?;
+        asset.issuer_sequence = append_to_index(
+            &mut ctx.accounts.issuer_index,
+            bumps.issuer_index,
+            &mut ctx.accounts.issuer_index_page,
+            bumps.issuer_index_page,
+            asset_key,
+        )?;
+        asset.type_sequence = append_to_index(
+            &mut ctx.accounts.type_index,
+            bumps.type_index,
+            &mut ctx.accounts.type_index_page,
+            bumps.type_index_page,
+            asset_key,
+        )?;
 
         // Update config
         let config = &mut ctx.accounts.config;
         config.total_assets += 1;
 
         emit!(AssetRegistered {
-            asset: asset.key(),
+            asset: asset_key,
             mint: asset.mint,
             authority: asset.authority,
             name: asset.name.clone(),
             total_value,
             total_supply,
+            sequence: asset.sequence,
         });
 
         msg!("Asset registered: {}", asset.name);
         Ok(())
     }
 
-    /// Update asset metadata
+    /// Update asset metadata (asset authority only)
     pub fn update_asset(
         ctx: Context<UpdateAsset>,
-        metadata_uri: Option<String>,
-        total_value: Option<u64>,
+        metadata_uri: String,
     ) -> Result<()> {
-        let asset = &mut ctx.accounts.asset;
-
-        if let Some(uri) = metadata_uri {
-            require!(uri.len() <= 256, AssetRegistryError::UriTooLong);
-            asset.metadata_uri = uri;
-        }
-
-        if let Some(value) = total_value {
-            require!(value > 0, AssetRegistryError::InvalidValue);
-            asset.total_value = value;
-        }
+        require!(metadata_uri.len() <= 256, AssetRegistryError::UriTooLong);
 
+        let asset = &mut ctx.accounts.asset;
+        asset.metadata_uri = metadata_uri;
         asset.updated_at = Clock::get()?.unix_timestamp;
 
         emit!(AssetUpdated {
@@ -104,451 +263,7241 @@ pub mod asset_registry {
         Ok(())
     }
 
-    /// Activate an asset (make it tradeable)
-    pub fn activate_asset(ctx: Context<UpdateAsset>) -> Result<()> {
-        let asset = &mut ctx.accounts.asset;
-        require!(asset.status == AssetStatus::Pending, AssetRegistryError::InvalidStatus);
-        
-        asset.status = AssetStatus::Active;
-        asset.updated_at = Clock::get()?.unix_timestamp;
+    /// Set the typed attributes of a real estate asset (asset authority only)
+    pub fn set_real_estate_attributes(
+        ctx: Context<SetRealEstateAttributes>,
+        address_hash: [u8; 32],
+        parcel_id: String,
+        square_footage: u64,
+        occupancy_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.asset.asset_type == AssetType::RealEstate,
+            AssetRegistryError::AssetTypeMismatch
+        );
+        require!(address_hash != [0u8; 32], AssetRegistryError::InvalidAttributes);
+        require!(
+            !parcel_id.is_empty() && parcel_id.len() <= 32,
+            AssetRegistryError::InvalidAttributes
+        );
+        require!(square_footage > 0, AssetRegistryError::InvalidAttributes);
+        require!(occupancy_bps <= BPS_DENOMINATOR, AssetRegistryError::InvalidAttributes);
 
-        emit!(AssetActivated {
-            asset: asset.key(),
-            activated_at: asset.updated_at,
-        });
+        let attributes = &mut ctx.accounts.attributes;
+        attributes.asset = ctx.accounts.asset.key();
+        attributes.address_hash = address_hash;
+        attributes.parcel_id = parcel_id;
+        attributes.square_footage = square_footage;
+        attributes.occupancy_bps = occupancy_bps;
+        attributes.updated_by = ctx.accounts.authority.key();
+        attributes.updated_at = Clock::get()?.unix_timestamp;
+        attributes.bump = ctx.bumps.attributes;
 
-        Ok(())
+        emit_attributes_updated(&ctx.accounts.asset, attributes.updated_by, attributes.updated_at)
     }
 
-    /// Freeze an asset (pause trading)
-    pub fn freeze_asset(ctx: Context<UpdateAsset>) -> Result<()> {
-        let asset = &mut ctx.accounts.asset;
-        require!(asset.status == AssetStatus::Active, AssetRegistryError::InvalidStatus);
-        
-        asset.status = AssetStatus::Frozen;
-        asset.updated_at = Clock::get()?.unix_timestamp;
-
-        emit!(AssetFrozen {
-            asset: asset.key(),
-            frozen_at: asset.updated_at,
-        });
-
-        Ok(())
-    }
+    /// Set the typed attributes of a receivables asset (asset authority only)
+    pub fn set_receivable_attributes(
+        ctx: Context<SetReceivableAttributes>,
+        obligor: Pubkey,
+        face_value: u64,
+        due_date: i64,
+        coupon_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.asset.asset_type == AssetType::Receivables,
+            AssetRegistryError::AssetTypeMismatch
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(obligor != Pubkey::default(), AssetRegistryError::InvalidAttributes);
+        require!(face_value > 0, AssetRegistryError::InvalidAttributes);
+        require!(due_date > now, AssetRegistryError::InvalidAttributes);
+        require!(coupon_bps <= BPS_DENOMINATOR, AssetRegistryError::InvalidAttributes);
 
-    /// Unfreeze an asset (resume trading)
-    pub fn unfreeze_asset(ctx: Context<UpdateAsset>) -> Result<()> {
-        let asset = &mut ctx.accounts.asset;
-        require!(asset.status == AssetStatus::Frozen, AssetRegistryError::InvalidStatus);
-        
-        asset.status = AssetStatus::Active;
-        asset.updated_at = Clock::get()?.unix_timestamp;
+        let attributes = &mut ctx.accounts.attributes;
+        attributes.asset = ctx.accounts.asset.key();
+        attributes.obligor = obligor;
+        attributes.face_value = face_value;
+        attributes.due_date = due_date;
+        attributes.coupon_bps = coupon_bps;
+        attributes.updated_by = ctx.accounts.authority.key();
+        attributes.updated_at = now;
+        attributes.bump = ctx.bumps.attributes;
 
-        Ok(())
+        emit_attributes_updated(&ctx.accounts.asset, attributes.updated_by, now)
     }
 
-    /// Burn/retire an asset
-    pub fn burn_asset(ctx: Context<BurnAsset>) -> Result<()> {
-        let asset = &mut ctx.accounts.asset;
+    /// Set the typed attributes of a securities asset (asset authority only)
+    /// The identifier's check digit is verified on-chain.
+    pub fn set_security_attributes(
+        ctx: Context<SetSecurityAttributes>,
+        identifier_type: SecurityIdentifierType,
+        identifier: String,
+        maturity: i64,
+        coupon_bps: u16,
+    ) -> Result<()> {
         require!(
-            asset.status != AssetStatus::Burned,
-            AssetRegistryError::AlreadyBurned
+            ctx.accounts.asset.asset_type == AssetType::Securities,
+            AssetRegistryError::AssetTypeMismatch
         );
-        
-        asset.status = AssetStatus::Burned;
-        asset.updated_at = Clock::get()?.unix_timestamp;
+        let now = Clock::get()?.unix_timestamp;
+        let valid_identifier = match identifier_type {
+            SecurityIdentifierType::Isin => is_valid_isin(&identifier),
+            SecurityIdentifierType::Cusip => is_valid_cusip(&identifier),
+        };
+        require!(valid_identifier, AssetRegistryError::InvalidSecurityIdentifier);
+        require!(maturity > now, AssetRegistryError::InvalidAttributes);
+        require!(coupon_bps <= BPS_DENOMINATOR, AssetRegistryError::InvalidAttributes);
 
-        emit!(AssetBurned {
-            asset: asset.key(),
-            burned_at: asset.updated_at,
-        });
+        let attributes = &mut ctx.accounts.attributes;
+        attributes.asset = ctx.accounts.asset.key();
+        attributes.identifier_type = identifier_type;
+        attributes.identifier = identifier;
+        attributes.maturity = maturity;
+        attributes.coupon_bps = coupon_bps;
+        attributes.updated_by = ctx.accounts.authority.key();
+        attributes.updated_at = now;
+        attributes.bump = ctx.bumps.attributes;
 
-        Ok(())
+        emit_attributes_updated(&ctx.accounts.asset, attributes.updated_by, now)
     }
 
-    /// Create a Token-2022 mint with transfer hook for compliance
-    /// This creates a new token mint with advanced features:
-    /// - Transfer hook pointing to compliance program
-    /// - Permanent delegate for regulatory freeze/seize
-    /// - Metadata extension for on-chain asset info
-    pub fn create_token_mint(
-        ctx: Context<CreateTokenMint>,
-        name: String,
-        symbol: String,
+    /// Attach a legal document to an asset (asset authority only)
+    /// Records are keyed by content hash so the same file cannot be registered twice.
+    pub fn add_document(
+        ctx: Context<AddDocument>,
+        document_type: DocumentType,
+        content_hash: [u8; 32],
         uri: String,
-        decimals: u8,
-        transfer_hook_program: Option<Pubkey>,
+        effective_at: i64,
     ) -> Result<()> {
-        require!(name.len() <= 32, AssetRegistryError::NameTooLong);
-        require!(symbol.len() <= 10, AssetRegistryError::SymbolTooLong);
-        require!(uri.len() <= 200, AssetRegistryError::UriTooLong);
+        require!(content_hash != [0u8; 32], AssetRegistryError::InvalidDocumentHash);
+        require!(uri.len() <= 256, AssetRegistryError::UriTooLong);
 
-        let mint_config = &mut ctx.accounts.mint_config;
-        mint_config.mint = ctx.accounts.mint.key();
-        mint_config.authority = ctx.accounts.authority.key();
-        mint_config.permanent_delegate = ctx.accounts.permanent_delegate.key();
-        mint_config.transfer_hook_program = transfer_hook_program;
-        mint_config.name = name.clone();
-        mint_config.symbol = symbol.clone();
-        mint_config.uri = uri.clone();
-        mint_config.decimals = decimals;
-        mint_config.is_frozen = false;
-        mint_config.created_at = Clock::get()?.unix_timestamp;
-        mint_config.bump = ctx.bumps.mint_config;
+        let document = &mut ctx.accounts.document;
+        document.asset = ctx.accounts.asset.key();
+        document.document_type = document_type;
+        document.content_hash = content_hash;
+        document.uri = uri;
+        document.version = 1;
+        document.effective_at = effective_at;
+        document.status = DocumentStatus::Active;
+        document.supersedes = None;
+        document.superseded_by = None;
+        document.ended_at = None;
+        document.added_by = ctx.accounts.authority.key();
+        document.added_at = Clock::get()?.unix_timestamp;
+        document.bump = ctx.bumps.document;
 
-        emit!(TokenMintCreated {
-            mint: ctx.accounts.mint.key(),
-            authority: ctx.accounts.authority.key(),
-            name,
-            symbol,
-            decimals,
-            transfer_hook_program,
+        emit!(DocumentAdded {
+            asset: document.asset,
+            document: document.key(),
+            document_type,
+            content_hash,
+            version: document.version,
+            effective_at,
         });
 
-        msg!("Token-2022 mint created: {}", ctx.accounts.mint.key());
+        msg!("Document added to {}", document.asset);
         Ok(())
     }
 
-    /// Set or update the transfer hook program for a mint
-    pub fn set_transfer_hook(
-        ctx: Context<UpdateMintConfig>,
-        transfer_hook_program: Pubkey,
+    /// Replace an active document with a new version (asset authority only)
+    /// The previous version stops governing the asset when the new one takes effect.
+    pub fn supersede_document(
+        ctx: Context<SupersedeDocument>,
+        content_hash: [u8; 32],
+        uri: String,
+        effective_at: i64,
     ) -> Result<()> {
-        let mint_config = &mut ctx.accounts.mint_config;
-        mint_config.transfer_hook_program = Some(transfer_hook_program);
+        require!(content_hash != [0u8; 32], AssetRegistryError::InvalidDocumentHash);
+        require!(uri.len() <= 256, AssetRegistryError::UriTooLong);
 
-        emit!(TransferHookUpdated {
-            mint: mint_config.mint,
-            transfer_hook_program,
+        let previous = &mut ctx.accounts.previous_document;
+        require!(
+            previous.status == DocumentStatus::Active,
+            AssetRegistryError::DocumentNotActive
+        );
+        require!(
+            effective_at >= previous.effective_at,
+            AssetRegistryError::InvalidEffectiveDate
+        );
+
+        let document = &mut ctx.accounts.document;
+        document.asset = previous.asset;
+        document.document_type = previous.document_type;
+        document.content_hash = content_hash;
+        document.uri = uri;
+        document.version = previous
+            .version
+            .checked_add(1)
+            .ok_or(AssetRegistryError::MathOverflow)?;
+        document.effective_at = effective_at;
+        document.status = DocumentStatus::Active;
+        document.supersedes = Some(previous.key());
+        document.superseded_by = None;
+        document.ended_at = None;
+        document.added_by = ctx.accounts.authority.key();
+        document.added_at = Clock::get()?.unix_timestamp;
+        document.bump = ctx.bumps.document;
+
+        previous.status = DocumentStatus::Superseded;
+        previous.superseded_by = Some(document.key());
+        previous.ended_at = Some(effective_at);
+
+        emit!(DocumentSuperseded {
+            asset: document.asset,
+            previous_document: previous.key(),
+            document: document.key(),
+            document_type: document.document_type,
+            content_hash,
+            version: document.version,
+            effective_at,
         });
 
-        msg!("Transfer hook set to: {}", transfer_hook_program);
+        msg!("Document superseded by version {}", document.version);
         Ok(())
     }
 
-    /// Freeze all transfers for a token using permanent delegate
-    pub fn freeze_mint(ctx: Context<UpdateMintConfig>) -> Result<()> {
-        let mint_config = &mut ctx.accounts.mint_config;
-        require!(!mint_config.is_frozen, AssetRegistryError::AlreadyFrozen);
-        
-        mint_config.is_frozen = true;
+    /// Withdraw an active document without a replacement (asset authority only)
+    pub fn revoke_document(ctx: Context<RevokeDocument>) -> Result<()> {
+        let document = &mut ctx.accounts.document;
+        require!(
+            document.status == DocumentStatus::Active,
+            AssetRegistryError::DocumentNotActive
+        );
 
-        emit!(MintFrozen {
-            mint: mint_config.mint,
-            frozen_at: Clock::get()?.unix_timestamp,
+        let now = Clock::get()?.unix_timestamp;
+        document.status = DocumentStatus::Revoked;
+        document.ended_at = Some(now);
+
+        emit!(DocumentRevoked {
+            asset: document.asset,
+            document: document.key(),
+            document_type: document.document_type,
+            content_hash: document.content_hash,
+            revoked_by: ctx.accounts.authority.key(),
+            revoked_at: now,
         });
 
-        msg!("Mint frozen: {}", mint_config.mint);
+        msg!("Document revoked for {}", document.asset);
         Ok(())
     }
 
-    /// Unfreeze transfers for a token
-    pub fn unfreeze_mint(ctx: Context<UpdateMintConfig>) -> Result<()> {
-        let mint_config = &mut ctx.accounts.mint_config;
-        require!(mint_config.is_frozen, AssetRegistryError::NotFrozen);
-        
-        mint_config.is_frozen = false;
+    /// Register a licensed appraiser (registry admin only)
+    pub fn register_appraiser(
+        ctx: Context<RegisterAppraiser>,
+        appraiser: Pubkey,
+        license_number: String,
+        license_expiry: i64,
+        asset_types: Vec<AssetType>,
+    ) -> Result<()> {
+        require!(license_number.len() <= 32, AssetRegistryError::LicenseNumberTooLong);
+        require!(
+            !asset_types.is_empty() && asset_types.len() <= MAX_APPRAISER_ASSET_TYPES,
+            AssetRegistryError::InvalidAssetTypes
+        );
+        require!(
+            license_expiry > Clock::get()?.unix_timestamp,
+            AssetRegistryError::InvalidExpiry
+        );
 
-        emit!(MintUnfrozen {
-            mint: mint_config.mint,
-            unfrozen_at: Clock::get()?.unix_timestamp,
+        let record = &mut ctx.accounts.appraiser_record;
+        record.appraiser = appraiser;
+        record.license_number = license_number;
+        record.license_expiry = license_expiry;
+        record.asset_types = asset_types;
+        record.is_active = true;
+        record.registered_by = ctx.accounts.authority.key();
+        record.registered_at = Clock::get()?.unix_timestamp;
+        record.bump = ctx.bumps.appraiser_record;
+
+        emit!(AppraiserRegistered {
+            appraiser,
+            license_expiry,
+            registered_by: record.registered_by,
         });
 
-        msg!("Mint unfrozen: {}", mint_config.mint);
+        msg!("Appraiser registered: {}", appraiser);
         Ok(())
     }
 
-    /// Mint tokens to a recipient (only authority)
-    pub fn mint_tokens(
-        ctx: Context<MintTokens>,
-        amount: u64,
+    /// Update an appraiser's license, coverage or active flag (registry admin only)
+    pub fn update_appraiser(
+        ctx: Context<UpdateAppraiser>,
+        license_expiry: Option<i64>,
+        asset_types: Option<Vec<AssetType>>,
+        is_active: Option<bool>,
     ) -> Result<()> {
-        let mint_config = &ctx.accounts.mint_config;
-        require!(!mint_config.is_frozen, AssetRegistryError::MintIsFrozen);
+        let record = &mut ctx.accounts.appraiser_record;
 
-        let seeds = &[
-            b"mint-authority",
-            mint_config.mint.as_ref(),
-            &[ctx.bumps.mint_authority],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        if let Some(expiry) = license_expiry {
+            record.license_expiry = expiry;
+        }
 
-        token_2022::mint_to(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token_2022::MintTo {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    to: ctx.accounts.recipient_token_account.to_account_info(),
-                    authority: ctx.accounts.mint_authority.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            amount,
-        )?;
+        if let Some(types) = asset_types {
+            require!(
+                !types.is_empty() && types.len() <= MAX_APPRAISER_ASSET_TYPES,
+                AssetRegistryError::InvalidAssetTypes
+            );
+            record.asset_types = types;
+        }
 
-        emit!(TokensMinted {
-            mint: ctx.accounts.mint.key(),
-            recipient: ctx.accounts.recipient.key(),
-            amount,
+        if let Some(active) = is_active {
+            record.is_active = active;
+        }
+
+        emit!(AppraiserUpdated {
+            appraiser: record.appraiser,
+            license_expiry: record.license_expiry,
+            is_active: record.is_active,
         });
 
-        msg!("Minted {} tokens to {}", amount, ctx.accounts.recipient.key());
+        msg!("Appraiser updated: {}", record.appraiser);
         Ok(())
     }
-}
 
-// ===========================================
-// ACCOUNTS
-// ===========================================
+    /// Record a valuation attested by a registered appraiser
+    /// The transaction must include an Ed25519 program instruction immediately
+    /// before this one, signed by the appraiser over
+    /// `asset || total_value (LE) || valuation_timestamp (LE) || report_hash`.
+    pub fn submit_valuation(
+        ctx: Context<SubmitValuation>,
+        total_value: u64,
+        method: ValuationMethod,
+        valuation_timestamp: i64,
+        report_hash: [u8; 32],
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let record = &ctx.accounts.appraiser_record;
+        let asset_key = ctx.accounts.asset.key();
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Config::INIT_SPACE,
-        seeds = [b"config"],
-        bump
-    )]
-    pub config: Account<'info, Config>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        require!(record.is_active, AssetRegistryError::AppraiserInactive);
+        require!(record.license_expiry > now, AssetRegistryError::AppraiserLicenseExpired);
+        require!(
+            record.asset_types.contains(&ctx.accounts.asset.asset_type),
+            AssetRegistryError::AppraiserNotQualified
+        );
+        require!(
+            valuation_timestamp <= now && now - valuation_timestamp <= MAX_VALUATION_AGE,
+            AssetRegistryError::StaleValuation
+        );
 
-#[derive(Accounts)]
-#[instruction(name: String)]
-pub struct RegisterAsset<'info> {
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
+        // Attestations must be newer than anything already on record
+        let history = &mut ctx.accounts.valuation_history;
+        if history.asset == Pubkey::default() {
+            history.asset = asset_key;
+            history.bump = ctx.bumps.valuation_history;
+        }
+        if let Some(latest) = history.latest() {
+            require!(
+                valuation_timestamp > latest.recorded_at,
+                AssetRegistryError::StaleValuation
+            );
+        }
 
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Asset::INIT_SPACE,
-        seeds = [b"asset", mint.key().as_ref()],
-        bump
-    )]
-    pub asset: Account<'info, Asset>,
+        let mut message = Vec::with_capacity(VALUATION_MESSAGE_LEN);
+        message.extend_from_slice(asset_key.as_ref());
+        message.extend_from_slice(&total_value.to_le_bytes());
+        message.extend_from_slice(&valuation_timestamp.to_le_bytes());
+        message.extend_from_slice(&report_hash);
+        verify_ed25519_instruction(&ctx.accounts.instructions, &record.appraiser, &message)?;
 
-    /// The token mint for this asset (Token-2022)
-    /// CHECK: Validated in instruction
-    pub mint: AccountInfo<'info>,
+        record_valuation(
+            &mut ctx.accounts.asset,
+            history,
+            total_value,
+            method,
+            record.appraiser,
+            report_hash,
+        )
+    }
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    /// Net asset value per whole token, scaled by `10^NAV_DECIMALS`,
+    /// in the same currency unit as `Asset.total_value`
+    pub fn get_nav_per_token(ctx: Context<GetNavPerToken>) -> Result<u64> {
+        let asset = &ctx.accounts.asset;
+        require!(asset.total_supply > 0, AssetRegistryError::InvalidSupply);
 
-    pub system_program: Program<'info, System>,
-}
+        let scale = 10u128
+            .checked_pow(ctx.accounts.mint_config.decimals as u32 + NAV_DECIMALS as u32)
+            .ok_or(AssetRegistryError::MathOverflow)?;
+        let nav = (asset.total_value as u128)
+            .checked_mul(scale)
+            .ok_or(AssetRegistryError::MathOverflow)?
+            / asset.total_supply as u128;
 
-#[derive(Accounts)]
-pub struct UpdateAsset<'info> {
-    #[account(
-        mut,
-        seeds = [b"asset", asset.mint.as_ref()],
-        bump = asset.bump,
-        has_one = authority
-    )]
-    pub asset: Account<'info, Asset>,
+        u64::try_from(nav).map_err(|_| error!(AssetRegistryError::MathOverflow))
+    }
 
-    pub authority: Signer<'info>,
-}
+    /// Set the oracle program whose price feeds assets may bind to (registry admin only)
+    pub fn set_pyth_program(ctx: Context<SetPythProgram>, pyth_program: Pubkey) -> Result<()> {
+        require!(pyth_program != Pubkey::default(), AssetRegistryError::InvalidOracleConfig);
 
-#[derive(Accounts)]
-pub struct BurnAsset<'info> {
-    #[account(
-        mut,
-        seeds = [b"asset", asset.mint.as_ref()],
-        bump = asset.bump,
-        has_one = authority
-    )]
-    pub asset: Account<'info, Asset>,
+        let config = &mut ctx.accounts.config;
+        config.pyth_program = pyth_program;
 
-    #[account(
+        emit!(PythProgramSet {
+            pyth_program,
+            updated_by: ctx.accounts.authority.key(),
+        });
+
+        msg!("Price feed program set to: {}", pyth_program);
+        Ok(())
+    }
+
+    /// Bind an asset to a Pyth-style price feed (valuation officer only)
+    /// `units` is the quantity of the underlying the asset represents;
+    /// `total_value` is refreshed as `units × price` in cents. The feed must be
+    /// owned by the oracle program pinned in `Config.pyth_program`.
+    pub fn bind_price_feed(
+        ctx: Context<BindPriceFeed>,
+        units: u64,
+        max_staleness: i64,
+        max_deviation_bps: u16,
+    ) -> Result<()> {
+        require!(units > 0, AssetRegistryError::InvalidAmount);
+        require!(max_staleness > 0, AssetRegistryError::InvalidOracleConfig);
+        require!(
+            max_deviation_bps > 0 && max_deviation_bps <= BPS_DENOMINATOR,
+            AssetRegistryError::InvalidOracleConfig
+        );
+
+        let pyth_program = ctx.accounts.config.pyth_program;
+        require!(pyth_program != Pubkey::default(), AssetRegistryError::PythProgramNotSet);
+        require_keys_eq!(
+            *ctx.accounts.price_feed.owner,
+            pyth_program,
+            AssetRegistryError::UnapprovedFeedProgram
+        );
+
+        // Reject accounts that are not price feeds up front
+        load_oracle_price(&ctx.accounts.price_feed)?;
+
+        let binding = &mut ctx.accounts.price_feed_binding;
+        binding.asset = ctx.accounts.asset.key();
+        binding.price_feed = ctx.accounts.price_feed.key();
+        binding.feed_program = *ctx.accounts.price_feed.owner;
+        binding.units = units;
+        binding.max_staleness = max_staleness;
+        binding.max_deviation_bps = max_deviation_bps;
+        binding.last_price = 0;
+        binding.last_expo = 0;
+        binding.last_publish_time = 0;
+        binding.last_update_slot = 0;
+        binding.bound_by = ctx.accounts.authority.key();
+        binding.bump = ctx.bumps.price_feed_binding;
+
+        emit!(PriceFeedBound {
+            asset: binding.asset,
+            price_feed: binding.price_feed,
+            units,
+            max_staleness,
+            max_deviation_bps,
+            bound_by: binding.bound_by,
+        });
+
+        msg!("Asset {} bound to price feed {}", binding.asset, binding.price_feed);
+        Ok(())
+    }
+
+    /// Remove an asset's price feed binding (valuation officer only)
+    pub fn unbind_price_feed(ctx: Context<UnbindPriceFeed>) -> Result<()> {
+        emit!(PriceFeedUnbound {
+            asset: ctx.accounts.price_feed_binding.asset,
+            price_feed: ctx.accounts.price_feed_binding.price_feed,
+            unbound_by: ctx.accounts.authority.key(),
+        });
+
+        msg!("Price feed unbound from {}", ctx.accounts.price_feed_binding.asset);
+        Ok(())
+    }
+
+    /// Refresh `Asset.total_value` from its bound price feed (permissionless crank)
+    /// Moves larger than the binding's deviation cap require a valuation officer co-signer.
+    pub fn refresh_valuation(ctx: Context<RefreshValuation>) -> Result<()> {
+        let clock = Clock::get()?;
+        let binding = &ctx.accounts.price_feed_binding;
+        let price = load_oracle_price(&ctx.accounts.price_feed)?;
+
+        require!(
+            price.publish_time <= clock.unix_timestamp
+                && clock.unix_timestamp - price.publish_time <= binding.max_staleness,
+            AssetRegistryError::StalePrice
+        );
+        require!(
+            price.publish_time > binding.last_publish_time,
+            AssetRegistryError::StalePrice
+        );
+
+        let total_value = price.value_of(binding.units)?;
+        let previous_value = ctx.accounts.asset.total_value;
+
+        // Large moves need a human in the loop
+        let mut cosigned_by = None;
+        if previous_value > 0 {
+            let change = (total_value as u128).abs_diff(previous_value as u128);
+            let deviation_bps = change * BPS_DENOMINATOR as u128 / previous_value as u128;
+            if deviation_bps > binding.max_deviation_bps as u128 {
+                let officer = ctx
+                    .accounts
+                    .officer
+                    .as_ref()
+                    .ok_or(AssetRegistryError::PriceDeviationExceeded)?;
+                let officer_role = ctx
+                    .accounts
+                    .officer_role
+                    .as_ref()
+                    .ok_or(AssetRegistryError::PriceDeviationExceeded)?;
+                require!(
+                    officer_role.authorizes(
+                        &officer.key(),
+                        Role::ValuationOfficer,
+                        Some(&ctx.accounts.asset.mint)
+                    ),
+                    AssetRegistryError::Unauthorized
+                );
+                cosigned_by = Some(officer.key());
+            }
+        }
+
+        let history = &mut ctx.accounts.valuation_history;
+        if history.asset == Pubkey::default() {
+            history.asset = ctx.accounts.asset.key();
+            history.bump = ctx.bumps.valuation_history;
+        }
+
+        record_valuation(
+            &mut ctx.accounts.asset,
+            history,
+            total_value,
+            ValuationMethod::MarkToMarket,
+            ctx.accounts.price_feed.key(),
+            [0u8; 32],
+        )?;
+
+        let binding = &mut ctx.accounts.price_feed_binding;
+        binding.last_price = price.price;
+        binding.last_expo = price.expo;
+        binding.last_publish_time = price.publish_time;
+        binding.last_update_slot = clock.slot;
+
+        emit!(OracleValuationRefreshed {
+            asset: binding.asset,
+            price_feed: binding.price_feed,
+            price: price.price,
+            expo: price.expo,
+            publish_time: price.publish_time,
+            total_value,
+            previous_value,
+            cosigned_by,
+            slot: clock.slot,
+        });
+
+        Ok(())
+    }
+
+    /// Register a custodian whose reserve attestations the registry accepts
+    /// (registry admin only)
+    pub fn register_custodian(
+        ctx: Context<RegisterCustodian>,
+        custodian: Pubkey,
+        name: String,
+    ) -> Result<()> {
+        require!(name.len() <= 64, AssetRegistryError::NameTooLong);
+
+        let record = &mut ctx.accounts.custodian_record;
+        record.custodian = custodian;
+        record.name = name;
+        record.is_active = true;
+        record.registered_by = ctx.accounts.authority.key();
+        record.registered_at = Clock::get()?.unix_timestamp;
+        record.bump = ctx.bumps.custodian_record;
+
+        emit!(CustodianRegistered {
+            custodian,
+            name: record.name.clone(),
+            registered_by: record.registered_by,
+        });
+
+        msg!("Custodian registered: {}", custodian);
+        Ok(())
+    }
+
+    /// Enable or disable a custodian's attestations (registry admin only)
+    pub fn update_custodian(ctx: Context<UpdateCustodian>, is_active: bool) -> Result<()> {
+        let record = &mut ctx.accounts.custodian_record;
+        record.is_active = is_active;
+
+        emit!(CustodianUpdated {
+            custodian: record.custodian,
+            is_active,
+        });
+
+        msg!("Custodian updated: {}", record.custodian);
+        Ok(())
+    }
+
+    /// Name the custodian holding an asset's collateral (asset authority only).
+    /// From then on minting needs a fresh attestation covering outstanding supply;
+    /// switching custodians discards the previous custodian's attestation.
+    pub fn assign_custodian(ctx: Context<AssignCustodian>) -> Result<()> {
+        let custodian = ctx.accounts.custodian_record.custodian;
+        let reserve = &mut ctx.accounts.reserve;
+
+        if reserve.custodian != custodian {
+            reserve.reserve_amount = 0;
+            reserve.attested_at = 0;
+            reserve.report_hash = [0; 32];
+        }
+        reserve.asset = ctx.accounts.asset.key();
+        reserve.mint = ctx.accounts.asset.mint;
+        reserve.custodian = custodian;
+        reserve.bump = ctx.bumps.reserve;
+
+        ctx.accounts.mint_config.requires_reserve = true;
+
+        emit!(CustodianAssigned {
+            asset: reserve.asset,
+            mint: reserve.mint,
+            custodian,
+            assigned_by: ctx.accounts.authority.key(),
+        });
+
+        msg!("Custodian {} assigned to {}", custodian, reserve.mint);
+        Ok(())
+    }
+
+    /// Record a reserve attestation signed by the asset's custodian
+    /// The transaction must include an Ed25519 program instruction immediately
+    /// before this one, signed by the custodian over
+    /// `"reserve" || asset || reserve_amount (LE) || attested_at (LE) || report_hash`.
+    /// `reserve_amount` is in token base units of collateral held.
+    pub fn attest_reserve(
+        ctx: Context<AttestReserve>,
+        reserve_amount: u64,
+        attested_at: i64,
+        report_hash: [u8; 32],
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let record = &ctx.accounts.custodian_record;
+        let asset_key = ctx.accounts.asset.key();
+
+        require!(record.is_active, AssetRegistryError::CustodianInactive);
+        require!(
+            attested_at <= now && now - attested_at <= MAX_RESERVE_AGE,
+            AssetRegistryError::StaleReserveAttestation
+        );
+
+        let reserve = &mut ctx.accounts.reserve;
+        require!(
+            attested_at > reserve.attested_at,
+            AssetRegistryError::StaleReserveAttestation
+        );
+
+        let mut message = Vec::with_capacity(RESERVE_MESSAGE_LEN);
+        message.extend_from_slice(RESERVE_MESSAGE_DOMAIN);
+        message.extend_from_slice(asset_key.as_ref());
+        message.extend_from_slice(&reserve_amount.to_le_bytes());
+        message.extend_from_slice(&attested_at.to_le_bytes());
+        message.extend_from_slice(&report_hash);
+        verify_ed25519_instruction(&ctx.accounts.instructions, &record.custodian, &message)?;
+
+        let previous_reserve = reserve.reserve_amount;
+        reserve.reserve_amount = reserve_amount;
+        reserve.attested_at = attested_at;
+        reserve.report_hash = report_hash;
+        reserve.attestation_count += 1;
+
+        let outstanding_supply = ctx.accounts.mint_config.outstanding_supply();
+        emit!(ReserveAttested {
+            asset: asset_key,
+            mint: reserve.mint,
+            custodian: reserve.custodian,
+            reserve_amount,
+            outstanding_supply,
+            attested_at,
+            report_hash,
+        });
+
+        if reserve_amount < previous_reserve || reserve_amount < outstanding_supply {
+            emit!(ReserveCoverageDropped {
+                asset: asset_key,
+                mint: reserve.mint,
+                previous_reserve,
+                reserve_amount,
+                outstanding_supply,
+            });
+        }
+
+        msg!("Reserve of {} attested for {}", reserve_amount, reserve.mint);
+        Ok(())
+    }
+
+    /// Activate an asset (make it tradeable)
+    /// Assets governed by an approver set are activated through `execute_activate_asset`.
+    pub fn activate_asset(ctx: Context<UpdateAsset>) -> Result<()> {
+        let asset = &mut ctx.accounts.asset;
+        require!(
+            asset.approver_set == Pubkey::default(),
+            AssetRegistryError::ApprovalRequired
+        );
+
+        activate(asset)
+    }
+
+    /// Freeze an asset (pause trading)
+    pub fn freeze_asset(ctx: Context<PauseAsset>) -> Result<()> {
+        let asset = &mut ctx.accounts.asset;
+        require!(asset.status == AssetStatus::Active, AssetRegistryError::InvalidStatus);
+
+        transition_status(asset, AssetStatus::Frozen, None)?;
+
+        emit!(AssetFrozen {
+            asset: asset.key(),
+            frozen_at: asset.updated_at,
+        });
+
+        Ok(())
+    }
+
+    /// Unfreeze an asset (resume trading)
+    pub fn unfreeze_asset(ctx: Context<PauseAsset>) -> Result<()> {
+        let asset = &mut ctx.accounts.asset;
+        require!(asset.status == AssetStatus::Frozen, AssetRegistryError::InvalidStatus);
+
+        transition_status(asset, AssetStatus::Active, None)?;
+
+        emit!(AssetUnfrozen {
+            asset: asset.key(),
+            unfrozen_at: asset.updated_at,
+        });
+
+        Ok(())
+    }
+
+    /// Move an asset through its debt lifecycle (asset authority only)
+    /// Covers maturity, default, liquidation and redemption, plus curing a default
+    /// back to Active. Other statuses have dedicated instructions.
+    pub fn set_lifecycle_status(
+        ctx: Context<UpdateAsset>,
+        status: AssetStatus,
+        reason: StatusChangeReason,
+    ) -> Result<()> {
+        let asset = &mut ctx.accounts.asset;
+        require!(
+            matches!(
+                status,
+                AssetStatus::Matured
+                    | AssetStatus::Defaulted
+                    | AssetStatus::InLiquidation
+                    | AssetStatus::Redeemed
+            ) || (status == AssetStatus::Active && asset.status == AssetStatus::Defaulted),
+            AssetRegistryError::InvalidStatusTransition
+        );
+
+        transition_status(asset, status, Some(reason))
+    }
+
+    /// Start retiring an asset (asset authority only)
+    /// Minting stops and holders' tokens can be burned until supply reaches zero
+    pub fn begin_retirement(ctx: Context<BeginRetirement>) -> Result<()> {
+        // Burns during a partial conversion would skew the split's supply check
+        require!(
+            ctx.accounts.mint_config.active_split == Pubkey::default(),
+            AssetRegistryError::SplitInProgress
+        );
+
+        let asset = &mut ctx.accounts.asset;
+        require!(
+            asset.status != AssetStatus::Burned,
+            AssetRegistryError::AlreadyBurned
+        );
+
+        transition_status(asset, AssetStatus::Retiring, None)?;
+
+        emit!(AssetRetirementStarted {
+            asset: asset.key(),
+            started_at: asset.updated_at,
+        });
+
+        Ok(())
+    }
+
+    /// Burn the caller's own tokens of a retiring asset
+    pub fn redeem_tokens(ctx: Context<RedeemTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, AssetRegistryError::InvalidAmount);
+        require!(
+            ctx.accounts.asset.status == AssetStatus::Retiring,
+            AssetRegistryError::AssetNotRetiring
+        );
+
+        token_2022::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_2022::Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        record_retirement_burn(
+            &mut ctx.accounts.mint_config,
+            ctx.accounts.holder_token_account.owner,
+            amount,
+            false,
+        )
+    }
+
+    /// Burn a holder's entire balance of a retiring asset via the permanent delegate
+    /// (asset authority only)
+    pub fn retire_holder_tokens(ctx: Context<RetireHolderTokens>) -> Result<()> {
+        require!(
+            ctx.accounts.asset.status == AssetStatus::Retiring,
+            AssetRegistryError::AssetNotRetiring
+        );
+
+        let amount = ctx.accounts.holder_token_account.amount;
+        require!(amount > 0, AssetRegistryError::InvalidAmount);
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[
+            b"permanent-delegate",
+            mint_key.as_ref(),
+            &[ctx.bumps.permanent_delegate],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_2022::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_2022::Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: ctx.accounts.permanent_delegate.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        record_retirement_burn(
+            &mut ctx.accounts.mint_config,
+            ctx.accounts.holder_token_account.owner,
+            amount,
+            true,
+        )
+    }
+
+    /// Mark a retiring asset as burned once no supply remains (asset authority only)
+    pub fn burn_asset(ctx: Context<BurnAsset>) -> Result<()> {
+        let asset = &mut ctx.accounts.asset;
+        require!(
+            asset.status != AssetStatus::Burned,
+            AssetRegistryError::AlreadyBurned
+        );
+        require!(
+            asset.status == AssetStatus::Retiring,
+            AssetRegistryError::AssetNotRetiring
+        );
+        require!(
+            ctx.accounts.mint_config.outstanding_supply() == 0 && ctx.accounts.mint.supply == 0,
+            AssetRegistryError::SupplyOutstanding
+        );
+
+        transition_status(asset, AssetStatus::Burned, None)?;
+
+        // Burned assets leave a tombstone so page positions stay stable
+        let asset_key = asset.key();
+        remove_from_index(
+            &mut ctx.accounts.global_index,
+            &mut ctx.accounts.global_index_page,
+            asset.sequence,
+            asset_key,
+        )?;
+        remove_from_index(
+            &mut ctx.accounts.issuer_index,
+            &mut ctx.accounts.issuer_index_page,
+            asset.issuer_sequence,
+            asset_key,
+        )?;
+        remove_from_index(
+            &mut ctx.accounts.type_index,
+            &mut ctx.accounts.type_index_page,
+            asset.type_sequence,
+            asset_key,
+        )?;
+
+        emit!(AssetBurned {
+            asset: asset.key(),
+            burned_at: asset.updated_at,
+        });
+
+        Ok(())
+    }
+
+    /// Close a burned asset and its mint config, returning rent to the asset authority
+    pub fn close_asset(ctx: Context<CloseAsset>) -> Result<()> {
+        require!(
+            ctx.accounts.asset.status == AssetStatus::Burned,
+            AssetRegistryError::InvalidStatus
+        );
+
+        emit!(AssetClosed {
+            asset: ctx.accounts.asset.key(),
+            mint: ctx.accounts.asset.mint,
+            closed_at: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Asset closed: {}", ctx.accounts.asset.key());
+        Ok(())
+    }
+
+    /// Create a Token-2022 mint with transfer hook for compliance
+    /// This allocates and initializes a new token mint with the RWA extensions:
+    /// - Transfer hook pointing to compliance program
+    /// - Permanent delegate (registry PDA) for regulatory forced transfers
+    /// - Metadata pointer and token metadata for on-chain asset info
+    /// The `mint-authority` PDA becomes mint, freeze and extension authority.
+    pub fn create_token_mint(
+        ctx: Context<CreateTokenMint>,
+        name: String,
+        symbol: String,
+        uri: String,
+        decimals: u8,
+        transfer_hook_program: Option<Pubkey>,
+        default_account_frozen: bool,
+    ) -> Result<()> {
+        require!(name.len() <= 32, AssetRegistryError::NameTooLong);
+        require!(symbol.len() <= 10, AssetRegistryError::SymbolTooLong);
+        require!(uri.len() <= 200, AssetRegistryError::UriTooLong);
+
+        let mint_key = ctx.accounts.mint.key();
+        let mint_authority_key = ctx.accounts.mint_authority.key();
+        let permanent_delegate_key = ctx.accounts.permanent_delegate.key();
+        let token_program_id = ctx.accounts.token_program.key();
+        let hook_program = transfer_hook_program.unwrap_or(compliance_program::ID);
+
+        // Fixed-size extensions are allocated up front; the token metadata TLV entry
+        // is variable length and reallocated by Token-2022, so only its rent is prefunded
+        let mut extensions = vec![
+            ExtensionType::TransferHook,
+            ExtensionType::PermanentDelegate,
+            ExtensionType::MetadataPointer,
+        ];
+        if default_account_frozen {
+            extensions.push(ExtensionType::DefaultAccountState);
+        }
+        let mint_space = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+        let token_metadata = TokenMetadata {
+            mint: mint_key,
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            ..Default::default()
+        };
+        let metadata_space = token_metadata.tlv_size_of()?;
+        let lamports = Rent::get()?.minimum_balance(mint_space + metadata_space);
+
+        system_program::create_account(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            lamports,
+            mint_space as u64,
+            &token_program_id,
+        )?;
+
+        let mint_info = ctx.accounts.mint.to_account_info();
+
+        // Extensions must be initialized before the mint itself
+        invoke(
+            &transfer_hook::instruction::initialize(
+                &token_program_id,
+                &mint_key,
+                Some(mint_authority_key),
+                Some(hook_program),
+            )?,
+            std::slice::from_ref(&mint_info),
+        )?;
+
+        invoke(
+            &spl_token_2022::instruction::initialize_permanent_delegate(
+                &token_program_id,
+                &mint_key,
+                &permanent_delegate_key,
+            )?,
+            std::slice::from_ref(&mint_info),
+        )?;
+
+        invoke(
+            &metadata_pointer::instruction::initialize(
+                &token_program_id,
+                &mint_key,
+                Some(mint_authority_key),
+                Some(mint_key),
+            )?,
+            std::slice::from_ref(&mint_info),
+        )?;
+
+        // New token accounts start frozen until the owner passes KYC
+        if default_account_frozen {
+            invoke(
+                &default_account_state::instruction::initialize_default_account_state(
+                    &token_program_id,
+                    &mint_key,
+                    &AccountState::Frozen,
+                )?,
+                std::slice::from_ref(&mint_info),
+            )?;
+        }
+
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_2022::InitializeMint2 {
+                    mint: mint_info.clone(),
+                },
+            ),
+            decimals,
+            &mint_authority_key,
+            Some(&mint_authority_key),
+        )?;
+
+        let seeds = &[
+            b"mint-authority",
+            mint_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::initialize(
+                &token_program_id,
+                &mint_key,
+                &mint_authority_key,
+                &mint_key,
+                &mint_authority_key,
+                name.clone(),
+                symbol.clone(),
+                uri.clone(),
+            ),
+            &[
+                mint_info.clone(),
+                ctx.accounts.mint_authority.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        validate_rwa_mint(&mint_info, &mint_authority_key)?;
+
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.mint = mint_key;
+        mint_config.authority = ctx.accounts.authority.key();
+        mint_config.permanent_delegate = permanent_delegate_key;
+        mint_config.transfer_hook_program = Some(hook_program);
+        mint_config.name = name.clone();
+        mint_config.symbol = symbol.clone();
+        mint_config.uri = uri;
+        mint_config.decimals = decimals;
+        mint_config.is_frozen = false;
+        mint_config.default_account_frozen = default_account_frozen;
+        mint_config.asset = Pubkey::default();
+        mint_config.minted_supply = 0;
+        mint_config.burned_supply = 0;
+        mint_config.created_at = Clock::get()?.unix_timestamp;
+        mint_config.bump = ctx.bumps.mint_config;
+        mint_config.version = ACCOUNT_VERSION;
+
+        emit!(TokenMintCreated {
+            mint: mint_key,
+            authority: ctx.accounts.authority.key(),
+            name,
+            symbol,
+            decimals,
+            transfer_hook_program: Some(hook_program),
+            default_account_frozen,
+        });
+
+        msg!("Token-2022 mint created: {}", mint_key);
+        Ok(())
+    }
+
+    /// Queue a new transfer hook program for a mint. It can be applied once
+    /// `TIMELOCK_DELAY` has passed, and a guardian may cancel it until then.
+    pub fn queue_transfer_hook(
+        ctx: Context<QueueTransferHook>,
+        transfer_hook_program: Pubkey,
+    ) -> Result<()> {
+        require_single_key(&ctx.accounts.mint_config, ctx.accounts.asset.as_deref())?;
+
+        queue_change(
+            &mut ctx.accounts.queued_change,
+            ctx.bumps.queued_change,
+            ctx.accounts.mint_config.mint,
+            TimelockedChange::TransferHook { transfer_hook_program },
+            ctx.accounts.authority.key(),
+        )
+    }
+
+    /// Apply a queued transfer hook change once its ETA has passed (permissionless)
+    pub fn apply_transfer_hook(ctx: Context<ApplyTransferHook>) -> Result<()> {
+        let queued_change = &ctx.accounts.queued_change;
+        require!(
+            Clock::get()?.unix_timestamp >= queued_change.eta,
+            AssetRegistryError::TimelockNotElapsed
+        );
+        let TimelockedChange::TransferHook { transfer_hook_program } = queued_change.change;
+
+        update_transfer_hook(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            &mut ctx.accounts.mint_config,
+            transfer_hook_program,
+        )?;
+
+        emit!(ChangeApplied {
+            queued_change: queued_change.key(),
+            target: queued_change.target,
+            change: queued_change.change.clone(),
+        });
+
+        Ok(())
+    }
+
+    /// Drop a queued change before it is applied (guardian only)
+    pub fn cancel_queued_change(ctx: Context<CancelQueuedChange>) -> Result<()> {
+        let queued_change = &ctx.accounts.queued_change;
+
+        emit!(ChangeCancelled {
+            queued_change: queued_change.key(),
+            target: queued_change.target,
+            change: queued_change.change.clone(),
+            cancelled_by: ctx.accounts.guardian.key(),
+        });
+
+        msg!("Queued change for {} cancelled", queued_change.target);
+        Ok(())
+    }
+
+    /// Freeze all transfers for a token using permanent delegate
+    /// Mints whose asset is governed by an approver set freeze through `execute_freeze_mint`.
+    pub fn freeze_mint(ctx: Context<FreezeMint>) -> Result<()> {
+        require_single_key(&ctx.accounts.mint_config, ctx.accounts.asset.as_deref())?;
+
+        freeze(&mut ctx.accounts.mint_config)
+    }
+
+    /// Unfreeze transfers for a token
+    pub fn unfreeze_mint(ctx: Context<UpdateMintConfig>) -> Result<()> {
+        let mint_config = &mut ctx.accounts.mint_config;
+        require!(mint_config.is_frozen, AssetRegistryError::NotFrozen);
+        // Old and new balances must not mix while a split is being converted
+        require!(
+            mint_config.active_split == Pubkey::default(),
+            AssetRegistryError::SplitInProgress
+        );
+
+        mint_config.is_frozen = false;
+
+        emit!(MintUnfrozen {
+            mint: mint_config.mint,
+            unfrozen_at: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Mint unfrozen: {}", mint_config.mint);
+        Ok(())
+    }
+
+    /// Freeze a single holder's token account (compliance officer only)
+    /// The mint authority PDA is the mint's freeze authority. A `HolderFreeze`
+    /// record exists for as long as the account stays frozen.
+    pub fn freeze_holder(
+        ctx: Context<FreezeHolder>,
+        reason: FreezeReason,
+        case_reference: String,
+    ) -> Result<()> {
+        require!(
+            !case_reference.is_empty() && case_reference.len() <= 64,
+            AssetRegistryError::InvalidCaseReference
+        );
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[
+            b"mint-authority",
+            mint_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_2022::freeze_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::FreezeAccount {
+                account: ctx.accounts.holder_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        let record = &mut ctx.accounts.holder_freeze;
+        record.mint = mint_key;
+        record.token_account = ctx.accounts.holder_token_account.key();
+        record.owner = ctx.accounts.holder_token_account.owner;
+        record.reason = reason;
+        record.case_reference = case_reference;
+        record.frozen_by = ctx.accounts.authority.key();
+        record.frozen_at = Clock::get()?.unix_timestamp;
+        record.bump = ctx.bumps.holder_freeze;
+
+        emit!(HolderFrozen {
+            mint: record.mint,
+            token_account: record.token_account,
+            owner: record.owner,
+            reason,
+            case_reference: record.case_reference.clone(),
+            frozen_by: record.frozen_by,
+            frozen_at: record.frozen_at,
+        });
+
+        msg!("Holder frozen: {}", record.owner);
+        Ok(())
+    }
+
+    /// Thaw a frozen holder's token account and close its freeze record
+    /// (compliance officer only)
+    pub fn thaw_holder(ctx: Context<ThawHolder>) -> Result<()> {
+        require_no_pending_conversion(&ctx.accounts.split_conversion)?;
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[
+            b"mint-authority",
+            mint_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_2022::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::ThawAccount {
+                account: ctx.accounts.holder_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        let record = &ctx.accounts.holder_freeze;
+        emit!(HolderThawed {
+            mint: record.mint,
+            token_account: record.token_account,
+            owner: record.owner,
+            case_reference: record.case_reference.clone(),
+            thawed_by: ctx.accounts.authority.key(),
+            thawed_at: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Holder thawed: {}", record.owner);
+        Ok(())
+    }
+
+    /// Thaw a default-frozen token account once its owner's compliance
+    /// whitelist entry is active. Callable by a KYC operator or by the
+    /// compliance program signing with its config PDA.
+    /// The registry's fee treasury and quarantine accounts need no whitelist entry.
+    pub fn thaw_kyc_account(ctx: Context<ThawKycAccount>) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let mint_key = ctx.accounts.mint.key();
+
+        let (compliance_signer, _) =
+            Pubkey::find_program_address(&[b"compliance-config"], &compliance_program::ID);
+        let is_operator = ctx
+            .accounts
+            .kyc_role
+            .as_ref()
+            .is_some_and(|role| role.authorizes(&authority, Role::KycOperator, Some(&mint_key)));
+        require!(
+            authority == compliance_signer || is_operator,
+            AssetRegistryError::Unauthorized
+        );
+
+        // Accounts under a legal hold are only released through `thaw_holder`
+        require!(
+            ctx.accounts.holder_freeze.data_is_empty(),
+            AssetRegistryError::AccountUnderLegalHold
+        );
+        require_no_pending_conversion(&ctx.accounts.split_conversion)?;
+
+        // Registry-owned fee treasury and quarantine accounts have no whitelist entry
+        let owner = ctx.accounts.holder_token_account.owner;
+        let (fee_treasury, _) = Pubkey::find_program_address(&[b"fee-treasury"], &crate::ID);
+        let (quarantine, _) =
+            Pubkey::find_program_address(&[b"quarantine", mint_key.as_ref()], &crate::ID);
+        require!(
+            owner == fee_treasury
+                || owner == quarantine
+                || is_whitelisted(&ctx.accounts.whitelist_entry, Clock::get()?.unix_timestamp)?,
+            AssetRegistryError::KycNotVerified
+        );
+
+        let seeds = &[
+            b"mint-authority",
+            mint_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_2022::thaw_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::ThawAccount {
+                account: ctx.accounts.holder_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        emit!(KycAccountThawed {
+            mint: mint_key,
+            token_account: ctx.accounts.holder_token_account.key(),
+            owner: ctx.accounts.holder_token_account.owner,
+            thawed_by: authority,
+        });
+
+        msg!("KYC account thawed: {}", ctx.accounts.holder_token_account.owner);
+        Ok(())
+    }
+
+    /// Mint tokens to a recipient (only authority)
+    /// Issuance is capped by the registered `Asset.total_supply`. With a `lockup`
+    /// schedule the issued lot is recorded against the recipient and held back
+    /// from transfers by the compliance hook until it vests.
+    pub fn mint_tokens(
+        ctx: Context<MintTokens>,
+        amount: u64,
+        lockup: Option<LockupSchedule>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.asset.approver_set == Pubkey::default(),
+            AssetRegistryError::ApprovalRequired
+        );
+
+        let fee_bps = ctx
+            .accounts
+            .config
+            .fee_bps_for(ctx.accounts.fee_override.as_deref());
+
+        issue_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            &ctx.accounts.recipient,
+            &ctx.accounts.recipient_token_account.to_account_info(),
+            &ctx.accounts.treasury_token_account,
+            fee_bps,
+            &ctx.accounts.asset,
+            &mut ctx.accounts.mint_config,
+            ctx.accounts.reserve.as_deref(),
+            ctx.accounts.lockup.as_deref_mut().map(|record| (record, ctx.bumps.lockup)),
+            amount,
+            lockup,
+        )
+    }
+
+    /// Mint to many recipients atomically (minter only)
+    /// Remaining accounts are `[recipient wallet, recipient token account, recipient
+    /// lockup]` triples in allocation order; the lockup PDA is created on first use
+    /// and only touched for allocations carrying a schedule. With
+    /// `create_missing_accounts`, absent token accounts must be the recipient's
+    /// associated token account and are created at the minter's expense.
+    pub fn batch_mint_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchMintTokens<'info>>,
+        allocations: Vec<MintAllocation>,
+        create_missing_accounts: bool,
+    ) -> Result<()> {
+        require!(
+            !allocations.is_empty() && allocations.len() <= MAX_BATCH_MINT_RECIPIENTS,
+            AssetRegistryError::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len() == allocations.len() * 3,
+            AssetRegistryError::InvalidBatchAccounts
+        );
+
+        let asset = &ctx.accounts.asset;
+        require!(asset.approver_set == Pubkey::default(), AssetRegistryError::ApprovalRequired);
+        // Accounts created here would start frozen and refuse the mint
+        require!(
+            !(create_missing_accounts && ctx.accounts.mint_config.default_account_frozen),
+            AssetRegistryError::FrozenAccountCreation
+        );
+
+        // The cap is enforced on the whole batch before anything is minted
+        let mut total_amount: u64 = 0;
+        for allocation in &allocations {
+            require!(allocation.amount > 0, AssetRegistryError::InvalidAmount);
+            total_amount = total_amount
+                .checked_add(allocation.amount)
+                .ok_or(AssetRegistryError::MathOverflow)?;
+        }
+        require_issuable(
+            asset,
+            &ctx.accounts.mint_config,
+            ctx.accounts.reserve.as_deref(),
+            total_amount,
+        )?;
+
+        let fee_bps = ctx
+            .accounts
+            .config
+            .fee_bps_for(ctx.accounts.fee_override.as_deref());
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[
+            b"mint-authority",
+            mint_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let mut total_fee: u64 = 0;
+        for (allocation, accounts) in allocations.iter().zip(ctx.remaining_accounts.chunks(3)) {
+            let (recipient, recipient_token_account, lockup) = (&accounts[0], &accounts[1], &accounts[2]);
+            require_keys_eq!(
+                recipient.key(),
+                allocation.recipient,
+                AssetRegistryError::InvalidBatchAccounts
+            );
+
+            if recipient_token_account.data_is_empty() {
+                require!(create_missing_accounts, AssetRegistryError::InvalidBatchAccounts);
+                require_keys_eq!(
+                    recipient_token_account.key(),
+                    get_associated_token_address_with_program_id(
+                        &allocation.recipient,
+                        &mint_key,
+                        &token_2022::ID,
+                    ),
+                    AssetRegistryError::InvalidBatchAccounts
+                );
+
+                associated_token::create(CpiContext::new(
+                    ctx.accounts.associated_token_program.to_account_info(),
+                    associated_token::Create {
+                        payer: ctx.accounts.authority.to_account_info(),
+                        associated_token: recipient_token_account.clone(),
+                        authority: recipient.clone(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        token_program: ctx.accounts.token_program.to_account_info(),
+                    },
+                ))?;
+            } else {
+                require_token_account(recipient_token_account, &mint_key, &allocation.recipient)?;
+            }
+
+            let fee = calculate_fee(allocation.amount, fee_bps)
+                .ok_or(AssetRegistryError::MathOverflow)?;
+            let recipient_amount = allocation.amount - fee;
+            total_fee += fee;
+
+            token_2022::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_2022::MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: recipient_token_account.clone(),
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                recipient_amount,
+            )?;
+
+            if let Some(schedule) = allocation.lockup {
+                record_batch_lockup(
+                    lockup,
+                    &ctx.accounts.authority,
+                    &ctx.accounts.system_program,
+                    &mint_key,
+                    &allocation.recipient,
+                    schedule,
+                    recipient_amount,
+                )?;
+            }
+
+            let mint_config = &mut ctx.accounts.mint_config;
+            mint_config.minted_supply = mint_config
+                .minted_supply
+                .checked_add(allocation.amount)
+                .ok_or(AssetRegistryError::MathOverflow)?;
+
+            emit!(TokensMinted {
+                mint: mint_key,
+                recipient: allocation.recipient,
+                amount: recipient_amount,
+                fee,
+                minted_supply: mint_config.minted_supply,
+                outstanding_supply: mint_config.outstanding_supply(),
+                total_supply: asset.total_supply,
+            });
+        }
+
+        // Fees for the whole batch land in the treasury in one transfer
+        if total_fee > 0 {
+            token_2022::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_2022::MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.treasury_token_account.to_account_info(),
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                total_fee,
+            )?;
+
+            emit!(FeeCollected {
+                source: FeeSource::Issuance,
+                asset_mint: mint_key,
+                fee_mint: mint_key,
+                amount: total_fee,
+                fee_bps,
+                treasury_account: ctx.accounts.treasury_token_account.key(),
+            });
+        }
+
+        let mint_config = &ctx.accounts.mint_config;
+        emit!(BatchMinted {
+            mint: mint_key,
+            recipients: allocations.len() as u16,
+            total_amount,
+            total_fee,
+            minted_supply: mint_config.minted_supply,
+            outstanding_supply: mint_config.outstanding_supply(),
+        });
+
+        msg!("Batch minted {} tokens to {} recipients", total_amount, allocations.len());
+        Ok(())
+    }
+
+    /// Tokens a holder may transfer now: balance less any unvested lockup lots
+    pub fn get_unlocked_balance(ctx: Context<GetUnlockedBalance>) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        let locked = load_locked_amount(&ctx.accounts.lockup, now)?;
+        Ok(ctx.accounts.holder_token_account.amount.saturating_sub(locked))
+    }
+
+    /// Announce a split of the asset's tokens (asset authority only).
+    /// From `effective_at`, with the mint frozen, every `old_units` tokens held
+    /// convert into `new_units`; a 1:10 split is `old_units = 1, new_units = 10`.
+    pub fn declare_split(
+        ctx: Context<DeclareSplit>,
+        old_units: u32,
+        new_units: u32,
+        effective_at: i64,
+    ) -> Result<()> {
+        require!(
+            old_units > 0 && new_units > 0 && old_units != new_units,
+            AssetRegistryError::InvalidSplitRatio
+        );
+        require!(
+            ctx.accounts.asset.status == AssetStatus::Active,
+            AssetRegistryError::AssetNotActive
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(effective_at >= now, AssetRegistryError::InvalidSplitEffectiveTime);
+
+        let mint_config = &mut ctx.accounts.mint_config;
+        require!(
+            mint_config.active_split == Pubkey::default(),
+            AssetRegistryError::SplitInProgress
+        );
+        require!(
+            mint_config.pending_split_conversions == 0,
+            AssetRegistryError::SplitConversionsPending
+        );
+
+        let split = &mut ctx.accounts.split;
+        split.mint = mint_config.mint;
+        split.index = mint_config.split_count;
+        split.old_units = old_units;
+        split.new_units = new_units;
+        split.effective_at = effective_at;
+        split.declared_by = ctx.accounts.authority.key();
+        split.declared_at = now;
+        split.holders_converted = 0;
+        split.converted_old_supply = 0;
+        split.converted_new_supply = 0;
+        split.destroyed_remainder = 0;
+        split.completed_at = None;
+        split.bump = ctx.bumps.split;
+
+        mint_config.active_split = split.key();
+        mint_config.split_count = mint_config
+            .split_count
+            .checked_add(1)
+            .ok_or(AssetRegistryError::MathOverflow)?;
+
+        emit!(SplitDeclared {
+            split: split.key(),
+            mint: split.mint,
+            index: split.index,
+            old_units,
+            new_units,
+            effective_at,
+            declared_by: split.declared_by,
+        });
+
+        msg!("Split {}:{} declared for {} effective {}", old_units, new_units, split.mint, effective_at);
+        Ok(())
+    }
+
+    /// Withdraw a split as long as no balance has been converted under it, even
+    /// once it has taken effect (asset authority only)
+    pub fn cancel_split(ctx: Context<CancelSplit>) -> Result<()> {
+        let split = &ctx.accounts.split;
+        require!(split.holders_converted == 0, AssetRegistryError::SplitConversionStarted);
+
+        ctx.accounts.mint_config.active_split = Pubkey::default();
+
+        emit!(SplitCancelled {
+            split: split.key(),
+            mint: split.mint,
+            index: split.index,
+        });
+
+        msg!("Split {} for {} cancelled", split.index, split.mint);
+        Ok(())
+    }
+
+    /// Convert a token account's balance at the split ratio (permissionless).
+    /// The old balance is burned through the permanent delegate and the new one
+    /// minted in its place, thawing a frozen account for the duration. The
+    /// owner's lockup lots are rescaled with the first account converted; the
+    /// payer funds the account's conversion receipt.
+    pub fn convert_split_balance(ctx: Context<ConvertSplitBalance>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let split = &ctx.accounts.split;
+        require!(now >= split.effective_at, AssetRegistryError::SplitNotEffective);
+        require!(ctx.accounts.mint_config.is_frozen, AssetRegistryError::NotFrozen);
+
+        let token_account = &ctx.accounts.holder_token_account;
+        let old_amount = token_account.amount;
+        let new_amount = split.convert(old_amount)?;
+        let refreeze = token_account.is_frozen();
+        rebalance(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            token_account,
+            (&ctx.accounts.permanent_delegate, ctx.bumps.permanent_delegate),
+            (&ctx.accounts.mint_authority, ctx.bumps.mint_authority),
+            old_amount,
+            new_amount,
+            refreeze,
+        )?;
+
+        // Unvested lots scale with the balance they lock
+        if ctx.accounts.lockup.owner == &crate::ID {
+            rescale_lockup(&ctx.accounts.lockup, split)?;
+        }
+
+        let conversion = &mut ctx.accounts.conversion;
+        conversion.split = split.key();
+        conversion.token_account = token_account.key();
+        conversion.holder = token_account.owner;
+        conversion.old_amount = old_amount;
+        conversion.new_amount = new_amount;
+        conversion.converted_at = now;
+        conversion.pending = false;
+        conversion.keep_frozen = refreeze;
+        conversion.bump = ctx.bumps.conversion;
+
+        record_conversion(
+            &mut ctx.accounts.mint_config,
+            &mut ctx.accounts.split,
+            &ctx.accounts.conversion,
+        )
+    }
+
+    /// Convert a balance left pending when its split was finalized (permissionless).
+    /// The account is thawed afterwards unless it was frozen before the split.
+    pub fn convert_pending_split_balance(ctx: Context<ConvertPendingSplitBalance>) -> Result<()> {
+        let split = &ctx.accounts.split;
+        let token_account = &ctx.accounts.holder_token_account;
+        let old_amount = token_account.amount;
+        let new_amount = split.convert(old_amount)?;
+        rebalance(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            token_account,
+            (&ctx.accounts.permanent_delegate, ctx.bumps.permanent_delegate),
+            (&ctx.accounts.mint_authority, ctx.bumps.mint_authority),
+            old_amount,
+            new_amount,
+            ctx.accounts.conversion.keep_frozen,
+        )?;
+
+        let conversion = &mut ctx.accounts.conversion;
+        conversion.old_amount = old_amount;
+        conversion.new_amount = new_amount;
+        conversion.converted_at = Clock::get()?.unix_timestamp;
+        conversion.pending = false;
+
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.pending_split_conversions = mint_config
+            .pending_split_conversions
+            .checked_sub(1)
+            .ok_or(AssetRegistryError::MathOverflow)?;
+
+        record_conversion(mint_config, &mut ctx.accounts.split, &ctx.accounts.conversion)
+    }
+
+    /// Finish a split once every balance is converted (permissionless).
+    /// Rescales the registered supply, so `get_nav_per_token` follows the new denomination.
+    pub fn complete_split(ctx: Context<CompleteSplit>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let split = &ctx.accounts.split;
+        require!(now >= split.effective_at, AssetRegistryError::SplitNotEffective);
+        require!(
+            ctx.accounts.mint.supply == split.converted_new_supply,
+            AssetRegistryError::SplitConversionIncomplete
+        );
+
+        let accounts = &mut *ctx.accounts;
+        finish_split(&mut accounts.asset, &mut accounts.mint_config, &mut accounts.split, now)
+    }
+
+    /// Finish a split with balances still unconverted (asset authority only).
+    /// Remaining accounts are `[token account, conversion receipt, owner lockup]`
+    /// triples whose balances must cover everything not yet converted. Each
+    /// account is frozen and given a pending receipt, funded by the authority,
+    /// and its owner's lockup lots are rescaled; the balance converts later
+    /// through `convert_pending_split_balance`. No further split can be declared
+    /// until every pending balance has converted.
+    pub fn finalize_split<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeSplit<'info>>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= ctx.accounts.split.effective_at,
+            AssetRegistryError::SplitNotEffective
+        );
+        require!(
+            ctx.remaining_accounts.chunks_exact(3).remainder().is_empty(),
+            AssetRegistryError::InvalidBatchAccounts
+        );
+
+        let mint_key = ctx.accounts.mint.key();
+        let split_key = ctx.accounts.split.key();
+        let authority_seeds = &[
+            b"mint-authority",
+            mint_key.as_ref(),
+            &[ctx.bumps.mint_authority],
+        ];
+        let mut unconverted_old_supply = 0u64;
+        for accounts in ctx.remaining_accounts.chunks(3) {
+            let (token_info, receipt_info, lockup_info) = (&accounts[0], &accounts[1], &accounts[2]);
+            require_keys_eq!(*token_info.owner, Token2022::id(), AssetRegistryError::InvalidBatchAccounts);
+            let token_account = TokenAccount::try_deserialize(&mut &token_info.try_borrow_data()?[..])?;
+            require_keys_eq!(token_account.mint, mint_key, AssetRegistryError::InvalidBatchAccounts);
+            unconverted_old_supply = unconverted_old_supply
+                .checked_add(token_account.amount)
+                .ok_or(AssetRegistryError::MathOverflow)?;
+
+            // Accounts converted already hold a receipt and cannot be counted again
+            let (receipt_key, receipt_bump) = Pubkey::find_program_address(
+                &[b"split-conversion", split_key.as_ref(), token_info.key.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(receipt_info.key(), receipt_key, AssetRegistryError::InvalidBatchAccounts);
+            require!(
+                receipt_info.data_is_empty(),
+                AssetRegistryError::SplitBalanceAlreadyConverted
+            );
+            let space = 8 + SplitConversion::INIT_SPACE;
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    CreateAccount {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: receipt_info.clone(),
+                    },
+                    &[&[
+                        b"split-conversion",
+                        split_key.as_ref(),
+                        token_info.key.as_ref(),
+                        &[receipt_bump],
+                    ]],
+                ),
+                Rent::get()?.minimum_balance(space),
+                space as u64,
+                &crate::ID,
+            )?;
+            let receipt = SplitConversion {
+                split: split_key,
+                token_account: token_info.key(),
+                holder: token_account.owner,
+                old_amount: token_account.amount,
+                new_amount: 0,
+                converted_at: 0,
+                pending: true,
+                keep_frozen: token_account.is_frozen(),
+                bump: receipt_bump,
+            };
+            receipt.try_serialize(&mut &mut receipt_info.try_borrow_mut_data()?[..])?;
+
+            // Old-denomination balances must not move once the mint is unfrozen
+            if !receipt.keep_frozen {
+                token_2022::freeze_account(CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_2022::FreezeAccount {
+                        account: token_info.clone(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    &[&authority_seeds[..]],
+                ))?;
+            }
+
+            let (lockup_key, _) = Pubkey::find_program_address(
+                &[b"lockup", mint_key.as_ref(), token_account.owner.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(lockup_info.key(), lockup_key, AssetRegistryError::InvalidBatchAccounts);
+            if lockup_info.owner == &crate::ID {
+                rescale_lockup(lockup_info, &ctx.accounts.split)?;
+            }
+        }
+
+        require!(
+            ctx.accounts
+                .split
+                .converted_new_supply
+                .checked_add(unconverted_old_supply)
+                == Some(ctx.accounts.mint.supply),
+            AssetRegistryError::SplitConversionIncomplete
+        );
+
+        let pending_accounts = (ctx.remaining_accounts.len() / 3) as u32;
+        let accounts = &mut *ctx.accounts;
+        accounts.mint_config.pending_split_conversions = pending_accounts;
+        finish_split(&mut accounts.asset, &mut accounts.mint_config, &mut accounts.split, now)?;
+
+        emit!(SplitFinalized {
+            split: split_key,
+            mint: mint_key,
+            pending_accounts,
+            unconverted_old_supply,
+            finalized_by: accounts.authority.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Set a per-asset platform fee overriding `Config.platform_fee_bps` (registry admin only)
+    pub fn set_fee_override(ctx: Context<SetFeeOverride>, fee_bps: u16) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, AssetRegistryError::InvalidFeeBps);
+
+        let fee_override = &mut ctx.accounts.fee_override;
+        fee_override.mint = ctx.accounts.mint.key();
+        fee_override.fee_bps = fee_bps;
+        fee_override.updated_by = ctx.accounts.authority.key();
+        fee_override.updated_at = Clock::get()?.unix_timestamp;
+        fee_override.bump = ctx.bumps.fee_override;
+
+        emit!(FeeOverrideSet {
+            mint: fee_override.mint,
+            fee_bps,
+            updated_by: fee_override.updated_by,
+        });
+
+        msg!("Fee override for {} set to {} bps", fee_override.mint, fee_bps);
+        Ok(())
+    }
+
+    /// Remove a per-asset fee override, reverting to the platform fee (registry admin only)
+    pub fn clear_fee_override(ctx: Context<ClearFeeOverride>) -> Result<()> {
+        emit!(FeeOverrideCleared {
+            mint: ctx.accounts.fee_override.mint,
+            cleared_by: ctx.accounts.authority.key(),
+        });
+
+        msg!("Fee override cleared for {}", ctx.accounts.fee_override.mint);
+        Ok(())
+    }
+
+    /// Withdraw collected fees from a fee treasury token account (registry admin only)
+    /// Accounts required by the mint's transfer hook are passed as remaining accounts.
+    pub fn withdraw_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawFees<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, AssetRegistryError::InvalidAmount);
+
+        let seeds = &[b"fee-treasury".as_ref(), &[ctx.bumps.fee_treasury]];
+        let signer_seeds = &[&seeds[..]];
+
+        invoke_transfer_checked(
+            &ctx.accounts.token_program.key(),
+            ctx.accounts.treasury_token_account.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.destination_token_account.to_account_info(),
+            ctx.accounts.fee_treasury.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.mint.decimals,
+            signer_seeds,
+        )?;
+
+        emit!(FeesWithdrawn {
+            mint: ctx.accounts.mint.key(),
+            amount,
+            destination: ctx.accounts.destination_token_account.key(),
+            withdrawn_by: ctx.accounts.authority.key(),
+        });
+
+        msg!("Withdrew {} fees to {}", amount, ctx.accounts.destination_token_account.key());
+        Ok(())
+    }
+
+    /// Propose a new registry config authority (current authority only)
+    pub fn propose_config_authority(
+        ctx: Context<ProposeConfigAuthority>,
+        new_authority: Pubkey,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        open_authority_transfer(
+            &mut ctx.accounts.authority_transfer,
+            ctx.accounts.config.key(),
+            AuthorityKind::Config,
+            ctx.accounts.authority.key(),
+            new_authority,
+            expires_at,
+            ctx.bumps.authority_transfer,
+        )
+    }
+
+    /// Accept a pending registry config authority transfer (proposed authority only)
+    pub fn accept_config_authority(ctx: Context<AcceptConfigAuthority>) -> Result<()> {
+        let transfer = &ctx.accounts.authority_transfer;
+        require!(
+            !transfer.is_expired(Clock::get()?.unix_timestamp),
+            AssetRegistryError::AuthorityTransferExpired
+        );
+
+        let config = &mut ctx.accounts.config;
+        config.authority = transfer.proposed_authority;
+
+        // Registry-admin power lives in the role assignment, so the handover
+        // moves it along with the config authority
+        let now = Clock::get()?.unix_timestamp;
+        let new_admin_role = &mut ctx.accounts.new_admin_role;
+        new_admin_role.scope = crate::ID;
+        new_admin_role.role = Role::RegistryAdmin;
+        new_admin_role.holder = transfer.proposed_authority;
+        new_admin_role.granted_by = transfer.current_authority;
+        new_admin_role.granted_at = now;
+        new_admin_role.bump = ctx.bumps.new_admin_role;
+
+        emit!(RoleGranted {
+            scope: new_admin_role.scope,
+            role: new_admin_role.role,
+            holder: new_admin_role.holder,
+            granted_by: new_admin_role.granted_by,
+        });
+
+        let old_admin_role = &ctx.accounts.old_admin_role;
+        if transfer.current_authority != transfer.proposed_authority
+            && old_admin_role.owner == &crate::ID
+            && !old_admin_role.data_is_empty()
+        {
+            close_program_account(
+                &old_admin_role.to_account_info(),
+                &ctx.accounts.current_authority,
+            )?;
+
+            emit!(RoleRevoked {
+                scope: crate::ID,
+                role: Role::RegistryAdmin,
+                holder: transfer.current_authority,
+                revoked_by: transfer.proposed_authority,
+            });
+        }
+
+        emit!(AuthorityTransferAccepted {
+            target: transfer.target,
+            kind: transfer.kind,
+            previous_authority: transfer.current_authority,
+            new_authority: transfer.proposed_authority,
+        });
+
+        msg!("Config authority transferred to: {}", config.authority);
+        Ok(())
+    }
+
+    /// Propose a new asset authority (current asset authority only)
+    pub fn propose_asset_authority(
+        ctx: Context<ProposeAssetAuthority>,
+        new_authority: Pubkey,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        open_authority_transfer(
+            &mut ctx.accounts.authority_transfer,
+            ctx.accounts.asset.key(),
+            AuthorityKind::Asset,
+            ctx.accounts.authority.key(),
+            new_authority,
+            expires_at,
+            ctx.bumps.authority_transfer,
+        )
+    }
+
+    /// Accept a pending asset authority transfer (proposed authority only)
+    pub fn accept_asset_authority(ctx: Context<AcceptAssetAuthority>) -> Result<()> {
+        let transfer = &ctx.accounts.authority_transfer;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            !transfer.is_expired(now),
+            AssetRegistryError::AuthorityTransferExpired
+        );
+
+        let asset = &mut ctx.accounts.asset;
+        asset.authority = transfer.proposed_authority;
+        asset.updated_at = now;
+
+        emit!(AuthorityTransferAccepted {
+            target: transfer.target,
+            kind: transfer.kind,
+            previous_authority: transfer.current_authority,
+            new_authority: transfer.proposed_authority,
+        });
+
+        msg!("Asset authority transferred to: {}", asset.authority);
+        Ok(())
+    }
+
+    /// Propose a new mint config authority (current mint config authority only)
+    pub fn propose_mint_config_authority(
+        ctx: Context<ProposeMintConfigAuthority>,
+        new_authority: Pubkey,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        open_authority_transfer(
+            &mut ctx.accounts.authority_transfer,
+            ctx.accounts.mint_config.key(),
+            AuthorityKind::MintConfig,
+            ctx.accounts.authority.key(),
+            new_authority,
+            expires_at,
+            ctx.bumps.authority_transfer,
+        )
+    }
+
+    /// Accept a pending mint config authority transfer (proposed authority only)
+    pub fn accept_mint_config_authority(ctx: Context<AcceptMintConfigAuthority>) -> Result<()> {
+        let transfer = &ctx.accounts.authority_transfer;
+        require!(
+            !transfer.is_expired(Clock::get()?.unix_timestamp),
+            AssetRegistryError::AuthorityTransferExpired
+        );
+
+        let mint_config = &mut ctx.accounts.mint_config;
+        mint_config.authority = transfer.proposed_authority;
+
+        emit!(AuthorityTransferAccepted {
+            target: transfer.target,
+            kind: transfer.kind,
+            previous_authority: transfer.current_authority,
+            new_authority: transfer.proposed_authority,
+        });
+
+        msg!("Mint config authority transferred to: {}", mint_config.authority);
+        Ok(())
+    }
+
+    /// Move tokens from any holder using the permanent delegate (compliance officer only)
+    /// Used for court orders and lost-key recoveries. Accounts required by the
+    /// mint's transfer hook are passed as remaining accounts.
+    pub fn force_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, ForceTransfer<'info>>,
+        amount: u64,
+        reason: ForcedTransferReason,
+        document_hash: [u8; 32],
+    ) -> Result<()> {
+        execute_forced_transfer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.source_token_account,
+            &ctx.accounts.destination_token_account,
+            &ctx.accounts.permanent_delegate,
+            ctx.bumps.permanent_delegate,
+            ctx.remaining_accounts,
+            amount,
+            reason,
+            document_hash,
+            ctx.accounts.authority.key(),
+            false,
+        )
+    }
+
+    /// Seize tokens from a holder into the mint's quarantine account (compliance officer only)
+    /// The quarantine token account must be owned by the `quarantine` PDA of the mint.
+    pub fn seize_to_quarantine<'info>(
+        ctx: Context<'_, '_, '_, 'info, SeizeToQuarantine<'info>>,
+        amount: u64,
+        reason: ForcedTransferReason,
+        document_hash: [u8; 32],
+    ) -> Result<()> {
+        execute_forced_transfer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.source_token_account,
+            &ctx.accounts.quarantine_token_account,
+            &ctx.accounts.permanent_delegate,
+            ctx.bumps.permanent_delegate,
+            ctx.remaining_accounts,
+            amount,
+            reason,
+            document_hash,
+            ctx.accounts.authority.key(),
+            true,
+        )
+    }
+
+    /// Cancel a pending authority transfer (proposing authority only)
+    /// Also used to clear expired proposals
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        let transfer = &ctx.accounts.authority_transfer;
+
+        emit!(AuthorityTransferCancelled {
+            target: transfer.target,
+            kind: transfer.kind,
+            proposed_authority: transfer.proposed_authority,
+            cancelled_by: ctx.accounts.current_authority.key(),
+        });
+
+        msg!("Authority transfer cancelled for: {}", transfer.target);
+        Ok(())
+    }
+
+    /// Install the registry-wide approver set (registry admin only)
+    /// The set governs the assets it is assigned to with `assign_registry_approver_set`,
+    /// acting through roles granted to its PDA once a proposal reaches its threshold.
+    pub fn create_registry_approver_set(
+        ctx: Context<CreateRegistryApproverSet>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        let approver_set = &mut ctx.accounts.approver_set;
+        approver_set.scope = crate::ID;
+        approver_set.proposal_count = 0;
+        approver_set.bump = ctx.bumps.approver_set;
+
+        set_approvers(approver_set, approvers, threshold)
+    }
+
+    /// Put an asset under the registry-wide approver set (registry admin only)
+    pub fn assign_registry_approver_set(ctx: Context<AssignRegistryApproverSet>) -> Result<()> {
+        let asset = &mut ctx.accounts.asset;
+        asset.approver_set = ctx.accounts.approver_set.key();
+
+        msg!("Asset {} governed by the registry approver set", asset.mint);
+        Ok(())
+    }
+
+    /// Install an asset's approver set (asset authority only)
+    /// From then on activation, minting, mint freezes and transfer hook changes for
+    /// the asset go only through proposals approved by the set, which acts through
+    /// roles granted to its PDA.
+    pub fn create_asset_approver_set(
+        ctx: Context<CreateAssetApproverSet>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        let approver_set = &mut ctx.accounts.approver_set;
+        approver_set.scope = ctx.accounts.asset.mint;
+        approver_set.proposal_count = 0;
+        approver_set.bump = ctx.bumps.approver_set;
+
+        ctx.accounts.asset.approver_set = approver_set.key();
+        set_approvers(approver_set, approvers, threshold)
+    }
+
+    /// Propose an action to an approver set (approvers only)
+    /// The proposer's own approval is recorded with the proposal.
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        action: ProposedAction,
+        expires_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at > now, AssetRegistryError::InvalidExpiry);
+
+        let approver_set = &mut ctx.accounts.approver_set;
+        require!(
+            action.allowed_in(&approver_set.scope),
+            AssetRegistryError::ApproverSetMismatch
+        );
+        let proposer = ctx.accounts.proposer.key();
+        let position = approver_set
+            .position_of(&proposer)
+            .ok_or(AssetRegistryError::NotAnApprover)?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.approver_set = approver_set.key();
+        proposal.id = approver_set.proposal_count;
+        proposal.action = action;
+        proposal.proposer = proposer;
+        proposal.approvals = 1 << position;
+        proposal.generation = approver_set.generation;
+        proposal.created_at = now;
+        proposal.expires_at = expires_at;
+        proposal.bump = ctx.bumps.proposal;
+
+        approver_set.proposal_count = approver_set
+            .proposal_count
+            .checked_add(1)
+            .ok_or(AssetRegistryError::MathOverflow)?;
+
+        emit!(ProposalCreated {
+            proposal: proposal.key(),
+            approver_set: proposal.approver_set,
+            id: proposal.id,
+            proposer,
+            action: proposal.action.clone(),
+            expires_at,
+        });
+
+        msg!("Proposal {} created", proposal.id);
+        Ok(())
+    }
+
+    /// Approve a pending proposal (approvers of its set only)
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        let approver_set = &ctx.accounts.approver_set;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.require_open(approver_set, Clock::get()?.unix_timestamp)?;
+
+        let approver = ctx.accounts.approver.key();
+        let position = approver_set
+            .position_of(&approver)
+            .ok_or(AssetRegistryError::NotAnApprover)?;
+        let bit = 1 << position;
+        require!(proposal.approvals & bit == 0, AssetRegistryError::AlreadyApproved);
+        proposal.approvals |= bit;
+
+        emit!(ProposalApproved {
+            proposal: proposal.key(),
+            approver,
+            approvals: proposal.approval_count(),
+            threshold: approver_set.threshold,
+        });
+
+        msg!("Proposal {} approved by {}", proposal.id, approver);
+        Ok(())
+    }
+
+    /// Withdraw a proposal before it executes (proposer only)
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+
+        emit!(ProposalCancelled {
+            proposal: proposal.key(),
+            approver_set: proposal.approver_set,
+            id: proposal.id,
+        });
+
+        msg!("Proposal {} cancelled", proposal.id);
+        Ok(())
+    }
+
+    /// Activate an asset through an approved proposal of its approver set
+    pub fn execute_activate_asset(ctx: Context<ExecuteActivateAsset>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        proposal.require_executable(&ctx.accounts.approver_set, Clock::get()?.unix_timestamp)?;
+        let ProposedAction::ActivateAsset { mint } = proposal.action else {
+            return err!(AssetRegistryError::ProposalActionMismatch);
+        };
+        require_keys_eq!(mint, ctx.accounts.asset.mint, AssetRegistryError::ProposalActionMismatch);
+
+        activate(&mut ctx.accounts.asset)?;
+        record_execution(proposal, ctx.accounts.executor.key());
+        Ok(())
+    }
+
+    /// Freeze a mint through an approved proposal; the set must hold the pauser role
+    pub fn execute_freeze_mint(ctx: Context<ExecuteFreezeMint>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        proposal.require_executable(&ctx.accounts.approver_set, Clock::get()?.unix_timestamp)?;
+        let ProposedAction::FreezeMint { mint } = proposal.action else {
+            return err!(AssetRegistryError::ProposalActionMismatch);
+        };
+        require_keys_eq!(mint, ctx.accounts.mint_config.mint, AssetRegistryError::ProposalActionMismatch);
+
+        freeze(&mut ctx.accounts.mint_config)?;
+        record_execution(proposal, ctx.accounts.executor.key());
+        Ok(())
+    }
+
+    /// Queue a mint's transfer hook change through an approved proposal; the set
+    /// must hold the compliance officer role. The change still waits out the timelock.
+    pub fn execute_set_transfer_hook(ctx: Context<ExecuteSetTransferHook>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        proposal.require_executable(&ctx.accounts.approver_set, Clock::get()?.unix_timestamp)?;
+        let ProposedAction::SetTransferHook { mint, transfer_hook_program } = proposal.action else {
+            return err!(AssetRegistryError::ProposalActionMismatch);
+        };
+        require_keys_eq!(mint, ctx.accounts.mint_config.mint, AssetRegistryError::ProposalActionMismatch);
+
+        queue_change(
+            &mut ctx.accounts.queued_change,
+            ctx.bumps.queued_change,
+            mint,
+            TimelockedChange::TransferHook { transfer_hook_program },
+            ctx.accounts.executor.key(),
+        )?;
+        record_execution(proposal, ctx.accounts.executor.key());
+        Ok(())
+    }
+
+    /// Mint tokens through an approved proposal; the set must hold the minter role
+    /// The executor pays for the recipient's lockup record when one is created.
+    pub fn execute_mint_tokens(ctx: Context<ExecuteMintTokens>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        proposal.require_executable(&ctx.accounts.approver_set, Clock::get()?.unix_timestamp)?;
+        let ProposedAction::MintTokens { mint, recipient, amount, lockup } = proposal.action else {
+            return err!(AssetRegistryError::ProposalActionMismatch);
+        };
+        require_keys_eq!(mint, ctx.accounts.mint.key(), AssetRegistryError::ProposalActionMismatch);
+        require_keys_eq!(recipient, ctx.accounts.recipient.key(), AssetRegistryError::ProposalActionMismatch);
+
+        let fee_bps = ctx
+            .accounts
+            .config
+            .fee_bps_for(ctx.accounts.fee_override.as_deref());
+
+        issue_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            &ctx.accounts.recipient,
+            &ctx.accounts.recipient_token_account.to_account_info(),
+            &ctx.accounts.treasury_token_account,
+            fee_bps,
+            &ctx.accounts.asset,
+            &mut ctx.accounts.mint_config,
+            ctx.accounts.reserve.as_deref(),
+            ctx.accounts.lockup.as_deref_mut().map(|record| (record, ctx.bumps.lockup)),
+            amount,
+            lockup,
+        )?;
+        record_execution(&ctx.accounts.proposal, ctx.accounts.executor.key());
+        Ok(())
+    }
+
+    /// Replace the approvers and threshold of a set through one of its own proposals
+    /// Proposals still open under the old approvers can no longer execute.
+    pub fn execute_update_approvers(ctx: Context<ExecuteUpdateApprovers>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        proposal.require_executable(&ctx.accounts.approver_set, Clock::get()?.unix_timestamp)?;
+        let ProposedAction::UpdateApprovers { approvers, threshold } = &proposal.action else {
+            return err!(AssetRegistryError::ProposalActionMismatch);
+        };
+
+        set_approvers(&mut ctx.accounts.approver_set, approvers.clone(), *threshold)?;
+        record_execution(proposal, ctx.accounts.executor.key());
+        Ok(())
+    }
+
+    /// Upgrade the registry config to the current account layout
+    /// Permissionless; the payer funds any extra rent
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        migrate::<Config>(&accounts.config, &accounts.payer, &accounts.system_program)?;
+        msg!("Config migrated to layout v{}", ACCOUNT_VERSION);
+        Ok(())
+    }
+
+    /// Upgrade an asset to the current account layout
+    /// Permissionless; the payer funds any extra rent
+    pub fn migrate_asset(ctx: Context<MigrateAsset>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        migrate::<Asset>(&accounts.asset, &accounts.payer, &accounts.system_program)?;
+        msg!("Asset {} migrated to layout v{}", accounts.asset.key(), ACCOUNT_VERSION);
+        Ok(())
+    }
+
+    /// Upgrade a mint configuration to the current account layout
+    /// Permissionless; the payer funds any extra rent
+    pub fn migrate_mint_config(ctx: Context<MigrateMintConfig>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        migrate::<MintConfig>(&accounts.mint_config, &accounts.payer, &accounts.system_program)?;
+        msg!("Mint config {} migrated to layout v{}", accounts.mint_config.key(), ACCOUNT_VERSION);
+        Ok(())
+    }
+}
+
+// ===========================================
+// HELPERS
+// ===========================================
+
+/// Basis point denominator (100%)
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+/// Highest fee the platform may charge (100%)
+pub const MAX_FEE_BPS: u16 = BPS_DENOMINATOR;
+
+/// Extra decimal places of precision in `get_nav_per_token`
+pub const NAV_DECIMALS: u8 = 6;
+
+/// Number of valuations retained in an asset's valuation history
+pub const VALUATION_HISTORY_LEN: usize = 32;
+
+/// Asset types a single appraiser may be licensed for
+pub const MAX_APPRAISER_ASSET_TYPES: usize = 7;
+
+/// Oldest appraiser attestation accepted by `submit_valuation` (7 days)
+pub const MAX_VALUATION_AGE: i64 = 7 * 24 * 60 * 60;
+
+/// Asset keys held by each asset index page
+pub const ASSET_INDEX_PAGE_LEN: usize = 32;
+
+/// Layout version of `Config`, `Asset` and `MintConfig` written by this program.
+/// Version 0 is the layout before versioning; new fields are carved out of the
+/// reserved padding and picked up by bumping this and calling `migrate_*`.
+pub const ACCOUNT_VERSION: u8 = 6;
+
+/// Most approvers an approver set may hold (one bit each in `Proposal.approvals`)
+pub const MAX_APPROVERS: usize = 16;
+
+/// Notice given before a queued change can be applied (48 hours)
+pub const TIMELOCK_DELAY: i64 = 2 * 24 * 60 * 60;
+
+/// Most unvested lockup lots a holder may carry per mint
+pub const MAX_LOCKUP_LOTS: usize = 8;
+
+/// Most recipients a single `batch_mint_tokens` call may serve
+pub const MAX_BATCH_MINT_RECIPIENTS: usize = 32;
+
+/// Signed valuation message: asset, value, timestamp and report hash
+pub const VALUATION_MESSAGE_LEN: usize = 32 + 8 + 8 + 32;
+
+/// Oldest custodian attestation that still allows issuance (7 days)
+pub const MAX_RESERVE_AGE: i64 = 7 * 24 * 60 * 60;
+
+/// Prefix of signed reserve messages, keeping them distinct from valuations
+pub const RESERVE_MESSAGE_DOMAIN: &[u8] = b"reserve";
+
+/// Signed reserve message: domain, asset, reserve amount, timestamp and report hash
+pub const RESERVE_MESSAGE_LEN: usize = RESERVE_MESSAGE_DOMAIN.len() + 32 + 8 + 8 + 32;
+
+/// Pyth price account magic number
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+/// Supported Pyth account layout version
+const PYTH_VERSION: u32 = 2;
+/// Pyth account type tag for price accounts
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+/// Pyth aggregate status for a live price
+const PYTH_STATUS_TRADING: u32 = 1;
+/// Bytes of a Pyth price account read by the registry
+const PYTH_PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+
+/// Ensure a mint carries the extensions every RWA token relies on and is
+/// controlled by the registry's mint authority PDA
+pub fn validate_rwa_mint(mint_info: &AccountInfo, mint_authority: &Pubkey) -> Result<()> {
+    require_keys_eq!(
+        *mint_info.owner,
+        token_2022::ID,
+        AssetRegistryError::InvalidMint
+    );
+
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+
+    require!(
+        mint.base.mint_authority == Some(*mint_authority).into(),
+        AssetRegistryError::InvalidMintAuthority
+    );
+
+    let transfer_hook = mint
+        .get_extension::<TransferHook>()
+        .map_err(|_| error!(AssetRegistryError::MissingMintExtension))?;
+    require!(
+        Option::<Pubkey>::from(transfer_hook.program_id).is_some(),
+        AssetRegistryError::MissingMintExtension
+    );
+
+    let permanent_delegate = mint
+        .get_extension::<PermanentDelegate>()
+        .map_err(|_| error!(AssetRegistryError::MissingMintExtension))?;
+    require!(
+        Option::<Pubkey>::from(permanent_delegate.delegate).is_some(),
+        AssetRegistryError::MissingMintExtension
+    );
+
+    let metadata_pointer = mint
+        .get_extension::<MetadataPointer>()
+        .map_err(|_| error!(AssetRegistryError::MissingMintExtension))?;
+    require!(
+        Option::<Pubkey>::from(metadata_pointer.metadata_address) == Some(mint_info.key()),
+        AssetRegistryError::MissingMintExtension
+    );
+
+    mint.get_variable_len_extension::<TokenMetadata>()
+        .map_err(|_| error!(AssetRegistryError::MissingMintExtension))?;
+
+    Ok(())
+}
+
+/// Check that the instruction preceding the current one is an Ed25519 program
+/// instruction verifying a single signature by `signer` over exactly `message`
+fn verify_ed25519_instruction(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions)?;
+    require!(current_index > 0, AssetRegistryError::MissingSignatureVerification);
+
+    let ix = load_instruction_at_checked(current_index as usize - 1, instructions)?;
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        AssetRegistryError::MissingSignatureVerification
+    );
+
+    // Layout: num_signatures (u8), padding (u8), then one offsets struct of
+    // seven u16 fields, followed by the signature, public key and message
+    let data = &ix.data;
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_LEN && data[0] == 1,
+        AssetRegistryError::InvalidSignatureVerification
+    );
+    let read_u16 = |field: usize| {
+        let at = ED25519_OFFSETS_START + field * 2;
+        u16::from_le_bytes([data[at], data[at + 1]])
+    };
+    let signature_ix_index = read_u16(1);
+    let public_key_offset = read_u16(2) as usize;
+    let public_key_ix_index = read_u16(3);
+    let message_offset = read_u16(4) as usize;
+    let message_size = read_u16(5) as usize;
+    let message_ix_index = read_u16(6);
+
+    // Signature, key and message must all live in the Ed25519 instruction itself
+    require!(
+        signature_ix_index == u16::MAX
+            && public_key_ix_index == u16::MAX
+            && message_ix_index == u16::MAX,
+        AssetRegistryError::InvalidSignatureVerification
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(AssetRegistryError::InvalidSignatureVerification)?;
+    require!(
+        public_key == signer.as_ref(),
+        AssetRegistryError::InvalidSignatureVerification
+    );
+
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(AssetRegistryError::InvalidSignatureVerification)?;
+    require!(
+        signed_message == message,
+        AssetRegistryError::InvalidSignatureVerification
+    );
+
+    Ok(())
+}
+
+/// Set the asset's current valuation and append it to the valuation history
+fn record_valuation(
+    asset: &mut Asset,
+    history: &mut ValuationHistory,
+    total_value: u64,
+    method: ValuationMethod,
+    appraiser: Pubkey,
+    report_hash: [u8; 32],
+) -> Result<()> {
+    require!(total_value > 0, AssetRegistryError::InvalidValue);
+
+    let now = Clock::get()?.unix_timestamp;
+    let previous_value = asset.total_value;
+    asset.total_value = total_value;
+    asset.updated_at = now;
+
+    history.push(ValuationEntry {
+        value: total_value,
+        recorded_at: now,
+        method,
+        appraiser,
+        report_hash,
+    });
+
+    emit!(ValuationRecorded {
+        asset: history.asset,
+        value: total_value,
+        previous_value,
+        method,
+        appraiser,
+        report_hash,
+        sequence: history.total_recorded,
+        recorded_at: now,
+    });
+
+    msg!("Valuation recorded for {}: {}", history.asset, total_value);
+    Ok(())
+}
+
+/// Aggregate price read from a Pyth-style price account
+pub struct OraclePrice {
+    /// Price mantissa
+    pub price: i64,
+    /// Power-of-ten exponent applied to `price`
+    pub expo: i32,
+    /// Confidence interval, same units as `price`
+    pub conf: u64,
+    /// Publish timestamp of the aggregate price
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    /// Value of `units` of the underlying in cents, rounded down
+    pub fn value_of(&self, units: u64) -> Result<u64> {
+        let cents = (self.price as u128)
+            .checked_mul(units as u128)
+            .and_then(|v| v.checked_mul(100))
+            .ok_or(AssetRegistryError::MathOverflow)?;
+        let scale = 10u128
+            .checked_pow(self.expo.unsigned_abs())
+            .ok_or(AssetRegistryError::MathOverflow)?;
+        let value = if self.expo < 0 {
+            cents / scale
+        } else {
+            cents.checked_mul(scale).ok_or(AssetRegistryError::MathOverflow)?
+        };
+        u64::try_from(value).map_err(|_| error!(AssetRegistryError::MathOverflow))
+    }
+}
+
+/// Parse the aggregate price of a Pyth v2 price account
+pub fn load_oracle_price(price_feed: &AccountInfo) -> Result<OraclePrice> {
+    let data = price_feed.try_borrow_data()?;
+    require!(
+        data.len() >= PYTH_PRICE_ACCOUNT_MIN_LEN,
+        AssetRegistryError::InvalidPriceFeed
+    );
+
+    let read_u32 = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap());
+    let read_u64 = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+
+    require!(
+        read_u32(0) == PYTH_MAGIC
+            && read_u32(4) == PYTH_VERSION
+            && read_u32(8) == PYTH_ACCOUNT_TYPE_PRICE,
+        AssetRegistryError::InvalidPriceFeed
+    );
+    require!(
+        read_u32(224) == PYTH_STATUS_TRADING,
+        AssetRegistryError::PriceUnavailable
+    );
+
+    let price = OraclePrice {
+        price: read_u64(208) as i64,
+        expo: read_u32(20) as i32,
+        conf: read_u64(216),
+        publish_time: read_u64(96) as i64,
+    };
+    require!(price.price > 0, AssetRegistryError::PriceUnavailable);
+
+    Ok(price)
+}
+
+/// Move an asset to `next` if the lifecycle table allows it, stamping the transition
+fn transition_status(
+    asset: &mut Account<Asset>,
+    next: AssetStatus,
+    reason: Option<StatusChangeReason>,
+) -> Result<()> {
+    let previous = asset.status;
+    require!(
+        previous.can_transition_to(next),
+        AssetRegistryError::InvalidStatusTransition
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    asset.status = next;
+    asset.status_reason = reason;
+    asset.status_changed_at = now;
+    asset.updated_at = now;
+    match next {
+        AssetStatus::Matured => asset.matured_at = Some(now),
+        AssetStatus::Defaulted => asset.defaulted_at = Some(now),
+        AssetStatus::InLiquidation => asset.liquidation_started_at = Some(now),
+        AssetStatus::Redeemed => asset.redeemed_at = Some(now),
+        _ => {}
+    }
+
+    emit!(AssetStatusChanged {
+        asset: asset.key(),
+        previous_status: previous,
+        status: next,
+        reason,
+        changed_at: now,
+    });
+
+    Ok(())
+}
+
+/// Status of the registry asset at `asset_info`, or `None` for a mint that was
+/// never registered. Lets other programs gate trading on registered assets
+/// without requiring every mint to be one.
+pub fn load_asset_status(asset_info: &AccountInfo) -> Result<Option<AssetStatus>> {
+    if asset_info.owner != &crate::ID || asset_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    let data = asset_info.try_borrow_data()?;
+    let asset = Asset::try_deserialize(&mut &data[..])?;
+    Ok(Some(asset.status))
+}
+
+/// Whether the registry mint config at `mint_config_info` has transfers frozen;
+/// `false` for a mint the registry never created
+pub fn load_mint_frozen(mint_config_info: &AccountInfo) -> Result<bool> {
+    if mint_config_info.owner != &crate::ID || mint_config_info.data_is_empty() {
+        return Ok(false);
+    }
+
+    let data = mint_config_info.try_borrow_data()?;
+    let mint_config = MintConfig::try_deserialize(&mut &data[..])?;
+    Ok(mint_config.is_frozen)
+}
+
+/// Append `asset` to an index, returning its position. Index and page fields are
+/// rewritten on every append so freshly created accounts need no separate setup.
+fn append_to_index(
+    index: &mut Account<AssetIndex>,
+    index_bump: u8,
+    page: &mut Account<AssetIndexPage>,
+    page_bump: u8,
+    asset: Pubkey,
+) -> Result<u64> {
+    let position = index.count;
+    page.index = index.key();
+    page.page = AssetIndex::page_of(position);
+    page.bump = page_bump;
+    page.assets.push(asset);
+
+    index.count += 1;
+    index.active += 1;
+    index.bump = index_bump;
+    Ok(position)
+}
+
+/// Replace `asset` at `position` with a tombstone
+fn remove_from_index(
+    index: &mut Account<AssetIndex>,
+    page: &mut Account<AssetIndexPage>,
+    position: u64,
+    asset: Pubkey,
+) -> Result<()> {
+    let slot = (position % ASSET_INDEX_PAGE_LEN as u64) as usize;
+    require!(
+        page.assets.get(slot) == Some(&asset),
+        AssetRegistryError::AssetIndexMismatch
+    );
+
+    page.assets[slot] = Pubkey::default();
+    index.active = index.active.saturating_sub(1);
+    Ok(())
+}
+
+/// Move a pending asset to active
+fn activate(asset: &mut Account<Asset>) -> Result<()> {
+    require!(asset.status == AssetStatus::Pending, AssetRegistryError::InvalidStatus);
+
+    transition_status(asset, AssetStatus::Active, None)?;
+
+    emit!(AssetActivated {
+        asset: asset.key(),
+        activated_at: asset.updated_at,
+    });
+
+    Ok(())
+}
+
+/// Refuse a single-signature action on a mint whose asset is governed by an approver set
+fn require_single_key(mint_config: &MintConfig, asset: Option<&Asset>) -> Result<()> {
+    if mint_config.asset == Pubkey::default() {
+        return Ok(());
+    }
+    let asset = asset.ok_or(AssetRegistryError::AssetAccountMissing)?;
+    require!(asset.approver_set == Pubkey::default(), AssetRegistryError::ApprovalRequired);
+    Ok(())
+}
+
+/// Halt transfers of a mint through the registry's frozen flag
+fn freeze(mint_config: &mut MintConfig) -> Result<()> {
+    require!(!mint_config.is_frozen, AssetRegistryError::AlreadyFrozen);
+
+    mint_config.is_frozen = true;
+
+    emit!(MintFrozen {
+        mint: mint_config.mint,
+        frozen_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Mint frozen: {}", mint_config.mint);
+    Ok(())
+}
+
+/// Point a mint's transfer hook extension at `transfer_hook_program`
+fn update_transfer_hook<'info>(
+    token_program: &Program<'info, Token2022>,
+    mint: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    mint_config: &mut MintConfig,
+    transfer_hook_program: Pubkey,
+) -> Result<()> {
+    let mint_key = mint.key();
+    let seeds = &[
+        b"mint-authority",
+        mint_key.as_ref(),
+        &[mint_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    invoke_signed(
+        &transfer_hook::instruction::update(
+            &token_program.key(),
+            &mint_key,
+            &mint_authority.key(),
+            &[],
+            Some(transfer_hook_program),
+        )?,
+        &[mint.clone(), mint_authority.clone()],
+        signer_seeds,
+    )?;
+
+    mint_config.transfer_hook_program = Some(transfer_hook_program);
+
+    emit!(TransferHookUpdated {
+        mint: mint_config.mint,
+        transfer_hook_program,
+    });
+
+    msg!("Transfer hook set to: {}", transfer_hook_program);
+    Ok(())
+}
+
+/// Mint `amount` within the registered supply, taking the platform fee in kind
+/// and recording an optional lockup lot against the recipient
+#[allow(clippy::too_many_arguments)]
+fn issue_tokens<'info>(
+    token_program: &Program<'info, Token2022>,
+    mint: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    recipient: &AccountInfo<'info>,
+    recipient_token_account: &AccountInfo<'info>,
+    treasury_token_account: &InterfaceAccount<'info, TokenAccount>,
+    fee_bps: u16,
+    asset: &Asset,
+    mint_config: &mut MintConfig,
+    reserve: Option<&Reserve>,
+    lockup: Option<(&mut Lockup, u8)>,
+    amount: u64,
+    schedule: Option<LockupSchedule>,
+) -> Result<()> {
+    require!(amount > 0, AssetRegistryError::InvalidAmount);
+    require_issuable(asset, mint_config, reserve, amount)?;
+
+    // The platform fee is taken in kind from the issued amount
+    let fee = calculate_fee(amount, fee_bps).ok_or(AssetRegistryError::MathOverflow)?;
+    let recipient_amount = amount - fee;
+
+    let seeds = &[
+        b"mint-authority",
+        mint_config.mint.as_ref(),
+        &[mint_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token_2022::MintTo {
+                mint: mint.clone(),
+                to: recipient_token_account.clone(),
+                authority: mint_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        recipient_amount,
+    )?;
+
+    if fee > 0 {
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_2022::MintTo {
+                    mint: mint.clone(),
+                    to: treasury_token_account.to_account_info(),
+                    authority: mint_authority.clone(),
+                },
+                signer_seeds,
+            ),
+            fee,
+        )?;
+
+        emit!(FeeCollected {
+            source: FeeSource::Issuance,
+            asset_mint: mint.key(),
+            fee_mint: mint.key(),
+            amount: fee,
+            fee_bps,
+            treasury_account: treasury_token_account.key(),
+        });
+    }
+
+    if let Some(schedule) = schedule {
+        let (lockup, lockup_bump) = lockup.ok_or(AssetRegistryError::LockupAccountMissing)?;
+        if lockup.mint == Pubkey::default() {
+            lockup.mint = mint.key();
+            lockup.holder = recipient.key();
+            lockup.bump = lockup_bump;
+        }
+        add_lockup_lot(lockup, schedule, recipient_amount)?;
+    }
+
+    mint_config.minted_supply = mint_config
+        .minted_supply
+        .checked_add(amount)
+        .ok_or(AssetRegistryError::MathOverflow)?;
+
+    emit!(TokensMinted {
+        mint: mint.key(),
+        recipient: recipient.key(),
+        amount: recipient_amount,
+        fee,
+        minted_supply: mint_config.minted_supply,
+        outstanding_supply: mint_config.outstanding_supply(),
+        total_supply: asset.total_supply,
+    });
+
+    msg!("Minted {} tokens to {}", recipient_amount, recipient.key());
+    Ok(())
+}
+
+/// Lock `amount` freshly issued tokens in `lockup` under `schedule`
+fn add_lockup_lot(lockup: &mut Lockup, schedule: LockupSchedule, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let lot = schedule.lot_for(amount, now)?;
+    lockup.add_lot(lot, now)?;
+
+    emit!(LockupCreated {
+        mint: lockup.mint,
+        holder: lockup.holder,
+        amount: lot.amount,
+        start: lot.start,
+        cliff_end: lot.cliff_end,
+        vesting_end: lot.vesting_end,
+        locked_amount: lockup.locked_amount(now),
+    });
+    Ok(())
+}
+
+/// Lock a batch allocation in the holder's lockup PDA, creating it on first use.
+/// Batch lockups arrive as remaining accounts, so Anchor cannot initialize them.
+fn record_batch_lockup<'info>(
+    lockup_info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    mint: &Pubkey,
+    holder: &Pubkey,
+    schedule: LockupSchedule,
+    amount: u64,
+) -> Result<()> {
+    let (expected, bump) =
+        Pubkey::find_program_address(&[b"lockup", mint.as_ref(), holder.as_ref()], &crate::ID);
+    require_keys_eq!(lockup_info.key(), expected, AssetRegistryError::InvalidBatchAccounts);
+
+    let mut lockup = if lockup_info.data_is_empty() {
+        let space = 8 + Lockup::INIT_SPACE;
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: payer.to_account_info(),
+                    to: lockup_info.clone(),
+                },
+                &[&[b"lockup", mint.as_ref(), holder.as_ref(), &[bump]]],
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &crate::ID,
+        )?;
+        Lockup {
+            mint: *mint,
+            holder: *holder,
+            lots: Vec::new(),
+            last_split: Pubkey::default(),
+            bump,
+        }
+    } else {
+        Lockup::try_deserialize(&mut &lockup_info.try_borrow_data()?[..])?
+    };
+
+    add_lockup_lot(&mut lockup, schedule, amount)?;
+    lockup.try_serialize(&mut &mut lockup_info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+/// Refuse to thaw a token account that `finalize_split` froze with its
+/// old-denomination balance still unconverted
+/// Close an account this program owns, refunding its rent to `receiver`
+fn close_program_account<'info>(
+    account: &AccountInfo<'info>,
+    receiver: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = account.lamports();
+    **receiver.try_borrow_mut_lamports()? = receiver
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(AssetRegistryError::MathOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&anchor_lang::system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+fn require_no_pending_conversion(conversion_info: &AccountInfo) -> Result<()> {
+    if conversion_info.owner != &crate::ID || conversion_info.data_is_empty() {
+        return Ok(());
+    }
+    let conversion = SplitConversion::try_deserialize(&mut &conversion_info.try_borrow_data()?[..])?;
+    require!(!conversion.pending, AssetRegistryError::SplitBalancePending);
+    Ok(())
+}
+
+/// Scale a lockup's unvested lots by `split`, once per split however many of
+/// the owner's token accounts convert
+fn rescale_lockup(lockup_info: &AccountInfo, split: &Account<Split>) -> Result<()> {
+    let mut data = lockup_info.try_borrow_mut_data()?;
+    let mut lockup = Lockup::try_deserialize(&mut &data[..])?;
+    if lockup.last_split == split.key() {
+        return Ok(());
+    }
+    for lot in lockup.lots.iter_mut() {
+        lot.amount = split.convert(lot.amount)?;
+    }
+    lockup.last_split = split.key();
+    lockup.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Replace `old_amount` in `token_account` with `new_amount`, burning through
+/// the permanent delegate and minting through the mint authority. Frozen
+/// accounts are thawed for the swap and frozen again when `refreeze` is set.
+#[allow(clippy::too_many_arguments)]
+fn rebalance<'info>(
+    token_program: &Program<'info, Token2022>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    (permanent_delegate, permanent_delegate_bump): (&AccountInfo<'info>, u8),
+    (mint_authority, mint_authority_bump): (&AccountInfo<'info>, u8),
+    old_amount: u64,
+    new_amount: u64,
+    refreeze: bool,
+) -> Result<()> {
+    let mint_key = mint.key();
+    let authority_seeds = &[
+        b"mint-authority",
+        mint_key.as_ref(),
+        &[mint_authority_bump],
+    ];
+
+    if token_account.is_frozen() {
+        token_2022::thaw_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token_2022::ThawAccount {
+                account: token_account.to_account_info(),
+                mint: mint.to_account_info(),
+                authority: mint_authority.clone(),
+            },
+            &[&authority_seeds[..]],
+        ))?;
+    }
+
+    if old_amount > 0 {
+        let seeds = &[
+            b"permanent-delegate",
+            mint_key.as_ref(),
+            &[permanent_delegate_bump],
+        ];
+        token_2022::burn(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_2022::Burn {
+                    mint: mint.to_account_info(),
+                    from: token_account.to_account_info(),
+                    authority: permanent_delegate.clone(),
+                },
+                &[&seeds[..]],
+            ),
+            old_amount,
+        )?;
+    }
+
+    if new_amount > 0 {
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_2022::MintTo {
+                    mint: mint.to_account_info(),
+                    to: token_account.to_account_info(),
+                    authority: mint_authority.clone(),
+                },
+                &[&authority_seeds[..]],
+            ),
+            new_amount,
+        )?;
+    }
+
+    if refreeze {
+        token_2022::freeze_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token_2022::FreezeAccount {
+                account: token_account.to_account_info(),
+                mint: mint.to_account_info(),
+                authority: mint_authority.clone(),
+            },
+            &[&authority_seeds[..]],
+        ))?;
+    }
+    Ok(())
+}
+
+/// Add a converted balance to the supply ledger and the split's totals
+fn record_conversion(
+    mint_config: &mut MintConfig,
+    split: &mut Account<Split>,
+    conversion: &SplitConversion,
+) -> Result<()> {
+    let (old_amount, new_amount) = (conversion.old_amount, conversion.new_amount);
+    mint_config.burned_supply = mint_config
+        .burned_supply
+        .checked_add(old_amount)
+        .ok_or(AssetRegistryError::MathOverflow)?;
+    mint_config.minted_supply = mint_config
+        .minted_supply
+        .checked_add(new_amount)
+        .ok_or(AssetRegistryError::MathOverflow)?;
+
+    let remainder = split.remainder(old_amount, new_amount);
+    split.holders_converted += 1;
+    split.converted_old_supply = split
+        .converted_old_supply
+        .checked_add(old_amount)
+        .ok_or(AssetRegistryError::MathOverflow)?;
+    split.converted_new_supply = split
+        .converted_new_supply
+        .checked_add(new_amount)
+        .ok_or(AssetRegistryError::MathOverflow)?;
+    split.destroyed_remainder = split
+        .destroyed_remainder
+        .checked_add(remainder)
+        .ok_or(AssetRegistryError::MathOverflow)?;
+
+    emit!(SplitBalanceConverted {
+        split: split.key(),
+        mint: split.mint,
+        token_account: conversion.token_account,
+        holder: conversion.holder,
+        old_amount,
+        new_amount,
+        remainder,
+    });
+
+    msg!("Converted {} into {} for {}", old_amount, new_amount, conversion.token_account);
+    Ok(())
+}
+
+/// Close out a split: rescale the registered supply and release the mint
+fn finish_split(
+    asset: &mut Asset,
+    mint_config: &mut MintConfig,
+    split: &mut Account<Split>,
+    now: i64,
+) -> Result<()> {
+    let old_total_supply = asset.total_supply;
+    asset.total_supply = split.convert(old_total_supply)?;
+    asset.updated_at = now;
+
+    mint_config.active_split = Pubkey::default();
+    split.completed_at = Some(now);
+
+    emit!(SplitCompleted {
+        split: split.key(),
+        mint: split.mint,
+        old_units: split.old_units,
+        new_units: split.new_units,
+        holders_converted: split.holders_converted,
+        old_total_supply,
+        new_total_supply: asset.total_supply,
+        outstanding_supply: mint_config.outstanding_supply(),
+        destroyed_remainder: split.destroyed_remainder,
+        completed_at: now,
+    });
+
+    msg!("Split {} for {} completed", split.index, split.mint);
+    Ok(())
+}
+
+/// Require that `amount` more tokens can be issued: the asset is active, the
+/// mint unfrozen, and the new outstanding supply within the registered supply
+/// and any attested reserve
+fn require_issuable(
+    asset: &Asset,
+    mint_config: &MintConfig,
+    reserve: Option<&Reserve>,
+    amount: u64,
+) -> Result<()> {
+    require!(asset.status == AssetStatus::Active, AssetRegistryError::AssetNotActive);
+    require!(!mint_config.is_frozen, AssetRegistryError::MintIsFrozen);
+
+    let outstanding_supply = mint_config
+        .outstanding_supply()
+        .checked_add(amount)
+        .ok_or(AssetRegistryError::MathOverflow)?;
+    require!(
+        outstanding_supply <= asset.total_supply,
+        AssetRegistryError::SupplyCapExceeded
+    );
+    require_reserve_coverage(mint_config, reserve, outstanding_supply)
+}
+
+/// Require a fresh custodian attestation covering `outstanding_supply` for
+/// reserve-backed mints
+fn require_reserve_coverage(
+    mint_config: &MintConfig,
+    reserve: Option<&Reserve>,
+    outstanding_supply: u64,
+) -> Result<()> {
+    if !mint_config.requires_reserve {
+        return Ok(());
+    }
+
+    let reserve = reserve.ok_or(AssetRegistryError::ReserveAccountMissing)?;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        reserve.attested_at > 0 && now - reserve.attested_at <= MAX_RESERVE_AGE,
+        AssetRegistryError::StaleReserveAttestation
+    );
+    require!(
+        outstanding_supply <= reserve.reserve_amount,
+        AssetRegistryError::InsufficientReserves
+    );
+    Ok(())
+}
+
+/// Validate and store an approver list, invalidating proposals approved under the previous one
+fn set_approvers(
+    approver_set: &mut Account<ApproverSet>,
+    approvers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(
+        !approvers.is_empty() && approvers.len() <= MAX_APPROVERS,
+        AssetRegistryError::InvalidApprovers
+    );
+    require!(
+        approvers
+            .iter()
+            .enumerate()
+            .all(|(i, approver)| !approvers[..i].contains(approver)),
+        AssetRegistryError::InvalidApprovers
+    );
+    require!(
+        threshold > 0 && usize::from(threshold) <= approvers.len(),
+        AssetRegistryError::InvalidThreshold
+    );
+
+    approver_set.approvers = approvers;
+    approver_set.threshold = threshold;
+    approver_set.generation = approver_set
+        .generation
+        .checked_add(1)
+        .ok_or(AssetRegistryError::MathOverflow)?;
+
+    emit!(ApproversUpdated {
+        approver_set: approver_set.key(),
+        scope: approver_set.scope,
+        approvers: approver_set.approvers.clone(),
+        threshold,
+        generation: approver_set.generation,
+    });
+
+    msg!(
+        "Approver set {} requires {} of {} approvals",
+        approver_set.key(),
+        threshold,
+        approver_set.approvers.len()
+    );
+    Ok(())
+}
+
+fn record_execution(proposal: &Account<Proposal>, executor: Pubkey) {
+    emit!(ProposalExecuted {
+        proposal: proposal.key(),
+        approver_set: proposal.approver_set,
+        id: proposal.id,
+        executed_by: executor,
+    });
+
+    msg!("Proposal {} executed", proposal.id);
+}
+
+/// Record a change for `target` that may be applied once `TIMELOCK_DELAY` has passed
+fn queue_change(
+    queued_change: &mut Account<QueuedChange>,
+    bump: u8,
+    target: Pubkey,
+    change: TimelockedChange,
+    queued_by: Pubkey,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let eta = now + TIMELOCK_DELAY;
+
+    queued_change.target = target;
+    queued_change.change = change.clone();
+    queued_change.queued_by = queued_by;
+    queued_change.queued_at = now;
+    queued_change.eta = eta;
+    queued_change.bump = bump;
+
+    emit!(ChangeQueued {
+        queued_change: queued_change.key(),
+        target,
+        change,
+        queued_by,
+        eta,
+    });
+
+    msg!("Change for {} queued until {}", target, eta);
+    Ok(())
+}
+
+/// Account layout carrying a version byte and reserved padding
+pub trait Versioned: AccountSerialize + AccountDeserialize + anchor_lang::Discriminator + Space {
+    /// Layout the account had before it was versioned
+    type Legacy: AnchorDeserialize;
+
+    fn version_mut(&mut self) -> &mut u8;
+
+    /// Current layout holding the fields of a pre-versioning account, at version 0
+    fn from_legacy(legacy: Self::Legacy) -> Self;
+}
+
+impl Versioned for Config {
+    type Legacy = LegacyConfig;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+
+    fn from_legacy(legacy: LegacyConfig) -> Self {
+        Config {
+            authority: legacy.authority,
+            platform_fee_bps: legacy.platform_fee_bps,
+            total_assets: legacy.total_assets,
+            bump: legacy.bump,
+            version: 0,
+            pyth_program: Pubkey::default(),
+            reserved: [0; 32],
+        }
+    }
+}
+
+impl Versioned for Asset {
+    type Legacy = LegacyAsset;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+
+    /// The registering authority becomes the issuer. Pre-versioning assets were
+    /// never appended to the asset indexes, so their positions stay zero.
+    fn from_legacy(legacy: LegacyAsset) -> Self {
+        Asset {
+            issuer: legacy.authority,
+            authority: legacy.authority,
+            mint: legacy.mint,
+            name: legacy.name,
+            asset_type: legacy.asset_type,
+            total_value: legacy.total_value,
+            total_supply: legacy.total_supply,
+            metadata_uri: legacy.metadata_uri,
+            status: legacy.status,
+            created_at: legacy.created_at,
+            updated_at: legacy.updated_at,
+            status_changed_at: legacy.updated_at,
+            status_reason: None,
+            matured_at: None,
+            defaulted_at: None,
+            liquidation_started_at: None,
+            redeemed_at: None,
+            sequence: 0,
+            issuer_sequence: 0,
+            type_sequence: 0,
+            bump: legacy.bump,
+            version: 0,
+            approver_set: Pubkey::default(),
+            reserved: [0; 32],
+        }
+    }
+}
+
+impl Versioned for MintConfig {
+    type Legacy = LegacyMintConfig;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+
+    /// Supply issued before versioning was not tracked, and pre-versioning mint
+    /// configurations were never linked to an asset, so both start out empty.
+    fn from_legacy(legacy: LegacyMintConfig) -> Self {
+        MintConfig {
+            mint: legacy.mint,
+            authority: legacy.authority,
+            permanent_delegate: legacy.permanent_delegate,
+            transfer_hook_program: legacy.transfer_hook_program,
+            name: legacy.name,
+            symbol: legacy.symbol,
+            uri: legacy.uri,
+            decimals: legacy.decimals,
+            is_frozen: legacy.is_frozen,
+            default_account_frozen: false,
+            asset: Pubkey::default(),
+            minted_supply: 0,
+            burned_supply: 0,
+            created_at: legacy.created_at,
+            bump: legacy.bump,
+            version: 0,
+            active_split: Pubkey::default(),
+            split_count: 0,
+            requires_reserve: false,
+            pending_split_conversions: 0,
+            reserved: [0; 23],
+        }
+    }
+}
+
+/// Upgrade an account in place to layout `to_version` and return the version it
+/// was read at; accounts already at `to_version` are left untouched. Accounts
+/// smaller than the current layout predate versioning and are decoded with
+/// `T::Legacy` before growing at `payer`'s expense. Later versions only carve
+/// fields out of the zeroed padding, so they read back as the current layout.
+/// Shared with the compliance and auction programs.
+pub fn migrate_account<'info, T: Versioned>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    to_version: u8,
+) -> Result<u8> {
+    let space = 8 + T::INIT_SPACE;
+    let mut account = if info.data_len() < space {
+        let legacy = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == T::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            T::Legacy::deserialize(&mut &data[8..])?
+        };
+
+        let shortfall = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        info.realloc(space, true)?;
+        // Legacy bytes beyond the rewritten layout would otherwise linger in the padding
+        info.try_borrow_mut_data()?[8..].fill(0);
+
+        T::from_legacy(legacy)
+    } else {
+        T::try_deserialize(&mut &info.try_borrow_data()?[..])?
+    };
+
+    let version = account.version_mut();
+    let from_version = *version;
+    if from_version < to_version {
+        *version = to_version;
+        account.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+    Ok(from_version)
+}
+
+/// Upgrade a registry account to `ACCOUNT_VERSION`
+fn migrate<'info, T: Versioned>(
+    info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let from_version = migrate_account::<T>(info, payer, system_program, ACCOUNT_VERSION)?;
+    require!(from_version < ACCOUNT_VERSION, AssetRegistryError::AccountAlreadyMigrated);
+
+    emit!(AccountMigrated {
+        account: info.key(),
+        from_version,
+        to_version: ACCOUNT_VERSION,
+    });
+    Ok(())
+}
+
+/// Tokens still locked at `now` under the holder lockup at `lockup_info`, or zero
+/// when the holder has none. Used by the compliance hook to cap transfers.
+pub fn load_locked_amount(lockup_info: &AccountInfo, now: i64) -> Result<u64> {
+    if lockup_info.owner != &crate::ID || lockup_info.data_is_empty() {
+        return Ok(0);
+    }
+
+    let data = lockup_info.try_borrow_data()?;
+    let lockup = Lockup::try_deserialize(&mut &data[..])?;
+    Ok(lockup.locked_amount(now))
+}
+
+/// Byte offsets into the compliance program's `WhitelistEntry` account
+const WHITELIST_KYC_VERIFIED_OFFSET: usize = 8 + 32 + 1 + 2;
+const WHITELIST_KYC_EXPIRY_OFFSET: usize = WHITELIST_KYC_VERIFIED_OFFSET + 1;
+const WHITELIST_IS_ACTIVE_OFFSET: usize = WHITELIST_KYC_EXPIRY_OFFSET + 8 + 8 + 8;
+
+/// Whether a compliance whitelist entry is active with unexpired KYC.
+/// Parsed by hand because the compliance program depends on this crate.
+pub fn is_whitelisted(whitelist_entry: &AccountInfo, now: i64) -> Result<bool> {
+    if whitelist_entry.owner != &compliance_program::ID {
+        return Ok(false);
+    }
+
+    let data = whitelist_entry.try_borrow_data()?;
+    let discriminator = anchor_lang::solana_program::hash::hash(b"account:WhitelistEntry");
+    if data.len() <= WHITELIST_IS_ACTIVE_OFFSET || data[..8] != discriminator.to_bytes()[..8] {
+        return Ok(false);
+    }
+
+    let kyc_expiry = i64::from_le_bytes(
+        data[WHITELIST_KYC_EXPIRY_OFFSET..WHITELIST_KYC_EXPIRY_OFFSET + 8]
+            .try_into()
+            .unwrap(),
+    );
+    Ok(data[WHITELIST_KYC_VERIFIED_OFFSET] == 1
+        && data[WHITELIST_IS_ACTIVE_OFFSET] == 1
+        && kyc_expiry > now)
+}
+
+/// Emit the shared event for any typed attribute update
+fn emit_attributes_updated(asset: &Account<Asset>, updated_by: Pubkey, updated_at: i64) -> Result<()> {
+    emit!(AssetAttributesUpdated {
+        asset: asset.key(),
+        asset_type: asset.asset_type,
+        updated_by,
+        updated_at,
+    });
+
+    msg!("Attributes updated for {}", asset.key());
+    Ok(())
+}
+
+/// Value of an ISIN/CUSIP character: digits as-is, letters from 10
+fn security_char_value(c: u8) -> Option<u32> {
+    match c {
+        b'0'..=b'9' => Some((c - b'0') as u32),
+        b'A'..=b'Z' => Some((c - b'A') as u32 + 10),
+        _ => None,
+    }
+}
+
+/// ISIN: 2-letter country code, 9 alphanumerics and a Luhn check digit
+pub fn is_valid_isin(isin: &str) -> bool {
+    let bytes = isin.as_bytes();
+    if bytes.len() != 12
+        || !bytes[..2].iter().all(u8::is_ascii_uppercase)
+        || !bytes[11].is_ascii_digit()
+    {
+        return false;
+    }
+
+    // Expand letters to two digits, then Luhn over everything but the check digit
+    let mut digits = Vec::with_capacity(22);
+    for &c in &bytes[..11] {
+        match security_char_value(c) {
+            Some(v) if v >= 10 => digits.extend([v / 10, v % 10]),
+            Some(v) => digits.push(v),
+            None => return false,
+        }
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| if i % 2 == 0 { (d * 2) / 10 + (d * 2) % 10 } else { d })
+        .sum();
+    (10 - sum % 10) % 10 == (bytes[11] - b'0') as u32
+}
+
+/// CUSIP: 8 alphanumerics and a modulus-10 "double-add-double" check digit
+pub fn is_valid_cusip(cusip: &str) -> bool {
+    let bytes = cusip.as_bytes();
+    if bytes.len() != 9 || !bytes[8].is_ascii_digit() {
+        return false;
+    }
+
+    let mut sum = 0;
+    for (i, &c) in bytes[..8].iter().enumerate() {
+        let mut v = match c {
+            b'*' => 36,
+            b'@' => 37,
+            b'#' => 38,
+            _ => match security_char_value(c) {
+                Some(v) => v,
+                None => return false,
+            },
+        };
+        if i % 2 == 1 {
+            v *= 2;
+        }
+        sum += v / 10 + v % 10;
+    }
+
+    (10 - sum % 10) % 10 == (bytes[8] - b'0') as u32
+}
+
+/// Ensure an existing account is a Token-2022 account for `mint` owned by `owner`
+fn require_token_account(info: &AccountInfo, mint: &Pubkey, owner: &Pubkey) -> Result<()> {
+    require_keys_eq!(*info.owner, token_2022::ID, AssetRegistryError::InvalidBatchAccounts);
+
+    let data = info.try_borrow_data()?;
+    let account = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    require!(
+        account.base.mint == *mint && account.base.owner == *owner,
+        AssetRegistryError::InvalidBatchAccounts
+    );
+    Ok(())
+}
+
+/// Platform fee owed on `amount` at `fee_bps`, rounded down
+pub fn calculate_fee(amount: u64, fee_bps: u16) -> Option<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)?
+        .checked_div(BPS_DENOMINATOR as u128)?;
+    u64::try_from(fee).ok()
+}
+
+/// Update the supply ledger after a retirement burn
+fn record_retirement_burn(
+    mint_config: &mut MintConfig,
+    owner: Pubkey,
+    amount: u64,
+    by_delegate: bool,
+) -> Result<()> {
+    mint_config.burned_supply = mint_config
+        .burned_supply
+        .checked_add(amount)
+        .ok_or(AssetRegistryError::MathOverflow)?;
+
+    emit!(TokensBurned {
+        mint: mint_config.mint,
+        owner,
+        amount,
+        burned_supply: mint_config.burned_supply,
+        outstanding_supply: mint_config.outstanding_supply(),
+        by_delegate,
+    });
+
+    msg!("Burned {} tokens held by {}", amount, owner);
+    Ok(())
+}
+
+/// Transfer tokens out of a holder's account signed by the permanent delegate PDA
+#[allow(clippy::too_many_arguments)]
+fn execute_forced_transfer<'info>(
+    token_program: &Program<'info, Token2022>,
+    mint: &InterfaceAccount<'info, Mint>,
+    source: &InterfaceAccount<'info, TokenAccount>,
+    destination: &InterfaceAccount<'info, TokenAccount>,
+    permanent_delegate: &AccountInfo<'info>,
+    permanent_delegate_bump: u8,
+    additional_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    reason: ForcedTransferReason,
+    document_hash: [u8; 32],
+    authority: Pubkey,
+    quarantined: bool,
+) -> Result<()> {
+    require!(amount > 0, AssetRegistryError::InvalidAmount);
+    require!(document_hash != [0u8; 32], AssetRegistryError::InvalidDocumentHash);
+    require_keys_neq!(source.key(), destination.key(), AssetRegistryError::InvalidDestination);
+
+    let mint_key = mint.key();
+    let seeds = &[
+        b"permanent-delegate",
+        mint_key.as_ref(),
+        &[permanent_delegate_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    invoke_transfer_checked(
+        &token_program.key(),
+        source.to_account_info(),
+        mint.to_account_info(),
+        destination.to_account_info(),
+        permanent_delegate.clone(),
+        additional_accounts,
+        amount,
+        mint.decimals,
+        signer_seeds,
+    )?;
+
+    emit!(ForcedTransfer {
+        mint: mint_key,
+        source: source.key(),
+        source_owner: source.owner,
+        destination: destination.key(),
+        destination_owner: destination.owner,
+        amount,
+        reason,
+        document_hash,
+        authority,
+        quarantined,
+        executed_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Forced transfer of {} tokens from {}", amount, source.owner);
+    Ok(())
+}
+
+/// Record a pending two-step authority transfer for `target`
+fn open_authority_transfer(
+    transfer: &mut AuthorityTransfer,
+    target: Pubkey,
+    kind: AuthorityKind,
+    current_authority: Pubkey,
+    proposed_authority: Pubkey,
+    expires_at: Option<i64>,
+    bump: u8,
+) -> Result<()> {
+    require_keys_neq!(
+        proposed_authority,
+        current_authority,
+        AssetRegistryError::InvalidProposedAuthority
+    );
+    require_keys_neq!(
+        proposed_authority,
+        Pubkey::default(),
+        AssetRegistryError::InvalidProposedAuthority
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(expires_at) = expires_at {
+        require!(expires_at > now, AssetRegistryError::InvalidExpiry);
+    }
+
+    transfer.target = target;
+    transfer.kind = kind;
+    transfer.current_authority = current_authority;
+    transfer.proposed_authority = proposed_authority;
+    transfer.proposed_at = now;
+    transfer.expires_at = expires_at;
+    transfer.bump = bump;
+
+    emit!(AuthorityTransferProposed {
+        target,
+        kind,
+        current_authority,
+        proposed_authority,
+        expires_at,
+    });
+
+    msg!("Authority transfer proposed for {} to {}", target, proposed_authority);
+    Ok(())
+}
+
+// ===========================================
+// ACCOUNTS
+// ===========================================
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RoleAssignment::INIT_SPACE,
+        seeds = [b"role", crate::ID.as_ref(), &[Role::RegistryAdmin as u8], authority.key().as_ref()],
+        bump
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role, holder: Pubkey)]
+pub struct GrantRegistryRole<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Granter's registry admin assignment
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RoleAssignment::INIT_SPACE,
+        seeds = [b"role", crate::ID.as_ref(), &[role as u8], holder.as_ref()],
+        bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRegistryRole<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Revoker's registry admin assignment
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        close = authority,
+        constraint = role_assignment.scope == crate::ID @ AssetRegistryError::InvalidRoleScope
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role, holder: Pubkey)]
+pub struct GrantAssetRole<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RoleAssignment::INIT_SPACE,
+        seeds = [b"role", asset.mint.as_ref(), &[role as u8], holder.as_ref()],
+        bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAssetRole<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        mut,
+        close = authority,
+        constraint = role_assignment.scope == asset.mint @ AssetRegistryError::InvalidRoleScope
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, asset_type: AssetType)]
+pub struct RegisterAsset<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Asset::INIT_SPACE,
+        seeds = [b"asset", mint.key().as_ref()],
+        bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    /// The token mint for this asset (Token-2022)
+    /// CHECK: Bound to a registry-created RWA mint through the mint config seeds
+    pub mint: AccountInfo<'info>,
+
+    /// Mint configuration created by `create_token_mint`, linked to the new asset
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = authority,
+        constraint = mint_config.asset == Pubkey::default() @ AssetRegistryError::MintAlreadyLinked
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Registry-wide issuer assignment of the registering authority
+    #[account(
+        constraint = issuer_role.authorizes(&authority.key(), Role::Issuer, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub issuer_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AssetIndex::INIT_SPACE,
+        seeds = [b"asset-index"],
+        bump
+    )]
+    pub global_index: Account<'info, AssetIndex>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AssetIndexPage::INIT_SPACE,
+        seeds = [b"asset-index-page", global_index.key().as_ref(), &global_index.next_page().to_le_bytes()],
+        bump
+    )]
+    pub global_index_page: Account<'info, AssetIndexPage>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AssetIndex::INIT_SPACE,
+        seeds = [b"asset-index".as_ref(), b"issuer", authority.key().as_ref()],
+        bump
+    )]
+    pub issuer_index: Account<'info, AssetIndex>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AssetIndexPage::INIT_SPACE,
+        seeds = [b"asset-index-page", issuer_index.key().as_ref(), &issuer_index.next_page().to_le_bytes()],
+        bump
+    )]
+    pub issuer_index_page: Account<'info, AssetIndexPage>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AssetIndex::INIT_SPACE,
+        seeds = [b"asset-index".as_ref(), b"type", &[asset_type as u8]],
+        bump
+    )]
+    pub type_index: Account<'info, AssetIndex>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AssetIndexPage::INIT_SPACE,
+        seeds = [b"asset-index-page", type_index.key().as_ref(), &type_index.next_page().to_le_bytes()],
+        bump
+    )]
+    pub type_index_page: Account<'info, AssetIndexPage>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAsset<'info> {
+    #[account(
+        mut,
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BeginRetirement<'info> {
+    #[account(
+        mut,
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        seeds = [b"mint-config", asset.mint.as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRealEstateAttributes<'info> {
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RealEstateAttributes::INIT_SPACE,
+        seeds = [b"attributes", asset.key().as_ref()],
+        bump
+    )]
+    pub attributes: Account<'info, RealEstateAttributes>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetReceivableAttributes<'info> {
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ReceivableAttributes::INIT_SPACE,
+        seeds = [b"attributes", asset.key().as_ref()],
+        bump
+    )]
+    pub attributes: Account<'info, ReceivableAttributes>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetSecurityAttributes<'info> {
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + SecurityAttributes::INIT_SPACE,
+        seeds = [b"attributes", asset.key().as_ref()],
+        bump
+    )]
+    pub attributes: Account<'info, SecurityAttributes>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(document_type: DocumentType, content_hash: [u8; 32])]
+pub struct AddDocument<'info> {
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AssetDocument::INIT_SPACE,
+        seeds = [b"document", asset.key().as_ref(), content_hash.as_ref()],
+        bump
+    )]
+    pub document: Account<'info, AssetDocument>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(content_hash: [u8; 32])]
+pub struct SupersedeDocument<'info> {
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"document", asset.key().as_ref(), previous_document.content_hash.as_ref()],
+        bump = previous_document.bump,
+        has_one = asset
+    )]
+    pub previous_document: Account<'info, AssetDocument>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AssetDocument::INIT_SPACE,
+        seeds = [b"document", asset.key().as_ref(), content_hash.as_ref()],
+        bump
+    )]
+    pub document: Account<'info, AssetDocument>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDocument<'info> {
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"document", asset.key().as_ref(), document.content_hash.as_ref()],
+        bump = document.bump,
+        has_one = asset
+    )]
+    pub document: Account<'info, AssetDocument>,
+}
+
+#[derive(Accounts)]
+#[instruction(appraiser: Pubkey)]
+pub struct RegisterAppraiser<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Appraiser::INIT_SPACE,
+        seeds = [b"appraiser", appraiser.as_ref()],
+        bump
+    )]
+    pub appraiser_record: Account<'info, Appraiser>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAppraiser<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"appraiser", appraiser_record.appraiser.as_ref()],
+        bump = appraiser_record.bump
+    )]
+    pub appraiser_record: Account<'info, Appraiser>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitValuation<'info> {
+    /// Relayer submitting the appraiser's attestation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        seeds = [b"appraiser", appraiser_record.appraiser.as_ref()],
+        bump = appraiser_record.bump
+    )]
+    pub appraiser_record: Account<'info, Appraiser>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ValuationHistory::INIT_SPACE,
+        seeds = [b"valuation-history", asset.key().as_ref()],
+        bump
+    )]
+    pub valuation_history: Account<'info, ValuationHistory>,
+
+    /// CHECK: Instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetNavPerToken<'info> {
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        seeds = [b"mint-config", asset.mint.as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetPythProgram<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct BindPriceFeed<'info> {
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::ValuationOfficer, Some(&asset.mint)) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Owner checked against `config.pyth_program` and validated as a
+    /// Pyth-style price account in the handler
+    pub price_feed: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PriceFeedBinding::INIT_SPACE,
+        seeds = [b"price-feed", asset.key().as_ref()],
+        bump
+    )]
+    pub price_feed_binding: Account<'info, PriceFeedBinding>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnbindPriceFeed<'info> {
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::ValuationOfficer, Some(&asset.mint)) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"price-feed", asset.key().as_ref()],
+        bump = price_feed_binding.bump,
+        has_one = asset
+    )]
+    pub price_feed_binding: Account<'info, PriceFeedBinding>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshValuation<'info> {
+    /// Anyone may crank a refresh
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        mut,
+        seeds = [b"price-feed", asset.key().as_ref()],
+        bump = price_feed_binding.bump,
+        has_one = asset,
+        has_one = price_feed
+    )]
+    pub price_feed_binding: Account<'info, PriceFeedBinding>,
+
+    /// CHECK: Must be the bound feed, owned by the bound oracle program
+    #[account(owner = price_feed_binding.feed_program @ AssetRegistryError::InvalidPriceFeed)]
+    pub price_feed: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + ValuationHistory::INIT_SPACE,
+        seeds = [b"valuation-history", asset.key().as_ref()],
+        bump
+    )]
+    pub valuation_history: Account<'info, ValuationHistory>,
+
+    /// Valuation officer co-signing a move beyond the deviation cap
+    pub officer: Option<Signer<'info>>,
+
+    pub officer_role: Option<Account<'info, RoleAssignment>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(custodian: Pubkey)]
+pub struct RegisterCustodian<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Custodian::INIT_SPACE,
+        seeds = [b"custodian", custodian.as_ref()],
+        bump
+    )]
+    pub custodian_record: Account<'info, Custodian>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCustodian<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"custodian", custodian_record.custodian.as_ref()],
+        bump = custodian_record.bump
+    )]
+    pub custodian_record: Account<'info, Custodian>,
+}
+
+#[derive(Accounts)]
+pub struct AssignCustodian<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", asset.mint.as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        seeds = [b"custodian", custodian_record.custodian.as_ref()],
+        bump = custodian_record.bump,
+        constraint = custodian_record.is_active @ AssetRegistryError::CustodianInactive
+    )]
+    pub custodian_record: Account<'info, Custodian>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Reserve::INIT_SPACE,
+        seeds = [b"reserve", asset.mint.as_ref()],
+        bump
+    )]
+    pub reserve: Account<'info, Reserve>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttestReserve<'info> {
+    /// Relayer submitting the custodian's attestation
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        seeds = [b"mint-config", asset.mint.as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve", asset.mint.as_ref()],
+        bump = reserve.bump,
+        has_one = asset
+    )]
+    pub reserve: Account<'info, Reserve>,
+
+    /// The asset's assigned custodian
+    #[account(
+        seeds = [b"custodian", reserve.custodian.as_ref()],
+        bump = custodian_record.bump
+    )]
+    pub custodian_record: Account<'info, Custodian>,
+
+    /// CHECK: Instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PauseAsset<'info> {
+    #[account(
+        mut,
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::Pauser, Some(&asset.mint)) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+}
+
+#[derive(Accounts)]
+pub struct BurnAsset<'info> {
+    #[account(
+        mut,
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"mint-config", asset.mint.as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(address = asset.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"asset-index"],
+        bump = global_index.bump
+    )]
+    pub global_index: Account<'info, AssetIndex>,
+
+    #[account(
+        mut,
+        seeds = [b"asset-index-page", global_index.key().as_ref(), &AssetIndex::page_of(asset.sequence).to_le_bytes()],
+        bump = global_index_page.bump
+    )]
+    pub global_index_page: Account<'info, AssetIndexPage>,
+
+    #[account(
+        mut,
+        seeds = [b"asset-index".as_ref(), b"issuer", asset.issuer.as_ref()],
+        bump = issuer_index.bump
+    )]
+    pub issuer_index: Account<'info, AssetIndex>,
+
+    #[account(
+        mut,
+        seeds = [b"asset-index-page", issuer_index.key().as_ref(), &AssetIndex::page_of(asset.issuer_sequence).to_le_bytes()],
+        bump = issuer_index_page.bump
+    )]
+    pub issuer_index_page: Account<'info, AssetIndexPage>,
+
+    #[account(
+        mut,
+        seeds = [b"asset-index".as_ref(), b"type", &[asset.asset_type as u8]],
+        bump = type_index.bump
+    )]
+    pub type_index: Account<'info, AssetIndex>,
+
+    #[account(
+        mut,
+        seeds = [b"asset-index-page", type_index.key().as_ref(), &AssetIndex::page_of(asset.type_sequence).to_le_bytes()],
+        bump = type_index_page.bump
+    )]
+    pub type_index_page: Account<'info, AssetIndexPage>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemTokens<'info> {
+    pub holder: Signer<'info>,
+
+    #[account(
+        seeds = [b"asset", mint.key().as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = mint, token::authority = holder)]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct RetireHolderTokens<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"asset", mint.key().as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = asset,
+        has_one = permanent_delegate
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA derived from mint, signs as permanent delegate
+    #[account(
+        seeds = [b"permanent-delegate", mint.key().as_ref()],
+        bump
+    )]
+    pub permanent_delegate: AccountInfo<'info>,
+
+    #[account(mut, token::mint = mint)]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAsset<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority,
+        close = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", asset.mint.as_ref()],
+        bump = mint_config.bump,
+        has_one = asset,
+        close = authority
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String, uri: String)]
+pub struct CreateTokenMint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PDA set as the mint's permanent delegate, used for forced transfers
+    /// CHECK: PDA derived from mint
+    #[account(
+        seeds = [b"permanent-delegate", mint.key().as_ref()],
+        bump
+    )]
+    pub permanent_delegate: AccountInfo<'info>,
+
+    /// The Token-2022 mint account, allocated and initialized by this instruction
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MintConfig::INIT_SPACE,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// PDA that will be the mint authority
+    /// CHECK: PDA derived from mint
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// Registry-wide issuer assignment of the creating authority
+    #[account(
+        constraint = issuer_role.authorizes(&authority.key(), Role::Issuer, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub issuer_role: Account<'info, RoleAssignment>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct QueueTransferHook<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::ComplianceOfficer, Some(&mint_config.mint)) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"mint-config", mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// Asset linked to the mint, required once one is registered
+    #[account(
+        seeds = [b"asset", mint_config.mint.as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Option<Account<'info, Asset>>,
+
+    /// One pending change per mint
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + QueuedChange::INIT_SPACE,
+        seeds = [b"timelock", mint_config.mint.as_ref()],
+        bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyTransferHook<'info> {
+    #[account(
+        mut,
+        seeds = [b"timelock", mint.key().as_ref()],
+        bump = queued_change.bump,
+        has_one = queued_by,
+        close = queued_by
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    /// CHECK: Account that queued the change, refunded its rent
+    #[account(mut)]
+    pub queued_by: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// CHECK: Token-2022 mint, bound by the mint config seeds
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// CHECK: PDA mint authority, also the transfer hook authority
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct CancelQueuedChange<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(
+        constraint = guardian_role.authorizes(&guardian.key(), Role::Guardian, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub guardian_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"timelock", queued_change.target.as_ref()],
+        bump = queued_change.bump,
+        has_one = queued_by,
+        close = queued_by
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    /// CHECK: Account that queued the change, refunded its rent
+    #[account(mut)]
+    pub queued_by: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FreezeMint<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::Pauser, Some(&mint_config.mint)) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// Asset linked to the mint, required once one is registered
+    #[account(
+        seeds = [b"asset", mint_config.mint.as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Option<Account<'info, Asset>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMintConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::Pauser, Some(&mint_config.mint)) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint_config.mint.as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+}
+
+#[derive(Accounts)]
+pub struct FreezeHolder<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::ComplianceOfficer, Some(&mint.key())) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA derived from mint, the mint's freeze authority
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    #[account(mut, token::mint = mint)]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + HolderFreeze::INIT_SPACE,
+        seeds = [b"holder-freeze", mint.key().as_ref(), holder_token_account.key().as_ref()],
+        bump
+    )]
+    pub holder_freeze: Account<'info, HolderFreeze>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ThawHolder<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::ComplianceOfficer, Some(&mint.key())) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA derived from mint, the mint's freeze authority
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    #[account(mut, token::mint = mint)]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"holder-freeze", mint.key().as_ref(), holder_token_account.key().as_ref()],
+        bump = holder_freeze.bump
+    )]
+    pub holder_freeze: Account<'info, HolderFreeze>,
+
+    #[account(
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// CHECK: Latest split declared on the mint; may have been cancelled
+    #[account(
+        seeds = [b"split", mint.key().as_ref(), &mint_config.split_count.saturating_sub(1).to_le_bytes()],
+        bump
+    )]
+    pub last_split: UncheckedAccount<'info>,
+
+    /// CHECK: Conversion receipt for the token account under `last_split`; must not be pending
+    #[account(
+        seeds = [b"split-conversion", last_split.key().as_ref(), holder_token_account.key().as_ref()],
+        bump
+    )]
+    pub split_conversion: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ThawKycAccount<'info> {
+    /// KYC operator, or the compliance config PDA when invoked by the compliance program
+    pub authority: Signer<'info>,
+
+    pub kyc_role: Option<Account<'info, RoleAssignment>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA derived from mint, the mint's freeze authority
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    #[account(mut, token::mint = mint)]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Compliance whitelist entry of the token account owner, parsed in the handler
+    #[account(
+        seeds = [b"whitelist", holder_token_account.owner.as_ref()],
+        bump,
+        seeds::program = compliance_program::ID
+    )]
+    pub whitelist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: Legal hold record for the token account; must not exist
+    #[account(
+        seeds = [b"holder-freeze", mint.key().as_ref(), holder_token_account.key().as_ref()],
+        bump
+    )]
+    pub holder_freeze: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// CHECK: Latest split declared on the mint; may have been cancelled
+    #[account(
+        seeds = [b"split", mint.key().as_ref(), &mint_config.split_count.saturating_sub(1).to_le_bytes()],
+        bump
+    )]
+    pub last_split: UncheckedAccount<'info>,
+
+    /// CHECK: Conversion receipt for the token account under `last_split`; must not be pending
+    #[account(
+        seeds = [b"split-conversion", last_split.key().as_ref(), holder_token_account.key().as_ref()],
+        bump
+    )]
+    pub split_conversion: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct MintTokens<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::Minter, Some(&mint.key())) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// Asset whose registered total supply caps issuance
+    #[account(
+        seeds = [b"asset", mint.key().as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    /// CHECK: Token-2022 mint
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// CHECK: PDA mint authority
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// CHECK: Recipient wallet
+    pub recipient: AccountInfo<'info>,
+
+    /// Recipient's token account; any lockup is recorded against its owner
+    #[account(mut, token::mint = mint, token::authority = recipient)]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Per-asset fee override, if one is set
+    #[account(
+        seeds = [b"fee-override", mint.key().as_ref()],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+
+    /// Custodian reserve record, required once the mint is reserve-backed
+    #[account(
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump = reserve.bump
+    )]
+    pub reserve: Option<Account<'info, Reserve>>,
+
+    /// CHECK: PDA owning the platform fee token accounts
+    #[account(
+        seeds = [b"fee-treasury"],
+        bump
+    )]
+    pub fee_treasury: AccountInfo<'info>,
+
+    /// Fee treasury token account for this mint
+    #[account(mut, token::mint = mint, token::authority = fee_treasury)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Recipient's lockup record, required when minting with a lockup schedule
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Lockup::INIT_SPACE,
+        seeds = [b"lockup", mint.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub lockup: Option<Account<'info, Lockup>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetUnlockedBalance<'info> {
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Holder's lockup record; uninitialized if the holder has none
+    #[account(
+        seeds = [b"lockup", holder_token_account.mint.as_ref(), holder_token_account.owner.as_ref()],
+        bump
+    )]
+    pub lockup: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BatchMintTokens<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::Minter, Some(&mint.key())) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// Asset whose registered total supply caps issuance
+    #[account(
+        seeds = [b"asset", mint.key().as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    /// CHECK: Token-2022 mint
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// CHECK: PDA mint authority
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Per-asset fee override, if one is set
+    #[account(
+        seeds = [b"fee-override", mint.key().as_ref()],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+
+    /// Custodian reserve record, required once the mint is reserve-backed
+    #[account(
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump = reserve.bump
+    )]
+    pub reserve: Option<Account<'info, Reserve>>,
+
+    /// CHECK: PDA owning the platform fee token accounts
+    #[account(
+        seeds = [b"fee-treasury"],
+        bump
+    )]
+    pub fee_treasury: AccountInfo<'info>,
+
+    /// Fee treasury token account for this mint
+    #[account(mut, token::mint = mint, token::authority = fee_treasury)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeclareSplit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", asset.mint.as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Split::INIT_SPACE,
+        seeds = [b"split", asset.mint.as_ref(), &mint_config.split_count.to_le_bytes()],
+        bump
+    )]
+    pub split: Account<'info, Split>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSplit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", asset.mint.as_ref()],
+        bump = mint_config.bump,
+        has_one = asset,
+        constraint = mint_config.active_split == split.key() @ AssetRegistryError::SplitNotActive
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"split", split.mint.as_ref(), &split.index.to_le_bytes()],
+        bump = split.bump,
+        close = authority
+    )]
+    pub split: Account<'info, Split>,
+}
+
+#[derive(Accounts)]
+pub struct ConvertSplitBalance<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = permanent_delegate,
+        constraint = mint_config.active_split == split.key() @ AssetRegistryError::SplitNotActive
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"split", mint.key().as_ref(), &split.index.to_le_bytes()],
+        bump = split.bump
+    )]
+    pub split: Account<'info, Split>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA derived from mint, signs as permanent delegate
+    #[account(
+        seeds = [b"permanent-delegate", mint.key().as_ref()],
+        bump
+    )]
+    pub permanent_delegate: AccountInfo<'info>,
+
+    /// CHECK: PDA mint authority
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// Any token account of the mint, associated or not
+    #[account(mut, token::mint = mint)]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Owner's lockup record; uninitialized if the owner has none
+    #[account(
+        mut,
+        seeds = [b"lockup", mint.key().as_ref(), holder_token_account.owner.as_ref()],
+        bump
+    )]
+    pub lockup: UncheckedAccount<'info>,
+
+    /// One conversion per token account and split
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SplitConversion::INIT_SPACE,
+        seeds = [b"split-conversion", split.key().as_ref(), holder_token_account.key().as_ref()],
+        bump
+    )]
+    pub conversion: Account<'info, SplitConversion>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConvertPendingSplitBalance<'info> {
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = permanent_delegate
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"split", mint.key().as_ref(), &split.index.to_le_bytes()],
+        bump = split.bump
+    )]
+    pub split: Account<'info, Split>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA derived from mint, signs as permanent delegate
+    #[account(
+        seeds = [b"permanent-delegate", mint.key().as_ref()],
+        bump
+    )]
+    pub permanent_delegate: AccountInfo<'info>,
+
+    /// CHECK: PDA mint authority
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    #[account(mut, token::mint = mint)]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receipt written by `finalize_split`
+    #[account(
+        mut,
+        seeds = [b"split-conversion", split.key().as_ref(), holder_token_account.key().as_ref()],
+        bump = conversion.bump,
+        constraint = conversion.pending @ AssetRegistryError::SplitBalanceAlreadyConverted
+    )]
+    pub conversion: Account<'info, SplitConversion>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteSplit<'info> {
+    #[account(
+        mut,
+        seeds = [b"asset", mint.key().as_ref()],
+        bump = asset.bump
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = asset,
+        constraint = mint_config.active_split == split.key() @ AssetRegistryError::SplitNotActive
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"split", mint.key().as_ref(), &split.index.to_le_bytes()],
+        bump = split.bump
+    )]
+    pub split: Account<'info, Split>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSplit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"asset", mint.key().as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = asset,
+        constraint = mint_config.active_split == split.key() @ AssetRegistryError::SplitNotActive
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"split", mint.key().as_ref(), &split.index.to_le_bytes()],
+        bump = split.bump
+    )]
+    pub split: Account<'info, Split>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA mint authority, freezes unconverted accounts
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeOverride<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    /// CHECK: Asset mint the override applies to
+    pub mint: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeOverride::INIT_SPACE,
+        seeds = [b"fee-override", mint.key().as_ref()],
+        bump
+    )]
+    pub fee_override: Account<'info, FeeOverride>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClearFeeOverride<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"fee-override", fee_override.mint.as_ref()],
+        bump = fee_override.bump,
+        close = authority
+    )]
+    pub fee_override: Account<'info, FeeOverride>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    /// CHECK: PDA owning the platform fee token accounts
+    #[account(
+        seeds = [b"fee-treasury"],
+        bump
+    )]
+    pub fee_treasury: AccountInfo<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = mint, token::authority = fee_treasury)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint)]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ForceTransfer<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::ComplianceOfficer, Some(&mint.key())) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = mint,
+        has_one = permanent_delegate
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA derived from mint, signs as permanent delegate
+    #[account(
+        seeds = [b"permanent-delegate", mint.key().as_ref()],
+        bump
+    )]
+    pub permanent_delegate: AccountInfo<'info>,
+
+    #[account(mut, token::mint = mint)]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint)]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct SeizeToQuarantine<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = role.authorizes(&authority.key(), Role::ComplianceOfficer, Some(&mint.key())) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = mint,
+        has_one = permanent_delegate
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA derived from mint, signs as permanent delegate
+    #[account(
+        seeds = [b"permanent-delegate", mint.key().as_ref()],
+        bump
+    )]
+    pub permanent_delegate: AccountInfo<'info>,
+
+    #[account(mut, token::mint = mint)]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// PDA owning the mint's quarantine token account
+    /// CHECK: PDA derived from mint, never signs
+    #[account(
+        seeds = [b"quarantine", mint.key().as_ref()],
+        bump
+    )]
+    pub quarantine: AccountInfo<'info>,
+
+    #[account(mut, token::mint = mint, token::authority = quarantine)]
+    pub quarantine_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeConfigAuthority<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuthorityTransfer::INIT_SPACE,
+        seeds = [b"authority-transfer", config.key().as_ref()],
+        bump
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptConfigAuthority<'info> {
+    #[account(mut)]
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.authority == authority_transfer.current_authority @ AssetRegistryError::StaleAuthorityTransfer
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"authority-transfer", config.key().as_ref()],
+        bump = authority_transfer.bump,
+        has_one = current_authority,
+        constraint = authority_transfer.proposed_authority == new_authority.key() @ AssetRegistryError::Unauthorized,
+        close = current_authority
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+
+    /// Outgoing authority, refunded the proposal and admin role rent
+    /// CHECK: Checked against the authority transfer record
+    #[account(mut)]
+    pub current_authority: AccountInfo<'info>,
+
+    /// Outgoing authority's registry admin assignment, closed on accept if
+    /// it still exists
+    /// CHECK: PDA derived from the outgoing authority; only closed when owned
+    /// by this program
+    #[account(
+        mut,
+        seeds = [b"role", crate::ID.as_ref(), &[Role::RegistryAdmin as u8], current_authority.key().as_ref()],
+        bump
+    )]
+    pub old_admin_role: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = new_authority,
+        space = 8 + RoleAssignment::INIT_SPACE,
+        seeds = [b"role", crate::ID.as_ref(), &[Role::RegistryAdmin as u8], new_authority.key().as_ref()],
+        bump
+    )]
+    pub new_admin_role: Account<'info, RoleAssignment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAssetAuthority<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuthorityTransfer::INIT_SPACE,
+        seeds = [b"authority-transfer", asset.key().as_ref()],
+        bump
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAssetAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        constraint = asset.authority == authority_transfer.current_authority @ AssetRegistryError::StaleAuthorityTransfer
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        mut,
+        seeds = [b"authority-transfer", asset.key().as_ref()],
+        bump = authority_transfer.bump,
+        has_one = current_authority,
+        constraint = authority_transfer.proposed_authority == new_authority.key() @ AssetRegistryError::Unauthorized,
+        close = current_authority
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+
+    /// Outgoing authority, refunded the proposal rent
+    /// CHECK: Checked against the authority transfer record
+    #[account(mut)]
+    pub current_authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeMintConfigAuthority<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"mint-config", mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+        has_one = authority
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AuthorityTransfer::INIT_SPACE,
+        seeds = [b"authority-transfer", mint_config.key().as_ref()],
+        bump
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptMintConfigAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+        constraint = mint_config.authority == authority_transfer.current_authority @ AssetRegistryError::StaleAuthorityTransfer
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"authority-transfer", mint_config.key().as_ref()],
+        bump = authority_transfer.bump,
+        has_one = current_authority,
+        constraint = authority_transfer.proposed_authority == new_authority.key() @ AssetRegistryError::Unauthorized,
+        close = current_authority
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+
+    /// Outgoing authority, refunded the proposal rent
+    /// CHECK: Checked against the authority transfer record
+    #[account(mut)]
+    pub current_authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub current_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"authority-transfer", authority_transfer.target.as_ref()],
+        bump = authority_transfer.bump,
+        has_one = current_authority,
+        close = current_authority
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+}
+
+#[derive(Accounts)]
+pub struct CreateRegistryApproverSet<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Creator's registry admin assignment
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ApproverSet::INIT_SPACE,
+        seeds = [b"approver-set", crate::ID.as_ref()],
+        bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AssignRegistryApproverSet<'info> {
+    pub authority: Signer<'info>,
+
+    /// Assigner's registry admin assignment
+    #[account(
+        constraint = admin_role.authorizes(&authority.key(), Role::RegistryAdmin, None) @ AssetRegistryError::Unauthorized
+    )]
+    pub admin_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"approver-set", crate::ID.as_ref()],
+        bump = approver_set.bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    #[account(
+        mut,
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        constraint = asset.approver_set == Pubkey::default() @ AssetRegistryError::ApproverSetAssigned
+    )]
+    pub asset: Account<'info, Asset>,
+}
+
+#[derive(Accounts)]
+pub struct CreateAssetApproverSet<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        has_one = authority,
+        constraint = asset.approver_set == Pubkey::default() @ AssetRegistryError::ApproverSetAssigned
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ApproverSet::INIT_SPACE,
+        seeds = [b"approver-set", asset.mint.as_ref()],
+        bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"approver-set", approver_set.scope.as_ref()],
+        bump = approver_set.bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal", approver_set.key().as_ref(), &approver_set.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub approver: Signer<'info>,
+
+    #[account(
+        seeds = [b"approver-set", approver_set.scope.as_ref()],
+        bump = approver_set.bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", approver_set.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = approver_set
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.approver_set.as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteActivateAsset<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"approver-set", approver_set.scope.as_ref()],
+        bump = approver_set.bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", approver_set.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = approver_set,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Proposer recorded on the proposal, refunded its rent
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"asset", asset.mint.as_ref()],
+        bump = asset.bump,
+        constraint = asset.approver_set == approver_set.key() @ AssetRegistryError::ApproverSetMismatch
+    )]
+    pub asset: Account<'info, Asset>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteFreezeMint<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"approver-set", approver_set.scope.as_ref()],
+        bump = approver_set.bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", approver_set.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = approver_set,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Proposer recorded on the proposal, refunded its rent
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    /// Pauser assignment held by the approver set
+    #[account(
+        constraint = role.authorizes(&approver_set.key(), Role::Pauser, Some(&mint_config.mint)) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// Asset governed by the approver set
+    #[account(
+        seeds = [b"asset", mint_config.mint.as_ref()],
+        bump = asset.bump,
+        constraint = asset.approver_set == approver_set.key() @ AssetRegistryError::ApproverSetMismatch
+    )]
+    pub asset: Account<'info, Asset>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteSetTransferHook<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"approver-set", approver_set.scope.as_ref()],
+        bump = approver_set.bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", approver_set.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = approver_set,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Proposer recorded on the proposal, refunded its rent
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    /// Compliance officer assignment held by the approver set
+    #[account(
+        constraint = role.authorizes(&approver_set.key(), Role::ComplianceOfficer, Some(&mint_config.mint)) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"mint-config", mint_config.mint.as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// Asset governed by the approver set
+    #[account(
+        seeds = [b"asset", mint_config.mint.as_ref()],
+        bump = asset.bump,
+        constraint = asset.approver_set == approver_set.key() @ AssetRegistryError::ApproverSetMismatch
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        init,
+        payer = executor,
+        space = 8 + QueuedChange::INIT_SPACE,
+        seeds = [b"timelock", mint_config.mint.as_ref()],
+        bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteMintTokens<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        seeds = [b"approver-set", approver_set.scope.as_ref()],
+        bump = approver_set.bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", approver_set.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = approver_set,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Proposer recorded on the proposal, refunded its rent
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    /// Minter assignment held by the approver set
+    #[account(
+        constraint = role.authorizes(&approver_set.key(), Role::Minter, Some(&mint.key())) @ AssetRegistryError::Unauthorized
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump = mint_config.bump,
+        has_one = asset
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    /// Asset governed by the approver set; its registered total supply caps issuance
+    #[account(
+        seeds = [b"asset", mint.key().as_ref()],
+        bump = asset.bump,
+        constraint = asset.approver_set == approver_set.key() @ AssetRegistryError::ApproverSetMismatch
+    )]
+    pub asset: Account<'info, Asset>,
+
+    /// CHECK: Token-2022 mint
+    #[account(mut)]
+    pub mint: AccountInfo<'info>,
+
+    /// CHECK: PDA mint authority
+    #[account(
+        seeds = [b"mint-authority", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// CHECK: Recipient wallet, matched against the proposal
+    pub recipient: AccountInfo<'info>,
+
+    /// Recipient's token account
+    #[account(mut, token::mint = mint, token::authority = recipient)]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
         seeds = [b"config"],
         bump = config.bump
     )]
     pub config: Account<'info, Config>,
 
-    pub authority: Signer<'info>,
+    /// Per-asset fee override, if one is set
+    #[account(
+        seeds = [b"fee-override", mint.key().as_ref()],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+
+    /// Custodian reserve record, required once the mint is reserve-backed
+    #[account(
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump = reserve.bump
+    )]
+    pub reserve: Option<Account<'info, Reserve>>,
+
+    /// CHECK: PDA owning the platform fee token accounts
+    #[account(
+        seeds = [b"fee-treasury"],
+        bump
+    )]
+    pub fee_treasury: AccountInfo<'info>,
+
+    /// Fee treasury token account for this mint
+    #[account(mut, token::mint = mint, token::authority = fee_treasury)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Recipient's lockup record, required when the proposal carries a lockup schedule
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + Lockup::INIT_SPACE,
+        seeds = [b"lockup", mint.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub lockup: Option<Account<'info, Lockup>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteUpdateApprovers<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"approver-set", approver_set.scope.as_ref()],
+        bump = approver_set.bump
+    )]
+    pub approver_set: Account<'info, ApproverSet>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", approver_set.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        has_one = approver_set,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Proposer recorded on the proposal, refunded its rent
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// Registry config in any earlier layout
+    /// CHECK: Owner is checked here; `migrate_account` checks the discriminator
+    #[account(mut, owner = crate::ID)]
+    pub config: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAsset<'info> {
+    /// Asset in any earlier layout
+    /// CHECK: Owner is checked here; `migrate_account` checks the discriminator
+    #[account(mut, owner = crate::ID)]
+    pub asset: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMintConfig<'info> {
+    /// Mint configuration in any earlier layout
+    /// CHECK: Owner is checked here; `migrate_account` checks the discriminator
+    #[account(mut, owner = crate::ID)]
+    pub mint_config: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ===========================================
+// STATE
+// ===========================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    /// Program authority
+    pub authority: Pubkey,
+    /// Platform fee in basis points (1 bps = 0.01%)
+    pub platform_fee_bps: u16,
+    /// Total number of registered assets
+    pub total_assets: u64,
+    /// PDA bump
+    pub bump: u8,
+    /// Layout version; 0 for accounts created before versioning
+    pub version: u8,
+    /// Oracle program whose price feeds assets may bind to; unset until an
+    /// admin pins it
+    pub pyth_program: Pubkey,
+    /// Zeroed space for future fields
+    pub reserved: [u8; 32],
+}
+
+impl Config {
+    /// Fee rate for an asset, honoring its override when present
+    pub fn fee_bps_for(&self, fee_override: Option<&FeeOverride>) -> u16 {
+        fee_override.map_or(self.platform_fee_bps, |fee_override| fee_override.fee_bps)
+    }
+}
+
+/// Head of a paged asset listing: global, per issuer or per asset type
+#[account]
+#[derive(InitSpace)]
+pub struct AssetIndex {
+    /// Assets ever appended; the next asset's position
+    pub count: u64,
+    /// Appended assets not yet burned
+    pub active: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl AssetIndex {
+    /// Page holding the asset at `position`
+    pub fn page_of(position: u64) -> u64 {
+        position / ASSET_INDEX_PAGE_LEN as u64
+    }
+
+    /// Page the next appended asset lands on
+    pub fn next_page(&self) -> u64 {
+        Self::page_of(self.count)
+    }
+}
+
+/// Fixed-size page of an asset index; burned assets are left as the default key
+#[account]
+#[derive(InitSpace)]
+pub struct AssetIndexPage {
+    /// Index this page belongs to
+    pub index: Pubkey,
+    /// Page number within the index
+    pub page: u64,
+    /// Asset PDAs in position order
+    #[max_len(ASSET_INDEX_PAGE_LEN)]
+    pub assets: Vec<Pubkey>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct FeeOverride {
+    /// Asset mint the override applies to
+    pub mint: Pubkey,
+    /// Fee in basis points replacing the platform fee
+    pub fee_bps: u16,
+    /// Last admin to set the override
+    pub updated_by: Pubkey,
+    /// Last update timestamp
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Asset {
+    /// Asset authority (bank)
+    pub authority: Pubkey,
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Asset name
+    #[max_len(64)]
+    pub name: String,
+    /// Type of asset
+    pub asset_type: AssetType,
+    /// Total value in cents (USD)
+    pub total_value: u64,
+    /// Total token supply
+    pub total_supply: u64,
+    /// Metadata URI (IPFS/Arweave)
+    #[max_len(256)]
+    pub metadata_uri: String,
+    /// Current status
+    pub status: AssetStatus,
+    /// Creation timestamp
+    pub created_at: i64,
+    /// Last update timestamp
+    pub updated_at: i64,
+    /// When the current status was entered
+    pub status_changed_at: i64,
+    /// Reason given for the current status, if any
+    pub status_reason: Option<StatusChangeReason>,
+    /// When the asset matured
+    pub matured_at: Option<i64>,
+    /// When the asset was declared in default
+    pub defaulted_at: Option<i64>,
+    /// When liquidation started
+    pub liquidation_started_at: Option<i64>,
+    /// When the asset was redeemed
+    pub redeemed_at: Option<i64>,
+    /// Authority that registered the asset, keying its issuer index
+    pub issuer: Pubkey,
+    /// Position in the global asset index
+    pub sequence: u64,
+    /// Position in the issuer's asset index
+    pub issuer_sequence: u64,
+    /// Position in the asset type index
+    pub type_sequence: u64,
+    /// PDA bump
+    pub bump: u8,
+    /// Layout version; 0 for accounts created before versioning
+    pub version: u8,
+    /// Approver set governing activation, minting, mint freezes and hook changes, or the default key
+    pub approver_set: Pubkey,
+    /// Zeroed space for future fields
+    pub reserved: [u8; 32],
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RealEstateAttributes {
+    /// Asset described
+    pub asset: Pubkey,
+    /// Hash of the normalized street address
+    pub address_hash: [u8; 32],
+    /// Land registry parcel identifier
+    #[max_len(32)]
+    pub parcel_id: String,
+    /// Gross floor area in square feet
+    pub square_footage: u64,
+    /// Occupied share of the lettable area
+    pub occupancy_bps: u16,
+    /// Last editor
+    pub updated_by: Pubkey,
+    /// Last update timestamp
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReceivableAttributes {
+    /// Asset described
+    pub asset: Pubkey,
+    /// Party that owes the receivable
+    pub obligor: Pubkey,
+    /// Face value in cents (USD)
+    pub face_value: u64,
+    /// Payment due timestamp
+    pub due_date: i64,
+    /// Annual coupon
+    pub coupon_bps: u16,
+    /// Last editor
+    pub updated_by: Pubkey,
+    /// Last update timestamp
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SecurityAttributes {
+    /// Asset described
+    pub asset: Pubkey,
+    /// Identifier scheme
+    pub identifier_type: SecurityIdentifierType,
+    /// ISIN or CUSIP
+    #[max_len(12)]
+    pub identifier: String,
+    /// Maturity timestamp
+    pub maturity: i64,
+    /// Annual coupon
+    pub coupon_bps: u16,
+    /// Last editor
+    pub updated_by: Pubkey,
+    /// Last update timestamp
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AssetDocument {
+    /// Asset the document governs
+    pub asset: Pubkey,
+    /// Kind of document
+    pub document_type: DocumentType,
+    /// SHA-256 of the document contents
+    pub content_hash: [u8; 32],
+    /// Where the document is stored (IPFS/Arweave)
+    #[max_len(256)]
+    pub uri: String,
+    /// Version within its supersede chain, starting at 1
+    pub version: u16,
+    /// When the document starts governing the asset
+    pub effective_at: i64,
+    /// Current status
+    pub status: DocumentStatus,
+    /// Version this document replaced
+    pub supersedes: Option<Pubkey>,
+    /// Version that replaced this document
+    pub superseded_by: Option<Pubkey>,
+    /// When the document stopped governing the asset
+    pub ended_at: Option<i64>,
+    /// Who registered the document
+    pub added_by: Pubkey,
+    /// Registration timestamp
+    pub added_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Appraiser {
+    /// Appraiser signing key
+    pub appraiser: Pubkey,
+    /// Professional license number
+    #[max_len(32)]
+    pub license_number: String,
+    /// License expiry timestamp
+    pub license_expiry: i64,
+    /// Asset types the appraiser is licensed to value
+    #[max_len(MAX_APPRAISER_ASSET_TYPES)]
+    pub asset_types: Vec<AssetType>,
+    /// Whether attestations are currently accepted
+    pub is_active: bool,
+    /// Admin who registered the appraiser
+    pub registered_by: Pubkey,
+    /// Registration timestamp
+    pub registered_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Custodian {
+    /// Custodian signing key
+    pub custodian: Pubkey,
+    /// Custodian name
+    #[max_len(64)]
+    pub name: String,
+    /// Whether attestations are currently accepted
+    pub is_active: bool,
+    /// Admin who registered the custodian
+    pub registered_by: Pubkey,
+    /// Registration timestamp
+    pub registered_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Latest custodian attestation of the collateral backing a mint
+#[account]
+#[derive(InitSpace)]
+pub struct Reserve {
+    /// Asset backed by the reserve
+    pub asset: Pubkey,
+    /// Token mint of the asset
+    pub mint: Pubkey,
+    /// Custodian holding the collateral
+    pub custodian: Pubkey,
+    /// Attested reserve in token base units
+    pub reserve_amount: u64,
+    /// Timestamp the custodian attested to; 0 before the first attestation
+    pub attested_at: i64,
+    /// Hash of the custodian's reserve report
+    pub report_hash: [u8; 32],
+    /// Attestations recorded so far
+    pub attestation_count: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ValuationHistory {
+    /// Asset the valuations belong to
+    pub asset: Pubkey,
+    /// Slot in `entries` the next valuation overwrites once the buffer is full
+    pub head: u16,
+    /// Total valuations ever recorded
+    pub total_recorded: u64,
+    /// Most recent valuations, oldest overwritten first
+    #[max_len(VALUATION_HISTORY_LEN)]
+    pub entries: Vec<ValuationEntry>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl ValuationHistory {
+    /// Append a valuation, overwriting the oldest entry when full
+    pub fn push(&mut self, entry: ValuationEntry) {
+        if self.entries.len() < VALUATION_HISTORY_LEN {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.head as usize] = entry;
+        }
+        self.head = ((self.head as usize + 1) % VALUATION_HISTORY_LEN) as u16;
+        self.total_recorded += 1;
+    }
+
+    /// Most recently recorded valuation
+    pub fn latest(&self) -> Option<&ValuationEntry> {
+        let index = (self.head as usize + VALUATION_HISTORY_LEN - 1) % VALUATION_HISTORY_LEN;
+        self.entries.get(index)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PriceFeedBinding {
+    /// Asset valued by the feed
+    pub asset: Pubkey,
+    /// Pyth-style price account
+    pub price_feed: Pubkey,
+    /// Program expected to own the price account
+    pub feed_program: Pubkey,
+    /// Units of the underlying the asset represents
+    pub units: u64,
+    /// Oldest accepted price, in seconds
+    pub max_staleness: i64,
+    /// Largest move per refresh without a valuation officer co-signer
+    pub max_deviation_bps: u16,
+    /// Last applied price mantissa
+    pub last_price: i64,
+    /// Last applied price exponent
+    pub last_expo: i32,
+    /// Publish time of the last applied price
+    pub last_publish_time: i64,
+    /// Slot of the last refresh
+    pub last_update_slot: u64,
+    /// Officer who bound the feed
+    pub bound_by: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct MintConfig {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Mint authority
+    pub authority: Pubkey,
+    /// Permanent delegate for freeze/seize
+    pub permanent_delegate: Pubkey,
+    /// Transfer hook program ID (optional)
+    pub transfer_hook_program: Option<Pubkey>,
+    /// Token name
+    #[max_len(32)]
+    pub name: String,
+    /// Token symbol
+    #[max_len(10)]
+    pub symbol: String,
+    /// Metadata URI
+    #[max_len(200)]
+    pub uri: String,
+    /// Token decimals
+    pub decimals: u8,
+    /// Whether the mint is frozen
+    pub is_frozen: bool,
+    /// Whether new token accounts start frozen until KYC thaws them
+    pub default_account_frozen: bool,
+    /// Asset this mint represents (set by `register_asset`)
+    pub asset: Pubkey,
+    /// Cumulative tokens minted
+    pub minted_supply: u64,
+    /// Cumulative tokens burned
+    pub burned_supply: u64,
+    /// Creation timestamp
+    pub created_at: i64,
+    /// PDA bump
+    pub bump: u8,
+    /// Layout version; 0 for accounts created before versioning
+    pub version: u8,
+    /// Split being converted, or the default key when there is none
+    pub active_split: Pubkey,
+    /// Splits declared so far; the next split's index
+    pub split_count: u32,
+    /// Whether issuance needs a fresh custodian attestation covering supply
+    pub requires_reserve: bool,
+    /// Balances left pending by `finalize_split` and not yet converted
+    pub pending_split_conversions: u32,
+    /// Zeroed space for future fields
+    pub reserved: [u8; 23],
+}
+
+impl MintConfig {
+    /// Tokens currently in circulation (minted minus burned)
+    pub fn outstanding_supply(&self) -> u64 {
+        self.minted_supply.saturating_sub(self.burned_supply)
+    }
+}
+
+/// `Config` as written before versioning; read only by `migrate_config`
+#[derive(AnchorDeserialize)]
+pub struct LegacyConfig {
+    pub authority: Pubkey,
+    pub platform_fee_bps: u16,
+    pub total_assets: u64,
+    pub bump: u8,
+}
+
+/// `Asset` as written before versioning; read only by `migrate_asset`
+#[derive(AnchorDeserialize)]
+pub struct LegacyAsset {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub asset_type: AssetType,
+    pub total_value: u64,
+    pub total_supply: u64,
+    pub metadata_uri: String,
+    pub status: AssetStatus,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
 }
 
-#[derive(Accounts)]
-#[instruction(name: String, symbol: String, uri: String)]
-pub struct CreateTokenMint<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+/// `MintConfig` as written before versioning; read only by `migrate_mint_config`
+#[derive(AnchorDeserialize)]
+pub struct LegacyMintConfig {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub permanent_delegate: Pubkey,
+    pub transfer_hook_program: Option<Pubkey>,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub decimals: u8,
+    pub is_frozen: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
 
-    /// The permanent delegate that can freeze/seize tokens
-    /// CHECK: Can be any pubkey designated as permanent delegate
-    pub permanent_delegate: AccountInfo<'info>,
+#[account]
+#[derive(InitSpace)]
+pub struct Lockup {
+    /// Token mint
+    pub mint: Pubkey,
+    /// Holder wallet whose transfers are restricted
+    pub holder: Pubkey,
+    /// Unvested issuance lots
+    #[max_len(MAX_LOCKUP_LOTS)]
+    pub lots: Vec<LockupLot>,
+    /// Last split the lots were rescaled for
+    pub last_split: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+}
 
-    /// The Token-2022 mint account (must be created externally with extensions)
-    /// CHECK: Will be validated as Token-2022 mint
-    pub mint: AccountInfo<'info>,
+impl Lockup {
+    /// Tokens still locked across all lots at `now`
+    pub fn locked_amount(&self, now: i64) -> u64 {
+        self.lots
+            .iter()
+            .fold(0u64, |locked, lot| locked.saturating_add(lot.locked_at(now)))
+    }
 
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + MintConfig::INIT_SPACE,
-        seeds = [b"mint-config", mint.key().as_ref()],
-        bump
-    )]
-    pub mint_config: Account<'info, MintConfig>,
+    /// Record a new lot, dropping lots that have fully vested
+    pub fn add_lot(&mut self, lot: LockupLot, now: i64) -> Result<()> {
+        self.lots.retain(|existing| existing.vesting_end > now);
+        require!(
+            self.lots.len() < MAX_LOCKUP_LOTS,
+            AssetRegistryError::TooManyLockupLots
+        );
+        self.lots.push(lot);
+        Ok(())
+    }
+}
 
-    /// PDA that will be the mint authority
-    /// CHECK: PDA derived from mint
-    #[account(
-        seeds = [b"mint-authority", mint.key().as_ref()],
-        bump
-    )]
-    pub mint_authority: AccountInfo<'info>,
+#[account]
+#[derive(InitSpace)]
+pub struct HolderFreeze {
+    /// Token mint
+    pub mint: Pubkey,
+    /// Frozen token account
+    pub token_account: Pubkey,
+    /// Owner of the frozen account
+    pub owner: Pubkey,
+    /// Why the account was frozen
+    pub reason: FreezeReason,
+    /// Legal case or ticket reference
+    #[max_len(64)]
+    pub case_reference: String,
+    /// Officer who froze the account
+    pub frozen_by: Pubkey,
+    /// Freeze timestamp
+    pub frozen_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
 
-    pub token_program: Program<'info, Token2022>,
-    pub system_program: Program<'info, System>,
+#[account]
+#[derive(InitSpace)]
+pub struct RoleAssignment {
+    /// Program ID for registry-wide roles, or the asset mint for asset-scoped roles
+    pub scope: Pubkey,
+    /// Granted role
+    pub role: Role,
+    /// Role holder
+    pub holder: Pubkey,
+    /// Who granted the role
+    pub granted_by: Pubkey,
+    /// Grant timestamp
+    pub granted_at: i64,
+    /// PDA bump
+    pub bump: u8,
 }
 
-#[derive(Accounts)]
-pub struct UpdateMintConfig<'info> {
-    pub authority: Signer<'info>,
+impl RoleAssignment {
+    /// Whether this assignment lets `holder` act as `role`, either registry-wide
+    /// or on the asset identified by `mint`
+    pub fn authorizes(&self, holder: &Pubkey, role: Role, mint: Option<&Pubkey>) -> bool {
+        self.holder == *holder
+            && self.role == role
+            && (self.scope == crate::ID || mint.is_some_and(|mint| self.scope == *mint))
+    }
+}
 
-    #[account(
-        mut,
-        seeds = [b"mint-config", mint_config.mint.as_ref()],
-        bump = mint_config.bump,
-        has_one = authority
-    )]
-    pub mint_config: Account<'info, MintConfig>,
+#[account]
+#[derive(InitSpace)]
+pub struct AuthorityTransfer {
+    /// Account whose authority is being handed over
+    pub target: Pubkey,
+    /// Kind of account being handed over
+    pub kind: AuthorityKind,
+    /// Authority at the time of the proposal
+    pub current_authority: Pubkey,
+    /// Authority that must accept the handover
+    pub proposed_authority: Pubkey,
+    /// Proposal timestamp
+    pub proposed_at: i64,
+    /// Optional deadline for acceptance
+    pub expires_at: Option<i64>,
+    /// PDA bump
+    pub bump: u8,
 }
 
-#[derive(Accounts)]
-pub struct MintTokens<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+impl AuthorityTransfer {
+    /// Whether the acceptance window has passed
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now > expires_at)
+    }
+}
 
-    #[account(
-        seeds = [b"mint-config", mint.key().as_ref()],
-        bump = mint_config.bump,
-        has_one = authority
-    )]
-    pub mint_config: Account<'info, MintConfig>,
+/// M-of-N approvers for sensitive actions, registry-wide or for one asset
+#[account]
+#[derive(InitSpace)]
+pub struct ApproverSet {
+    /// Asset mint the set governs, or the registry program ID for the global set
+    pub scope: Pubkey,
+    /// Keys allowed to propose and approve
+    #[max_len(MAX_APPROVERS)]
+    pub approvers: Vec<Pubkey>,
+    /// Approvals a proposal needs before it can execute
+    pub threshold: u8,
+    /// Proposals created so far; the next proposal's id
+    pub proposal_count: u64,
+    /// Incremented whenever the approvers change
+    pub generation: u32,
+    /// PDA bump
+    pub bump: u8,
+}
 
-    /// CHECK: Token-2022 mint
-    #[account(mut)]
-    pub mint: AccountInfo<'info>,
+impl ApproverSet {
+    /// Position of `key` in the approver list
+    pub fn position_of(&self, key: &Pubkey) -> Option<usize> {
+        self.approvers.iter().position(|approver| approver == key)
+    }
+}
 
-    /// CHECK: PDA mint authority
-    #[account(
-        seeds = [b"mint-authority", mint.key().as_ref()],
-        bump
-    )]
-    pub mint_authority: AccountInfo<'info>,
+/// Pending action awaiting approval by an approver set
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    /// Approver set deciding the proposal
+    pub approver_set: Pubkey,
+    /// Sequential id within the approver set
+    pub id: u64,
+    /// Action replayed on execution
+    pub action: ProposedAction,
+    /// Approver who created the proposal; receives its rent back
+    pub proposer: Pubkey,
+    /// Bitmap of approver positions that approved
+    pub approvals: u16,
+    /// Approver set generation the approvals refer to
+    pub generation: u32,
+    /// Creation timestamp
+    pub created_at: i64,
+    /// Last moment the proposal can be approved or executed
+    pub expires_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
 
-    /// CHECK: Recipient wallet
-    pub recipient: AccountInfo<'info>,
+impl Proposal {
+    /// Approvals recorded so far
+    pub fn approval_count(&self) -> u8 {
+        self.approvals.count_ones() as u8
+    }
 
-    /// CHECK: Recipient's token account
-    #[account(mut)]
-    pub recipient_token_account: AccountInfo<'info>,
+    /// Require the proposal to be unexpired and approved by the current approvers
+    pub fn require_open(&self, approver_set: &ApproverSet, now: i64) -> Result<()> {
+        require!(now <= self.expires_at, AssetRegistryError::ProposalExpired);
+        require!(
+            self.generation == approver_set.generation,
+            AssetRegistryError::ProposalStale
+        );
+        Ok(())
+    }
 
-    pub token_program: Program<'info, Token2022>,
+    /// Require the proposal to be open and to have reached the set's threshold
+    pub fn require_executable(&self, approver_set: &ApproverSet, now: i64) -> Result<()> {
+        self.require_open(approver_set, now)?;
+        require!(
+            self.approval_count() >= approver_set.threshold,
+            AssetRegistryError::ThresholdNotMet
+        );
+        Ok(())
+    }
 }
 
-// ===========================================
-// STATE
-// ===========================================
-
+/// Change waiting out `TIMELOCK_DELAY` before it can be applied
 #[account]
 #[derive(InitSpace)]
-pub struct Config {
-    /// Program authority
-    pub authority: Pubkey,
-    /// Platform fee in basis points (1 bps = 0.01%)
-    pub platform_fee_bps: u16,
-    /// Total number of registered assets
-    pub total_assets: u64,
+pub struct QueuedChange {
+    /// Account the change applies to (the mint for transfer hook changes)
+    pub target: Pubkey,
+    /// Change applied once the ETA passes
+    pub change: TimelockedChange,
+    /// Who queued the change; receives its rent back
+    pub queued_by: Pubkey,
+    /// Queue timestamp
+    pub queued_at: i64,
+    /// Earliest moment the change can be applied
+    pub eta: i64,
     /// PDA bump
     pub bump: u8,
 }
 
+/// Re-denomination of a mint's tokens, converted holder by holder
 #[account]
 #[derive(InitSpace)]
-pub struct Asset {
-    /// Asset authority (bank)
-    pub authority: Pubkey,
-    /// Token mint address
+pub struct Split {
+    /// Token mint being re-denominated
     pub mint: Pubkey,
-    /// Asset name
-    #[max_len(64)]
-    pub name: String,
-    /// Type of asset
-    pub asset_type: AssetType,
-    /// Total value in cents (USD)
-    pub total_value: u64,
-    /// Total token supply
-    pub total_supply: u64,
-    /// Metadata URI (IPFS/Arweave)
-    #[max_len(256)]
-    pub metadata_uri: String,
-    /// Current status
-    pub status: AssetStatus,
-    /// Creation timestamp
-    pub created_at: i64,
-    /// Last update timestamp
-    pub updated_at: i64,
+    /// Sequential index within the mint
+    pub index: u32,
+    /// Old tokens per conversion unit
+    pub old_units: u32,
+    /// New tokens per conversion unit
+    pub new_units: u32,
+    /// Conversions may start at this timestamp
+    pub effective_at: i64,
+    /// Asset authority that declared the split
+    pub declared_by: Pubkey,
+    /// Declaration timestamp
+    pub declared_at: i64,
+    /// Holders converted so far
+    pub holders_converted: u64,
+    /// Old tokens burned by conversions
+    pub converted_old_supply: u64,
+    /// New tokens minted by conversions
+    pub converted_new_supply: u64,
+    /// Old tokens that rounded away when converting balances
+    pub destroyed_remainder: u64,
+    /// When every balance had been converted
+    pub completed_at: Option<i64>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Split {
+    /// New tokens for `amount` old ones, rounded down
+    pub fn convert(&self, amount: u64) -> Result<u64> {
+        let converted = amount as u128 * self.new_units as u128 / self.old_units as u128;
+        u64::try_from(converted).map_err(|_| error!(AssetRegistryError::MathOverflow))
+    }
+
+    /// Old tokens in `amount` that did not convert into a whole new token
+    pub fn remainder(&self, amount: u64, converted: u64) -> u64 {
+        let backing = (converted as u128 * self.old_units as u128).div_ceil(self.new_units as u128);
+        amount.saturating_sub(backing as u64)
+    }
+}
+
+/// Receipt of a token account's balance conversion under a split
+#[account]
+#[derive(InitSpace)]
+pub struct SplitConversion {
+    /// Split applied
+    pub split: Pubkey,
+    /// Token account converted
+    pub token_account: Pubkey,
+    /// Owner of the token account
+    pub holder: Pubkey,
+    /// Balance burned
+    pub old_amount: u64,
+    /// Balance minted
+    pub new_amount: u64,
+    /// Conversion timestamp
+    pub converted_at: i64,
+    /// Whether the balance awaits `convert_pending_split_balance`
+    pub pending: bool,
+    /// Whether the account stays frozen after converting
+    pub keep_frozen: bool,
     /// PDA bump
     pub bump: u8,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct MintConfig {
-    /// Token mint address
-    pub mint: Pubkey,
-    /// Mint authority
-    pub authority: Pubkey,
-    /// Permanent delegate for freeze/seize
-    pub permanent_delegate: Pubkey,
-    /// Transfer hook program ID (optional)
-    pub transfer_hook_program: Option<Pubkey>,
-    /// Token name
-    #[max_len(32)]
-    pub name: String,
-    /// Token symbol
-    #[max_len(10)]
-    pub symbol: String,
-    /// Metadata URI
-    #[max_len(200)]
-    pub uri: String,
-    /// Token decimals
-    pub decimals: u8,
-    /// Whether the mint is frozen
-    pub is_frozen: bool,
-    /// Creation timestamp
-    pub created_at: i64,
-    /// PDA bump
-    pub bump: u8,
+// ===========================================
+// ENUMS
+// ===========================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AssetType {
+    RealEstate,
+    Equipment,
+    Receivables,
+    Securities,
+    Commodities,
+    IntellectualProperty,
+    Other,
+}
+
+/// Holding period applied to an issuance, relative to the mint time.
+/// A cliff equal to the vesting duration is a plain lock-up; a shorter cliff
+/// releases the lot linearly from issuance once the cliff has passed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct LockupSchedule {
+    /// Seconds before any of the lot unlocks
+    pub cliff_duration: i64,
+    /// Seconds until the whole lot has unlocked
+    pub vesting_duration: i64,
+}
+
+impl LockupSchedule {
+    /// Lot for `amount` issued at `now` under this schedule
+    pub fn lot_for(&self, amount: u64, now: i64) -> Result<LockupLot> {
+        require!(
+            self.cliff_duration >= 0
+                && self.vesting_duration > 0
+                && self.cliff_duration <= self.vesting_duration,
+            AssetRegistryError::InvalidLockupSchedule
+        );
+
+        Ok(LockupLot {
+            amount,
+            start: now,
+            cliff_end: now + self.cliff_duration,
+            vesting_end: now + self.vesting_duration,
+        })
+    }
+}
+
+/// Tokens from a single issuance held under a lockup schedule
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct LockupLot {
+    /// Tokens issued in the lot
+    pub amount: u64,
+    /// Issuance timestamp
+    pub start: i64,
+    /// Nothing unlocks before this timestamp
+    pub cliff_end: i64,
+    /// Everything has unlocked by this timestamp
+    pub vesting_end: i64,
+}
+
+impl LockupLot {
+    /// Tokens of this lot still locked at `now`
+    pub fn locked_at(&self, now: i64) -> u64 {
+        if now >= self.vesting_end {
+            return 0;
+        }
+        if now < self.cliff_end {
+            return self.amount;
+        }
+
+        let vested = (self.amount as u128) * (now - self.start) as u128
+            / (self.vesting_end - self.start) as u128;
+        self.amount - vested as u64
+    }
+}
+
+/// One recipient of a batch mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct MintAllocation {
+    /// Recipient wallet
+    pub recipient: Pubkey,
+    /// Tokens to issue, before the platform fee
+    pub amount: u64,
+    /// Holding period for the issued tokens, if any
+    pub lockup: Option<LockupSchedule>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ValuationEntry {
+    /// Asset value in cents (USD)
+    pub value: u64,
+    /// Recording timestamp
+    pub recorded_at: i64,
+    /// Basis of the valuation
+    pub method: ValuationMethod,
+    /// Who submitted the valuation
+    pub appraiser: Pubkey,
+    /// Hash of the valuation report
+    pub report_hash: [u8; 32],
+}
+
+/// Basis on which an asset was valued
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ValuationMethod {
+    /// Independent third-party appraisal
+    Appraisal,
+    /// Comparable market transactions
+    MarketComparable,
+    /// Discounted income or cash flows
+    IncomeApproach,
+    /// Replacement or historical cost
+    CostApproach,
+    /// Quoted market price
+    MarkToMarket,
+    /// Any other documented method
+    Other,
+}
+
+/// Segregated duties within the registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Role {
+    /// Manages registry-wide role assignments
+    RegistryAdmin,
+    /// Registers assets and creates their mints
+    Issuer,
+    /// Mints tokens within the registered supply
+    Minter,
+    /// Freezes and unfreezes assets and mints
+    Pauser,
+    /// Updates asset valuations
+    ValuationOfficer,
+    /// Manages transfer hook and regulatory controls
+    ComplianceOfficer,
+    /// Thaws token accounts of KYC-verified holders
+    KycOperator,
+    /// Cancels queued changes during their timelock
+    Guardian,
+}
+
+impl Role {
+    /// Operational roles that an asset authority may delegate for its own asset
+    pub fn is_asset_scoped(&self) -> bool {
+        matches!(
+            self,
+            Role::Minter
+                | Role::Pauser
+                | Role::ValuationOfficer
+                | Role::ComplianceOfficer
+                | Role::KycOperator
+        )
+    }
+}
+
+/// Structured reason codes for regulatory forced transfers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ForcedTransferReason {
+    /// Court or regulator order
+    CourtOrder,
+    /// Holder lost access to their keys
+    LostKeyRecovery,
+    /// Sanctions screening hit
+    Sanctions,
+    /// Fraud or theft remediation
+    Fraud,
+    /// Holder deceased, estate settlement
+    Estate,
+    /// Any other documented regulatory action
+    Other,
+}
+
+/// Why a single holder's account was frozen
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum FreezeReason {
+    /// Litigation or regulatory legal hold
+    LegalHold,
+    /// Court or regulator order
+    CourtOrder,
+    /// Sanctions screening hit
+    Sanctions,
+    /// Suspected fraud under investigation
+    FraudInvestigation,
+    /// Holder deceased, estate pending
+    Estate,
+    /// Any other documented reason
+    Other,
+}
+
+/// Where a platform fee was charged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum FeeSource {
+    /// Primary issuance via `mint_tokens`
+    Issuance,
+    /// Escrow release
+    EscrowRelease,
+    /// Auction settlement
+    AuctionSettlement,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum SecurityIdentifierType {
+    Isin,
+    Cusip,
+}
+
+/// Legal documents attached to an asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DocumentType {
+    OfferingMemorandum,
+    TitleDeed,
+    Appraisal,
+    AuditReport,
+    CustodyAgreement,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DocumentStatus {
+    Active,
+    /// Replaced by a newer version
+    Superseded,
+    /// Withdrawn without replacement
+    Revoked,
+}
+
+/// Sensitive operation carried out by `execute_*` once a proposal is approved
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ProposedAction {
+    /// `activate_asset` for the asset of `mint`
+    ActivateAsset { mint: Pubkey },
+    /// `freeze_mint`
+    FreezeMint { mint: Pubkey },
+    /// `queue_transfer_hook`
+    SetTransferHook { mint: Pubkey, transfer_hook_program: Pubkey },
+    /// `mint_tokens`
+    MintTokens {
+        mint: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        lockup: Option<LockupSchedule>,
+    },
+    /// Replace the proposing set's approvers and threshold
+    UpdateApprovers {
+        #[max_len(MAX_APPROVERS)]
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+    },
 }
 
-// ===========================================
-// ENUMS
-// ===========================================
+impl ProposedAction {
+    /// Whether an approver set scoped to `scope` may propose this action.
+    /// Execution further requires the set to govern the target asset.
+    pub fn allowed_in(&self, scope: &Pubkey) -> bool {
+        match self {
+            ProposedAction::ActivateAsset { mint }
+            | ProposedAction::FreezeMint { mint }
+            | ProposedAction::SetTransferHook { mint, .. }
+            | ProposedAction::MintTokens { mint, .. } => *scope == crate::ID || scope == mint,
+            ProposedAction::UpdateApprovers { .. } => true,
+        }
+    }
+}
+
+/// Change that must wait out `TIMELOCK_DELAY` before taking effect
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum TimelockedChange {
+    /// Point the mint's transfer hook at a new program
+    TransferHook { transfer_hook_program: Pubkey },
+}
 
+/// Accounts whose authority can be handed over
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
-pub enum AssetType {
-    RealEstate,
-    Equipment,
-    Receivables,
-    Securities,
-    Commodities,
-    IntellectualProperty,
-    Other,
+pub enum AuthorityKind {
+    Config,
+    Asset,
+    MintConfig,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
@@ -557,6 +7506,80 @@ pub enum AssetStatus {
     Active,
     Frozen,
     Burned,
+    /// Winding down: minting stopped, holder tokens being burned
+    Retiring,
+    /// Reached its maturity date, awaiting repayment
+    Matured,
+    /// Obligor failed to pay or breached a covenant
+    Defaulted,
+    /// Underlying being sold to repay holders
+    InLiquidation,
+    /// Holders repaid in full
+    Redeemed,
+}
+
+impl AssetStatus {
+    /// Allowed lifecycle transitions
+    pub fn can_transition_to(&self, next: AssetStatus) -> bool {
+        use AssetStatus::*;
+        matches!(
+            (self, next),
+            (Pending, Active)
+                | (Active, Frozen)
+                | (Active, Retiring)
+                | (Active, Matured)
+                | (Active, Defaulted)
+                | (Frozen, Active)
+                | (Frozen, Retiring)
+                | (Frozen, Matured)
+                | (Frozen, Defaulted)
+                | (Matured, Defaulted)
+                | (Matured, Redeemed)
+                | (Defaulted, Active)
+                | (Defaulted, InLiquidation)
+                | (Defaulted, Redeemed)
+                | (InLiquidation, Redeemed)
+                | (Redeemed, Retiring)
+                | (Retiring, Burned)
+        )
+    }
+
+    /// States from which the asset can never return to `Active`
+    pub fn is_wound_down(&self) -> bool {
+        matches!(
+            self,
+            AssetStatus::Retiring
+                | AssetStatus::Burned
+                | AssetStatus::InLiquidation
+                | AssetStatus::Redeemed
+        )
+    }
+
+    /// Defaulted or liquidating assets must not trade
+    pub fn is_distressed(&self) -> bool {
+        matches!(self, AssetStatus::Defaulted | AssetStatus::InLiquidation)
+    }
+}
+
+/// Why an asset entered its current lifecycle status
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum StatusChangeReason {
+    /// Scheduled maturity date reached
+    Maturity,
+    /// Missed principal or coupon payment
+    PaymentDefault,
+    /// Covenant breach
+    CovenantBreach,
+    /// Obligor insolvency or bankruptcy
+    Insolvency,
+    /// Default cured by the obligor
+    DefaultCured,
+    /// Court or regulator order
+    CourtOrder,
+    /// Holders repaid
+    Repayment,
+    /// Any other documented reason
+    Other,
 }
 
 // ===========================================
@@ -571,6 +7594,7 @@ pub struct AssetRegistered {
     pub name: String,
     pub total_value: u64,
     pub total_supply: u64,
+    pub sequence: u64,
 }
 
 #[event]
@@ -579,6 +7603,45 @@ pub struct AssetUpdated {
     pub updated_at: i64,
 }
 
+#[event]
+pub struct AssetAttributesUpdated {
+    pub asset: Pubkey,
+    pub asset_type: AssetType,
+    pub updated_by: Pubkey,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct DocumentAdded {
+    pub asset: Pubkey,
+    pub document: Pubkey,
+    pub document_type: DocumentType,
+    pub content_hash: [u8; 32],
+    pub version: u16,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct DocumentSuperseded {
+    pub asset: Pubkey,
+    pub previous_document: Pubkey,
+    pub document: Pubkey,
+    pub document_type: DocumentType,
+    pub content_hash: [u8; 32],
+    pub version: u16,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct DocumentRevoked {
+    pub asset: Pubkey,
+    pub document: Pubkey,
+    pub document_type: DocumentType,
+    pub content_hash: [u8; 32],
+    pub revoked_by: Pubkey,
+    pub revoked_at: i64,
+}
+
 #[event]
 pub struct AssetActivated {
     pub asset: Pubkey,
@@ -591,12 +7654,112 @@ pub struct AssetFrozen {
     pub frozen_at: i64,
 }
 
+#[event]
+pub struct AssetUnfrozen {
+    pub asset: Pubkey,
+    pub unfrozen_at: i64,
+}
+
+#[event]
+pub struct AssetStatusChanged {
+    pub asset: Pubkey,
+    pub previous_status: AssetStatus,
+    pub status: AssetStatus,
+    pub reason: Option<StatusChangeReason>,
+    pub changed_at: i64,
+}
+
 #[event]
 pub struct AssetBurned {
     pub asset: Pubkey,
     pub burned_at: i64,
 }
 
+#[event]
+pub struct AssetRetirementStarted {
+    pub asset: Pubkey,
+    pub started_at: i64,
+}
+
+#[event]
+pub struct TokensBurned {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub burned_supply: u64,
+    pub outstanding_supply: u64,
+    pub by_delegate: bool,
+}
+
+#[event]
+pub struct AssetClosed {
+    pub asset: Pubkey,
+    pub mint: Pubkey,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct AppraiserRegistered {
+    pub appraiser: Pubkey,
+    pub license_expiry: i64,
+    pub registered_by: Pubkey,
+}
+
+#[event]
+pub struct AppraiserUpdated {
+    pub appraiser: Pubkey,
+    pub license_expiry: i64,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct ValuationRecorded {
+    pub asset: Pubkey,
+    pub value: u64,
+    pub previous_value: u64,
+    pub method: ValuationMethod,
+    pub appraiser: Pubkey,
+    pub report_hash: [u8; 32],
+    pub sequence: u64,
+    pub recorded_at: i64,
+}
+
+#[event]
+pub struct PythProgramSet {
+    pub pyth_program: Pubkey,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct PriceFeedBound {
+    pub asset: Pubkey,
+    pub price_feed: Pubkey,
+    pub units: u64,
+    pub max_staleness: i64,
+    pub max_deviation_bps: u16,
+    pub bound_by: Pubkey,
+}
+
+#[event]
+pub struct PriceFeedUnbound {
+    pub asset: Pubkey,
+    pub price_feed: Pubkey,
+    pub unbound_by: Pubkey,
+}
+
+#[event]
+pub struct OracleValuationRefreshed {
+    pub asset: Pubkey,
+    pub price_feed: Pubkey,
+    pub price: i64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub total_value: u64,
+    pub previous_value: u64,
+    pub cosigned_by: Option<Pubkey>,
+    pub slot: u64,
+}
+
 #[event]
 pub struct TokenMintCreated {
     pub mint: Pubkey,
@@ -605,6 +7768,7 @@ pub struct TokenMintCreated {
     pub symbol: String,
     pub decimals: u8,
     pub transfer_hook_program: Option<Pubkey>,
+    pub default_account_frozen: bool,
 }
 
 #[event]
@@ -625,11 +7789,320 @@ pub struct MintUnfrozen {
     pub unfrozen_at: i64,
 }
 
+#[event]
+pub struct RoleGranted {
+    pub scope: Pubkey,
+    pub role: Role,
+    pub holder: Pubkey,
+    pub granted_by: Pubkey,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub scope: Pubkey,
+    pub role: Role,
+    pub holder: Pubkey,
+    pub revoked_by: Pubkey,
+}
+
+#[event]
+pub struct ForcedTransfer {
+    pub mint: Pubkey,
+    pub source: Pubkey,
+    pub source_owner: Pubkey,
+    pub destination: Pubkey,
+    pub destination_owner: Pubkey,
+    pub amount: u64,
+    pub reason: ForcedTransferReason,
+    pub document_hash: [u8; 32],
+    pub authority: Pubkey,
+    pub quarantined: bool,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct HolderFrozen {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub reason: FreezeReason,
+    pub case_reference: String,
+    pub frozen_by: Pubkey,
+    pub frozen_at: i64,
+}
+
+#[event]
+pub struct HolderThawed {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub case_reference: String,
+    pub thawed_by: Pubkey,
+    pub thawed_at: i64,
+}
+
+#[event]
+pub struct KycAccountThawed {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    pub thawed_by: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub target: Pubkey,
+    pub kind: AuthorityKind,
+    pub current_authority: Pubkey,
+    pub proposed_authority: Pubkey,
+    pub expires_at: Option<i64>,
+}
+
+#[event]
+pub struct AuthorityTransferAccepted {
+    pub target: Pubkey,
+    pub kind: AuthorityKind,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub target: Pubkey,
+    pub kind: AuthorityKind,
+    pub proposed_authority: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+/// Platform fee paid into the treasury; shared by the registry, escrow and auction programs
+#[event]
+pub struct FeeCollected {
+    pub source: FeeSource,
+    pub asset_mint: Pubkey,
+    /// Mint the fee was paid in; the asset mint itself for issuance
+    pub fee_mint: Pubkey,
+    pub amount: u64,
+    pub fee_bps: u16,
+    pub treasury_account: Pubkey,
+}
+
+#[event]
+pub struct FeeOverrideSet {
+    pub mint: Pubkey,
+    pub fee_bps: u16,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct FeeOverrideCleared {
+    pub mint: Pubkey,
+    pub cleared_by: Pubkey,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub withdrawn_by: Pubkey,
+}
+
 #[event]
 pub struct TokensMinted {
     pub mint: Pubkey,
     pub recipient: Pubkey,
     pub amount: u64,
+    pub fee: u64,
+    pub minted_supply: u64,
+    pub outstanding_supply: u64,
+    pub total_supply: u64,
+}
+
+#[event]
+pub struct LockupCreated {
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub start: i64,
+    pub cliff_end: i64,
+    pub vesting_end: i64,
+    pub locked_amount: u64,
+}
+
+#[event]
+pub struct BatchMinted {
+    pub mint: Pubkey,
+    pub recipients: u16,
+    pub total_amount: u64,
+    pub total_fee: u64,
+    pub minted_supply: u64,
+    pub outstanding_supply: u64,
+}
+
+#[event]
+pub struct ApproversUpdated {
+    pub approver_set: Pubkey,
+    pub scope: Pubkey,
+    pub approvers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub generation: u32,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub approver_set: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: ProposedAction,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub proposal: Pubkey,
+    pub approver: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub approver_set: Pubkey,
+    pub id: u64,
+    pub executed_by: Pubkey,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    pub proposal: Pubkey,
+    pub approver_set: Pubkey,
+    pub id: u64,
+}
+
+#[event]
+pub struct ChangeQueued {
+    pub queued_change: Pubkey,
+    pub target: Pubkey,
+    pub change: TimelockedChange,
+    pub queued_by: Pubkey,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ChangeApplied {
+    pub queued_change: Pubkey,
+    pub target: Pubkey,
+    pub change: TimelockedChange,
+}
+
+#[event]
+pub struct ChangeCancelled {
+    pub queued_change: Pubkey,
+    pub target: Pubkey,
+    pub change: TimelockedChange,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct SplitDeclared {
+    pub split: Pubkey,
+    pub mint: Pubkey,
+    pub index: u32,
+    pub old_units: u32,
+    pub new_units: u32,
+    pub effective_at: i64,
+    pub declared_by: Pubkey,
+}
+
+#[event]
+pub struct SplitCancelled {
+    pub split: Pubkey,
+    pub mint: Pubkey,
+    pub index: u32,
+}
+
+#[event]
+pub struct SplitBalanceConverted {
+    pub split: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub holder: Pubkey,
+    pub old_amount: u64,
+    pub new_amount: u64,
+    pub remainder: u64,
+}
+
+#[event]
+pub struct SplitCompleted {
+    pub split: Pubkey,
+    pub mint: Pubkey,
+    pub old_units: u32,
+    pub new_units: u32,
+    pub holders_converted: u64,
+    pub old_total_supply: u64,
+    pub new_total_supply: u64,
+    pub outstanding_supply: u64,
+    pub destroyed_remainder: u64,
+    pub completed_at: i64,
+}
+
+#[event]
+pub struct SplitFinalized {
+    pub split: Pubkey,
+    pub mint: Pubkey,
+    pub pending_accounts: u32,
+    pub unconverted_old_supply: u64,
+    pub finalized_by: Pubkey,
+}
+
+#[event]
+pub struct CustodianRegistered {
+    pub custodian: Pubkey,
+    pub name: String,
+    pub registered_by: Pubkey,
+}
+
+#[event]
+pub struct CustodianUpdated {
+    pub custodian: Pubkey,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct CustodianAssigned {
+    pub asset: Pubkey,
+    pub mint: Pubkey,
+    pub custodian: Pubkey,
+    pub assigned_by: Pubkey,
+}
+
+#[event]
+pub struct ReserveAttested {
+    pub asset: Pubkey,
+    pub mint: Pubkey,
+    pub custodian: Pubkey,
+    pub reserve_amount: u64,
+    pub outstanding_supply: u64,
+    pub attested_at: i64,
+    pub report_hash: [u8; 32],
+}
+
+#[event]
+pub struct ReserveCoverageDropped {
+    pub asset: Pubkey,
+    pub mint: Pubkey,
+    pub previous_reserve: u64,
+    pub reserve_amount: u64,
+    pub outstanding_supply: u64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
 }
 
 // ===========================================
@@ -660,4 +8133,160 @@ pub enum AssetRegistryError {
     MintIsFrozen,
     #[msg("Unauthorized")]
     Unauthorized,
+    #[msg("Mint is not a Token-2022 mint")]
+    InvalidMint,
+    #[msg("Mint authority is not the registry mint authority PDA")]
+    InvalidMintAuthority,
+    #[msg("Mint is missing a required RWA extension")]
+    MissingMintExtension,
+    #[msg("Invalid token amount")]
+    InvalidAmount,
+    #[msg("Asset is not active")]
+    AssetNotActive,
+    #[msg("Minting would exceed the asset's registered total supply")]
+    SupplyCapExceeded,
+    #[msg("Mint is already linked to an asset")]
+    MintAlreadyLinked,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Role cannot be granted in this scope")]
+    InvalidRoleScope,
+    #[msg("Registry admins cannot revoke their own admin role")]
+    CannotRevokeOwnAdminRole,
+    #[msg("Proposed authority must differ from the current authority")]
+    InvalidProposedAuthority,
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("Authority transfer has expired")]
+    AuthorityTransferExpired,
+    #[msg("Authority changed since the transfer was proposed")]
+    StaleAuthorityTransfer,
+    #[msg("Document hash is required")]
+    InvalidDocumentHash,
+    #[msg("Destination must differ from source")]
+    InvalidDestination,
+    #[msg("Asset is not being retired")]
+    AssetNotRetiring,
+    #[msg("Tokens are still outstanding")]
+    SupplyOutstanding,
+    #[msg("Fee exceeds 10000 basis points")]
+    InvalidFeeBps,
+    #[msg("License number too long (max 32 chars)")]
+    LicenseNumberTooLong,
+    #[msg("Invalid appraiser asset types")]
+    InvalidAssetTypes,
+    #[msg("Appraiser is not active")]
+    AppraiserInactive,
+    #[msg("Appraiser license has expired")]
+    AppraiserLicenseExpired,
+    #[msg("Appraiser is not licensed for this asset type")]
+    AppraiserNotQualified,
+    #[msg("Valuation timestamp is stale or in the future")]
+    StaleValuation,
+    #[msg("Missing Ed25519 signature verification instruction")]
+    MissingSignatureVerification,
+    #[msg("Ed25519 signature does not match the valuation")]
+    InvalidSignatureVerification,
+    #[msg("Invalid price feed binding parameters")]
+    InvalidOracleConfig,
+    #[msg("Account is not a valid price feed")]
+    InvalidPriceFeed,
+    #[msg("Price feed is not trading")]
+    PriceUnavailable,
+    #[msg("Price is stale")]
+    StalePrice,
+    #[msg("Price moved beyond the deviation cap without a valuation officer co-signer")]
+    PriceDeviationExceeded,
+    #[msg("Document is not active")]
+    DocumentNotActive,
+    #[msg("Effective date precedes the document it replaces")]
+    InvalidEffectiveDate,
+    #[msg("Attributes do not match the asset type")]
+    AssetTypeMismatch,
+    #[msg("Invalid asset attributes")]
+    InvalidAttributes,
+    #[msg("Invalid ISIN or CUSIP")]
+    InvalidSecurityIdentifier,
+    #[msg("Asset status transition is not allowed")]
+    InvalidStatusTransition,
+    #[msg("Case reference is required (max 64 characters)")]
+    InvalidCaseReference,
+    #[msg("Token account is under a legal hold")]
+    AccountUnderLegalHold,
+    #[msg("Owner is not an active, KYC-verified whitelist entry")]
+    KycNotVerified,
+    #[msg("Batch must contain between 1 and 32 allocations")]
+    InvalidBatchSize,
+    #[msg("Remaining accounts do not match the batch allocations")]
+    InvalidBatchAccounts,
+    #[msg("Lockup cliff must not exceed a positive vesting duration")]
+    InvalidLockupSchedule,
+    #[msg("A lockup schedule requires the recipient's lockup account")]
+    LockupAccountMissing,
+    #[msg("Holder has too many unvested lockup lots")]
+    TooManyLockupLots,
+    #[msg("Asset is not at its recorded index position")]
+    AssetIndexMismatch,
+    #[msg("Account is already at the current layout version")]
+    AccountAlreadyMigrated,
+    #[msg("Approvers must be 1 to 16 distinct keys")]
+    InvalidApprovers,
+    #[msg("Threshold must be between 1 and the number of approvers")]
+    InvalidThreshold,
+    #[msg("Signer is not an approver of this set")]
+    NotAnApprover,
+    #[msg("Approver has already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+    #[msg("Approvers changed since the proposal was created")]
+    ProposalStale,
+    #[msg("Proposal has not reached the approval threshold")]
+    ThresholdNotMet,
+    #[msg("Proposal action does not match the accounts supplied")]
+    ProposalActionMismatch,
+    #[msg("Approver set cannot carry out this action")]
+    ApproverSetMismatch,
+    #[msg("Action requires an approved proposal")]
+    ApprovalRequired,
+    #[msg("Queued change cannot be applied before its ETA")]
+    TimelockNotElapsed,
+    #[msg("Split ratio must be two different positive unit counts")]
+    InvalidSplitRatio,
+    #[msg("Split cannot take effect in the past")]
+    InvalidSplitEffectiveTime,
+    #[msg("A split is being converted for this mint")]
+    SplitInProgress,
+    #[msg("Split is not the mint's active split")]
+    SplitNotActive,
+    #[msg("Split has not taken effect yet")]
+    SplitNotEffective,
+    #[msg("Balances have already been converted under this split")]
+    SplitConversionStarted,
+    #[msg("Some balances have not been converted yet")]
+    SplitConversionIncomplete,
+    #[msg("Custodian is not active")]
+    CustodianInactive,
+    #[msg("Reserve attestation is stale or in the future")]
+    StaleReserveAttestation,
+    #[msg("Outstanding supply would exceed the attested reserve")]
+    InsufficientReserves,
+    #[msg("Reserve account required for reserve-backed mints")]
+    ReserveAccountMissing,
+    #[msg("Token accounts on this mint start frozen; create and thaw them before minting")]
+    FrozenAccountCreation,
+    #[msg("Asset already governed by an approver set")]
+    ApproverSetAssigned,
+    #[msg("Asset account required for a registered mint")]
+    AssetAccountMissing,
+    #[msg("Balances from the previous split are still pending conversion")]
+    SplitConversionsPending,
+    #[msg("Balance has already been converted under this split")]
+    SplitBalanceAlreadyConverted,
+    #[msg("Token account holds a balance awaiting split conversion")]
+    SplitBalancePending,
+    #[msg("No price feed program has been pinned in the registry config")]
+    PythProgramNotSet,
+    #[msg("Price feed is not owned by the pinned price feed program")]
+    UnapprovedFeedProgram,
 }
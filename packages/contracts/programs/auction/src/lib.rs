@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::{self, Token2022, TransferChecked};
+use anchor_spl::token_interface::{Mint, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
+use asset_registry::{
+    calculate_fee, load_asset_status, migrate_account, Config, FeeCollected, FeeOverride, FeeSource,
+    Versioned,
+};
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnU");
 
@@ -31,6 +36,7 @@ pub mod auction {
         require!(start_time >= clock.unix_timestamp, AuctionError::InvalidStartTime);
         require!(end_time > start_time, AuctionError::InvalidEndTime);
         require!(end_time - start_time >= 3600, AuctionError::AuctionTooShort); // Min 1 hour
+        require_tradeable(&ctx.accounts.asset)?;
 
         let auction = &mut ctx.accounts.auction;
         auction.seller = ctx.accounts.seller.key();
@@ -48,6 +54,7 @@ pub mod auction {
         auction.total_bids = 0;
         auction.created_at = clock.unix_timestamp;
         auction.bump = ctx.bumps.auction;
+        auction.version = ACCOUNT_VERSION;
 
         // Transfer asset tokens from seller to auction vault
         let transfer_ctx = CpiContext::new(
@@ -95,6 +102,7 @@ pub mod auction {
             ctx.accounts.bidder.key() != auction.seller,
             AuctionError::SellerCannotBid
         );
+        require_tradeable(&ctx.accounts.asset)?;
 
         // Check bid is valid
         if auction.current_bid == 0 {
@@ -166,6 +174,7 @@ pub mod auction {
         bid.timestamp = clock.unix_timestamp;
         bid.status = BidStatus::Active;
         bid.bump = ctx.bumps.bid;
+        bid.version = ACCOUNT_VERSION;
 
         // Update auction state
         auction.current_bid = bid_amount;
@@ -258,6 +267,14 @@ pub mod auction {
                 ctx.accounts.asset_mint.decimals,
             )?;
 
+            // Platform fee is deducted from the seller's proceeds
+            let fee_bps = ctx
+                .accounts
+                .config
+                .fee_bps_for(ctx.accounts.fee_override.as_deref());
+            let fee = calculate_fee(auction.current_bid, fee_bps)
+                .ok_or(AuctionError::MathOverflow)?;
+
             // Transfer payment to seller
             let transfer_payment_ctx = CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
@@ -272,10 +289,39 @@ pub mod auction {
 
             token_2022::transfer_checked(
                 transfer_payment_ctx,
-                auction.current_bid,
+                auction.current_bid - fee,
                 ctx.accounts.payment_mint.decimals,
             )?;
 
+            // Transfer platform fee to the registry fee treasury
+            if fee > 0 {
+                let transfer_fee_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.auction_payment_vault.to_account_info(),
+                        mint: ctx.accounts.payment_mint.to_account_info(),
+                        to: ctx.accounts.treasury_payment_account.to_account_info(),
+                        authority: auction.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+
+                token_2022::transfer_checked(
+                    transfer_fee_ctx,
+                    fee,
+                    ctx.accounts.payment_mint.decimals,
+                )?;
+
+                emit!(FeeCollected {
+                    source: FeeSource::AuctionSettlement,
+                    asset_mint: auction.asset_mint,
+                    fee_mint: auction.payment_mint,
+                    amount: fee,
+                    fee_bps,
+                    treasury_account: ctx.accounts.treasury_payment_account.key(),
+                });
+            }
+
             auction.status = AuctionStatus::Settled;
 
             emit!(AuctionSettled {
@@ -416,14 +462,116 @@ pub mod auction {
         msg!("Auction extended from {} to {}", old_end_time, new_end_time);
         Ok(())
     }
+
+    /// Upgrade an auction to the current account layout
+    /// Permissionless; the payer funds any extra rent
+    pub fn migrate_auction(ctx: Context<MigrateAuction>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        migrate::<Auction>(&accounts.auction, &accounts.payer, &accounts.system_program)?;
+        msg!("Auction {} migrated to layout v{}", accounts.auction.key(), ACCOUNT_VERSION);
+        Ok(())
+    }
+
+    /// Upgrade a bid to the current account layout
+    /// Permissionless; the payer funds any extra rent
+    pub fn migrate_bid(ctx: Context<MigrateBid>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        migrate::<Bid>(&accounts.bid, &accounts.payer, &accounts.system_program)?;
+        msg!("Bid {} migrated to layout v{}", accounts.bid.key(), ACCOUNT_VERSION);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Refuse registered assets that are defaulted or in liquidation
+fn require_tradeable(asset: &UncheckedAccount) -> Result<()> {
+    if let Some(status) = load_asset_status(asset)? {
+        require!(!status.is_distressed(), AuctionError::AssetNotTradeable);
+    }
+    Ok(())
+}
+
+impl Versioned for Auction {
+    type Legacy = LegacyAuction;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+
+    fn from_legacy(legacy: LegacyAuction) -> Self {
+        Auction {
+            seller: legacy.seller,
+            asset_mint: legacy.asset_mint,
+            payment_mint: legacy.payment_mint,
+            asset_amount: legacy.asset_amount,
+            starting_price: legacy.starting_price,
+            reserve_price: legacy.reserve_price,
+            min_bid_increment: legacy.min_bid_increment,
+            current_bid: legacy.current_bid,
+            current_bidder: legacy.current_bidder,
+            start_time: legacy.start_time,
+            end_time: legacy.end_time,
+            status: legacy.status,
+            total_bids: legacy.total_bids,
+            created_at: legacy.created_at,
+            bump: legacy.bump,
+            version: 0,
+            reserved: [0; 64],
+        }
+    }
+}
+
+impl Versioned for Bid {
+    type Legacy = LegacyBid;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+
+    fn from_legacy(legacy: LegacyBid) -> Self {
+        Bid {
+            auction: legacy.auction,
+            bidder: legacy.bidder,
+            amount: legacy.amount,
+            timestamp: legacy.timestamp,
+            status: legacy.status,
+            bump: legacy.bump,
+            version: 0,
+            reserved: [0; 64],
+        }
+    }
+}
+
+/// Upgrade an account to `ACCOUNT_VERSION` through the registry's shared migration
+fn migrate<'info, T: Versioned>(
+    info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let from_version = migrate_account::<T>(info, payer, system_program, ACCOUNT_VERSION)?;
+    require!(from_version < ACCOUNT_VERSION, AuctionError::AccountAlreadyMigrated);
+
+    emit!(AccountMigrated {
+        account: info.key(),
+        from_version,
+        to_version: ACCOUNT_VERSION,
+    });
+    Ok(())
 }
 
 // ============================================================================
 // Account Structures
 // ============================================================================
 
+/// Layout version written to new accounts; new fields are carved out of the
+/// reserved padding at the end of each account and gated on a version bump
+pub const ACCOUNT_VERSION: u8 = 1;
+
 #[account]
-#[derive(Default)]
+#[derive(InitSpace)]
 pub struct Auction {
     /// Seller who created the auction
     pub seller: Pubkey,
@@ -455,29 +603,14 @@ pub struct Auction {
     pub created_at: i64,
     /// PDA bump
     pub bump: u8,
-}
-
-impl Auction {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // seller
-        32 + // asset_mint
-        32 + // payment_mint
-        8 +  // asset_amount
-        8 +  // starting_price
-        8 +  // reserve_price
-        8 +  // min_bid_increment
-        8 +  // current_bid
-        32 + // current_bidder
-        8 +  // start_time
-        8 +  // end_time
-        1 +  // status
-        8 +  // total_bids
-        8 +  // created_at
-        1;   // bump
+    /// Layout version; 0 for accounts created before versioning
+    pub version: u8,
+    /// Zeroed space for future fields
+    pub reserved: [u8; 64],
 }
 
 #[account]
-#[derive(Default)]
+#[derive(InitSpace)]
 pub struct Bid {
     /// Auction this bid is for
     pub auction: Pubkey,
@@ -491,23 +624,48 @@ pub struct Bid {
     pub status: BidStatus,
     /// PDA bump
     pub bump: u8,
+    /// Layout version; 0 for accounts created before versioning
+    pub version: u8,
+    /// Zeroed space for future fields
+    pub reserved: [u8; 64],
 }
 
-impl Bid {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // auction
-        32 + // bidder
-        8 +  // amount
-        8 +  // timestamp
-        1 +  // status
-        1;   // bump
+/// `Auction` as written before versioning; read only by `migrate_auction`
+#[derive(AnchorDeserialize)]
+pub struct LegacyAuction {
+    pub seller: Pubkey,
+    pub asset_mint: Pubkey,
+    pub payment_mint: Pubkey,
+    pub asset_amount: u64,
+    pub starting_price: u64,
+    pub reserve_price: u64,
+    pub min_bid_increment: u64,
+    pub current_bid: u64,
+    pub current_bidder: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub status: AuctionStatus,
+    pub total_bids: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// `Bid` as written before versioning; read only by `migrate_bid`
+#[derive(AnchorDeserialize)]
+pub struct LegacyBid {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub status: BidStatus,
+    pub bump: u8,
 }
 
 // ============================================================================
 // Enums
 // ============================================================================
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
 pub enum AuctionStatus {
     #[default]
     Created,
@@ -517,7 +675,7 @@ pub enum AuctionStatus {
     Failed,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
 pub enum BidStatus {
     #[default]
     Active,
@@ -539,15 +697,23 @@ pub struct CreateAuction<'info> {
     #[account(
         init,
         payer = seller,
-        space = Auction::LEN,
+        space = 8 + Auction::INIT_SPACE,
         seeds = [b"auction", seller.key().as_ref(), asset_mint.key().as_ref(), &Clock::get()?.unix_timestamp.to_le_bytes()],
         bump
     )]
     pub auction: Account<'info, Auction>,
 
-    pub asset_mint: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Mint>>,
+    pub asset_mint: InterfaceAccount<'info, Mint>,
     
-    pub payment_mint: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Mint>>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Registry asset PDA for the mint; uninitialized if the mint is not a registered asset
+    #[account(
+        seeds = [b"asset", asset_mint.key().as_ref()],
+        bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub asset: UncheckedAccount<'info>,
 
     #[account(
         mut,
@@ -555,7 +721,7 @@ pub struct CreateAuction<'info> {
         associated_token::authority = seller,
         associated_token::token_program = token_program,
     )]
-    pub seller_asset_account: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Account>>,
+    pub seller_asset_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init,
@@ -564,7 +730,7 @@ pub struct CreateAuction<'info> {
         associated_token::authority = auction,
         associated_token::token_program = token_program,
     )]
-    pub auction_asset_vault: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Account>>,
+    pub auction_asset_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init,
@@ -573,7 +739,7 @@ pub struct CreateAuction<'info> {
         associated_token::authority = auction,
         associated_token::token_program = token_program,
     )]
-    pub auction_payment_vault: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Account>>,
+    pub auction_payment_vault: InterfaceAccount<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -595,13 +761,21 @@ pub struct PlaceBid<'info> {
     #[account(
         init,
         payer = bidder,
-        space = Bid::LEN,
+        space = 8 + Bid::INIT_SPACE,
         seeds = [b"bid", auction.key().as_ref(), bidder.key().as_ref()],
         bump
     )]
     pub bid: Account<'info, Bid>,
 
-    pub payment_mint: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Mint>>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Registry asset PDA for the mint; uninitialized if the mint is not a registered asset
+    #[account(
+        seeds = [b"asset", auction.asset_mint.as_ref()],
+        bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub asset: UncheckedAccount<'info>,
 
     #[account(
         mut,
@@ -609,7 +783,7 @@ pub struct PlaceBid<'info> {
         associated_token::authority = bidder,
         associated_token::token_program = token_program,
     )]
-    pub bidder_payment_account: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Account>>,
+    pub bidder_payment_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
@@ -617,7 +791,7 @@ pub struct PlaceBid<'info> {
         associated_token::authority = auction,
         associated_token::token_program = token_program,
     )]
-    pub auction_payment_vault: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Account>>,
+    pub auction_payment_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Previous bidder's payment account for refund
     #[account(mut)]
@@ -655,9 +829,11 @@ pub struct SettleAuction<'info> {
     )]
     pub auction: Account<'info, Auction>,
 
-    pub asset_mint: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Mint>>,
+    #[account(address = auction.asset_mint)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
     
-    pub payment_mint: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Mint>>,
+    #[account(address = auction.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -665,7 +841,7 @@ pub struct SettleAuction<'info> {
         associated_token::authority = auction,
         associated_token::token_program = token_program,
     )]
-    pub auction_asset_vault: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Account>>,
+    pub auction_asset_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
@@ -673,7 +849,7 @@ pub struct SettleAuction<'info> {
         associated_token::authority = auction,
         associated_token::token_program = token_program,
     )]
-    pub auction_payment_vault: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Account>>,
+    pub auction_payment_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Winner's asset account
     #[account(mut)]
@@ -691,6 +867,37 @@ pub struct SettleAuction<'info> {
     #[account(mut)]
     pub seller_payment_account: UncheckedAccount<'info>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Per-asset fee override, if one is set
+    #[account(
+        seeds = [b"fee-override", asset_mint.key().as_ref()],
+        bump = fee_override.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+
+    /// CHECK: Registry PDA owning the platform fee token accounts
+    #[account(
+        seeds = [b"fee-treasury"],
+        bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    /// Fee treasury token account for the payment mint
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = fee_treasury,
+    )]
+    pub treasury_payment_account: InterfaceAccount<'info, TokenAccount>,
+
     pub token_program: Program<'info, Token2022>,
 }
 
@@ -706,7 +913,7 @@ pub struct CancelAuction<'info> {
     )]
     pub auction: Account<'info, Auction>,
 
-    pub asset_mint: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Mint>>,
+    pub asset_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -714,7 +921,7 @@ pub struct CancelAuction<'info> {
         associated_token::authority = auction,
         associated_token::token_program = token_program,
     )]
-    pub auction_asset_vault: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Account>>,
+    pub auction_asset_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
@@ -722,7 +929,7 @@ pub struct CancelAuction<'info> {
         associated_token::authority = authority,
         associated_token::token_program = token_program,
     )]
-    pub seller_asset_account: InterfaceAccount<'info, token_2022::spl_token_2022::extension::BaseStateWithExtensions<token_2022::spl_token_2022::state::Account>>,
+    pub seller_asset_account: InterfaceAccount<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token2022>,
 }
@@ -740,6 +947,32 @@ pub struct ExtendAuction<'info> {
     pub auction: Account<'info, Auction>,
 }
 
+#[derive(Accounts)]
+pub struct MigrateAuction<'info> {
+    /// An auction in any earlier layout
+    /// CHECK: Owner is checked here; `migrate_account` checks the discriminator
+    #[account(mut, owner = crate::ID)]
+    pub auction: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateBid<'info> {
+    /// A bid in any earlier layout
+    /// CHECK: Owner is checked here; `migrate_account` checks the discriminator
+    #[account(mut, owner = crate::ID)]
+    pub bid: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 // ============================================================================
 // Events
 // ============================================================================
@@ -805,6 +1038,13 @@ pub struct AuctionExtended {
     pub new_end_time: i64,
 }
 
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
 // ============================================================================
 // Errors
 // ============================================================================
@@ -847,4 +1087,10 @@ pub enum AuctionError {
     CannotCancelAuction,
     #[msg("Auction has active bids")]
     HasActiveBids,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Asset is defaulted or in liquidation")]
+    AssetNotTradeable,
+    #[msg("Account is already at the current layout version")]
+    AccountAlreadyMigrated,
 }
@@ -1,6 +1,17 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_2022::{self, Token2022};
-use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions},
+    state::Account as TokenAccountState,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use asset_registry::program::AssetRegistry;
+use asset_registry::{
+    load_asset_status, load_locked_amount, load_mint_frozen, migrate_account, AssetStatus, Role,
+    RoleAssignment, Versioned, TIMELOCK_DELAY,
+};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnV");
 
@@ -28,6 +39,7 @@ pub mod compliance {
         config.total_whitelisted = 0;
         config.total_blacklisted = 0;
         config.bump = ctx.bumps.config;
+        config.version = ACCOUNT_VERSION;
 
         emit!(ComplianceInitialized {
             authority: config.authority,
@@ -56,6 +68,7 @@ pub mod compliance {
         whitelist_entry.last_transfer = 0;
         whitelist_entry.is_active = true;
         whitelist_entry.bump = ctx.bumps.whitelist_entry;
+        whitelist_entry.version = ACCOUNT_VERSION;
 
         let config = &mut ctx.accounts.config;
         config.total_whitelisted += 1;
@@ -102,6 +115,7 @@ pub mod compliance {
         blacklist_entry.added_by = ctx.accounts.authority.key();
         blacklist_entry.is_active = true;
         blacklist_entry.bump = ctx.bumps.blacklist_entry;
+        blacklist_entry.version = ACCOUNT_VERSION;
 
         let config = &mut ctx.accounts.config;
         config.total_blacklisted += 1;
@@ -146,6 +160,7 @@ pub mod compliance {
         rule.max_amount = max_amount;
         rule.created_at = Clock::get()?.unix_timestamp;
         rule.bump = ctx.bumps.jurisdiction_rule;
+        rule.version = ACCOUNT_VERSION;
 
         emit!(JurisdictionRuleAdded {
             from_jurisdiction,
@@ -157,50 +172,145 @@ pub mod compliance {
         Ok(())
     }
 
-    /// Update compliance configuration
-    pub fn update_config(
-        ctx: Context<UpdateConfig>,
+    /// Pause or resume transfers
+    /// Takes effect immediately; transfer limits go through `queue_config_update`
+    pub fn update_config(ctx: Context<UpdateConfig>, is_paused: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.is_paused = is_paused;
+
+        if is_paused {
+            emit!(TransfersPaused {
+                paused_by: ctx.accounts.authority.key(),
+            });
+        } else {
+            emit!(TransfersResumed {
+                resumed_by: ctx.accounts.authority.key(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Queue new transfer limits. They can be applied once the registry's
+    /// `TIMELOCK_DELAY` has passed, and a registry guardian may cancel them until then.
+    pub fn queue_config_update(
+        ctx: Context<QueueConfigUpdate>,
         max_transfer_amount: Option<u64>,
         transfer_cooldown: Option<i64>,
-        is_paused: Option<bool>,
     ) -> Result<()> {
+        require!(
+            max_transfer_amount.is_some() || transfer_cooldown.is_some(),
+            ComplianceError::EmptyConfigUpdate
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let eta = now + TIMELOCK_DELAY;
+
+        let queued_update = &mut ctx.accounts.queued_update;
+        queued_update.config = ctx.accounts.config.key();
+        queued_update.max_transfer_amount = max_transfer_amount;
+        queued_update.transfer_cooldown = transfer_cooldown;
+        queued_update.queued_by = ctx.accounts.authority.key();
+        queued_update.queued_at = now;
+        queued_update.eta = eta;
+        queued_update.bump = ctx.bumps.queued_update;
+
+        emit!(ConfigUpdateQueued {
+            queued_update: queued_update.key(),
+            max_transfer_amount,
+            transfer_cooldown,
+            queued_by: queued_update.queued_by,
+            eta,
+        });
+
+        msg!("Config update queued until {}", eta);
+        Ok(())
+    }
+
+    /// Apply queued transfer limits once their ETA has passed (permissionless)
+    pub fn apply_config_update(ctx: Context<ApplyConfigUpdate>) -> Result<()> {
+        let queued_update = &ctx.accounts.queued_update;
+        require!(
+            Clock::get()?.unix_timestamp >= queued_update.eta,
+            ComplianceError::TimelockNotElapsed
+        );
         let config = &mut ctx.accounts.config;
 
-        if let Some(amount) = max_transfer_amount {
+        if let Some(amount) = queued_update.max_transfer_amount {
             config.max_transfer_amount = amount;
         }
 
-        if let Some(cooldown) = transfer_cooldown {
+        if let Some(cooldown) = queued_update.transfer_cooldown {
             config.transfer_cooldown = cooldown;
         }
 
-        if let Some(paused) = is_paused {
-            config.is_paused = paused;
-            if paused {
-                emit!(TransfersPaused {
-                    paused_by: ctx.accounts.authority.key(),
-                });
-            } else {
-                emit!(TransfersResumed {
-                    resumed_by: ctx.accounts.authority.key(),
-                });
-            }
-        }
+        emit!(ConfigUpdateApplied {
+            queued_update: queued_update.key(),
+            max_transfer_amount: queued_update.max_transfer_amount,
+            transfer_cooldown: queued_update.transfer_cooldown,
+        });
 
         Ok(())
     }
 
+    /// Drop queued transfer limits before they are applied (registry guardian only)
+    pub fn cancel_config_update(ctx: Context<CancelConfigUpdate>) -> Result<()> {
+        emit!(ConfigUpdateCancelled {
+            queued_update: ctx.accounts.queued_update.key(),
+            cancelled_by: ctx.accounts.guardian.key(),
+        });
+
+        msg!("Config update cancelled");
+        Ok(())
+    }
+
+    /// Create the validation account listing the extra accounts Token-2022
+    /// passes to the hook. Permissionless; the list is the same for every mint.
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        let extra_account_metas = extra_account_metas()?;
+        let mut data = ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &extra_account_metas)?;
+
+        msg!("Transfer hook accounts initialized for mint: {}", ctx.accounts.mint.key());
+        Ok(())
+    }
+
     /// Token-2022 Transfer Hook - Execute transfer validation
     /// This is called by the Token-2022 program during transfers
     pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
         let config = &ctx.accounts.config;
         let clock = Clock::get()?;
+        let mint = ctx.accounts.mint.key();
+        let sender = ctx.accounts.source_token_account.owner;
+        let receiver = ctx.accounts.destination_token_account.owner;
+
+        // Court orders and recoveries move tokens through the registry's
+        // permanent delegate and skip holder checks
+        let (permanent_delegate, _) = Pubkey::find_program_address(
+            &[b"permanent-delegate", mint.as_ref()],
+            &asset_registry::ID,
+        );
+        if ctx.accounts.authority.key() == permanent_delegate {
+            msg!("Forced transfer of {} tokens from {}", amount, sender);
+            return Ok(());
+        }
 
         // Check if transfers are paused
         require!(!config.is_paused, ComplianceError::TransfersPaused);
 
+        // Check the registry allows the asset to trade; mints it never
+        // registered have no asset or mint config to consult
+        if let Some(status) = load_asset_status(&ctx.accounts.asset)? {
+            require!(status == AssetStatus::Active, ComplianceError::AssetNotTradeable);
+        }
+        require!(
+            !load_mint_frozen(&ctx.accounts.mint_config)?,
+            ComplianceError::MintFrozen
+        );
+
         // Check sender is not blacklisted
-        if let Some(sender_blacklist) = &ctx.accounts.sender_blacklist {
+        if let Some(sender_blacklist) = load_entry::<BlacklistEntry>(&ctx.accounts.sender_blacklist)? {
             require!(
                 !sender_blacklist.is_active,
                 ComplianceError::SenderBlacklisted
@@ -208,7 +318,7 @@ pub mod compliance {
         }
 
         // Check receiver is not blacklisted
-        if let Some(receiver_blacklist) = &ctx.accounts.receiver_blacklist {
+        if let Some(receiver_blacklist) = load_entry::<BlacklistEntry>(&ctx.accounts.receiver_blacklist)? {
             require!(
                 !receiver_blacklist.is_active,
                 ComplianceError::ReceiverBlacklisted
@@ -216,7 +326,7 @@ pub mod compliance {
         }
 
         // Check sender whitelist and KYC
-        let sender_whitelist = ctx.accounts.sender_whitelist.as_ref()
+        let sender_whitelist = load_entry::<WhitelistEntry>(&ctx.accounts.sender_whitelist)?
             .ok_or(ComplianceError::SenderNotWhitelisted)?;
         require!(sender_whitelist.is_active, ComplianceError::SenderNotWhitelisted);
         require!(
@@ -225,7 +335,7 @@ pub mod compliance {
         );
 
         // Check receiver whitelist and KYC
-        let receiver_whitelist = ctx.accounts.receiver_whitelist.as_ref()
+        let receiver_whitelist = load_entry::<WhitelistEntry>(&ctx.accounts.receiver_whitelist)?
             .ok_or(ComplianceError::ReceiverNotWhitelisted)?;
         require!(receiver_whitelist.is_active, ComplianceError::ReceiverNotWhitelisted);
         require!(
@@ -247,8 +357,31 @@ pub mod compliance {
             );
         }
 
-        // Check jurisdiction rules
-        if let Some(jurisdiction_rule) = &ctx.accounts.jurisdiction_rule {
+        // Check the transfer leaves any unvested lockup lots in place
+        let locked = load_locked_amount(&ctx.accounts.sender_lockup, clock.unix_timestamp)?;
+        if locked > 0 {
+            let balance = balance_before_transfer(&ctx.accounts.source_token_account, amount)?;
+            require!(
+                amount <= balance.saturating_sub(locked),
+                ComplianceError::TransferExceedsUnlocked
+            );
+        }
+
+        // Check the rule between the sender's and receiver's jurisdictions, if any
+        let (rule_key, _) = Pubkey::find_program_address(
+            &[
+                b"jurisdiction",
+                sender_whitelist.jurisdiction.as_ref(),
+                receiver_whitelist.jurisdiction.as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            ctx.accounts.jurisdiction_rule.key(),
+            rule_key,
+            ComplianceError::InvalidJurisdictionRule
+        );
+        if let Some(jurisdiction_rule) = load_entry::<JurisdictionRule>(&ctx.accounts.jurisdiction_rule)? {
             require!(
                 jurisdiction_rule.is_allowed,
                 ComplianceError::JurisdictionNotAllowed
@@ -263,17 +396,13 @@ pub mod compliance {
         }
 
         emit!(TransferValidated {
-            sender: ctx.accounts.sender.key(),
-            receiver: ctx.accounts.receiver.key(),
+            sender,
+            receiver,
             amount,
             timestamp: clock.unix_timestamp,
         });
 
-        msg!("Transfer validated: {} tokens from {} to {}", 
-            amount, 
-            ctx.accounts.sender.key(), 
-            ctx.accounts.receiver.key()
-        );
+        msg!("Transfer validated: {} tokens from {} to {}", amount, sender, receiver);
         Ok(())
     }
 
@@ -302,34 +431,272 @@ pub mod compliance {
         Ok(())
     }
 
+    /// Thaw a whitelisted investor's default-frozen token account in the asset registry
+    pub fn thaw_whitelisted_account(ctx: Context<ThawWhitelistedAccount>) -> Result<()> {
+        let seeds = &[b"compliance-config".as_ref(), &[ctx.accounts.config.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        asset_registry::cpi::thaw_kyc_account(CpiContext::new_with_signer(
+            ctx.accounts.registry_program.to_account_info(),
+            asset_registry::cpi::accounts::ThawKycAccount {
+                authority: ctx.accounts.config.to_account_info(),
+                kyc_role: None,
+                mint: ctx.accounts.mint.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                holder_token_account: ctx.accounts.holder_token_account.to_account_info(),
+                whitelist_entry: ctx.accounts.whitelist_entry.to_account_info(),
+                holder_freeze: ctx.accounts.holder_freeze.to_account_info(),
+                mint_config: ctx.accounts.mint_config.to_account_info(),
+                last_split: ctx.accounts.last_split.to_account_info(),
+                split_conversion: ctx.accounts.split_conversion.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        msg!("Token account thawed for: {}", ctx.accounts.whitelist_entry.investor);
+        Ok(())
+    }
+
     /// Fallback instruction for transfer hook interface
+    /// Token-2022 calls the interface's `Execute` during transfers, which is
+    /// routed into `transfer_hook`
     pub fn fallback<'info>(
-        _program_id: &Pubkey,
+        program_id: &Pubkey,
         accounts: &'info [AccountInfo<'info>],
         data: &[u8],
     ) -> Result<()> {
-        let instruction = ExecuteInstruction::unpack(data)?;
-
-        // The transfer hook validation happens here
-        // Token-2022 calls this during token transfers
-        
-        match instruction {
-            ExecuteInstruction::Execute { amount } => {
-                msg!("Transfer hook executed for amount: {}", amount);
-                // Additional validation can be done here
+        match TransferHookInstruction::unpack(data)? {
+            TransferHookInstruction::Execute { amount } => {
+                __private::__global::transfer_hook(program_id, accounts, &amount.to_le_bytes())
             }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
         }
+    }
+
+    /// Upgrade the compliance config to the current account layout
+    /// Permissionless; the payer funds any extra rent
+    pub fn migrate_config(ctx: Context<MigrateComplianceConfig>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        migrate::<ComplianceConfig>(&accounts.config, &accounts.payer, &accounts.system_program)?;
+        msg!("Compliance config {} migrated to layout v{}", accounts.config.key(), ACCOUNT_VERSION);
+        Ok(())
+    }
+
+    /// Upgrade a whitelist entry to the current account layout
+    /// Permissionless; the payer funds any extra rent
+    pub fn migrate_whitelist_entry(ctx: Context<MigrateWhitelistEntry>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        migrate::<WhitelistEntry>(&accounts.whitelist_entry, &accounts.payer, &accounts.system_program)?;
+        msg!("Whitelist entry {} migrated to layout v{}", accounts.whitelist_entry.key(), ACCOUNT_VERSION);
+        Ok(())
+    }
+
+    /// Upgrade a blacklist entry to the current account layout
+    /// Permissionless; the payer funds any extra rent
+    pub fn migrate_blacklist_entry(ctx: Context<MigrateBlacklistEntry>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        migrate::<BlacklistEntry>(&accounts.blacklist_entry, &accounts.payer, &accounts.system_program)?;
+        msg!("Blacklist entry {} migrated to layout v{}", accounts.blacklist_entry.key(), ACCOUNT_VERSION);
+        Ok(())
+    }
 
+    /// Upgrade a jurisdiction rule to the current account layout
+    /// Permissionless; the payer funds any extra rent
+    pub fn migrate_jurisdiction_rule(ctx: Context<MigrateJurisdictionRule>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        migrate::<JurisdictionRule>(&accounts.jurisdiction_rule, &accounts.payer, &accounts.system_program)?;
+        msg!("Jurisdiction rule {} migrated to layout v{}", accounts.jurisdiction_rule.key(), ACCOUNT_VERSION);
         Ok(())
     }
 }
 
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Offset of `WhitelistEntry.jurisdiction`: discriminator, investor, investor type
+const WHITELIST_JURISDICTION_OFFSET: u8 = 8 + 32 + 1;
+
+/// Accounts Token-2022 appends to `Execute` after the validation account, in
+/// `TransferHook` order. Holders are read from the owner field of the source
+/// (index 0) and destination (index 2) token accounts rather than the signer,
+/// which may be a delegate. The jurisdiction rule is keyed on both whitelist
+/// entries, so Token-2022 can only resolve it, forced transfers included, when
+/// both holders have one.
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    let literal = |bytes: &[u8]| Seed::Literal { bytes: bytes.to_vec() };
+    let mint = Seed::AccountKey { index: 1 };
+    let sender = Seed::AccountData { account_index: 0, data_index: 32, length: 32 };
+    let receiver = Seed::AccountData { account_index: 2, data_index: 32, length: 32 };
+    let jurisdiction = |whitelist_index: u8| Seed::AccountData {
+        account_index: whitelist_index,
+        data_index: WHITELIST_JURISDICTION_OFFSET,
+        length: 2,
+    };
+    // Indexes of the registry program and the whitelist entries in the full account list
+    let registry = 6;
+    let (sender_whitelist, receiver_whitelist) = (10, 11);
+
+    Ok(vec![
+        ExtraAccountMeta::new_with_seeds(&[literal(b"compliance-config")], false, false)?,
+        ExtraAccountMeta::new_with_pubkey(&asset_registry::ID, false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(registry, &[literal(b"asset"), mint.clone()], false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(registry, &[literal(b"mint-config"), mint.clone()], false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(registry, &[literal(b"lockup"), mint, sender.clone()], false, false)?,
+        ExtraAccountMeta::new_with_seeds(&[literal(b"whitelist"), sender.clone()], false, false)?,
+        ExtraAccountMeta::new_with_seeds(&[literal(b"whitelist"), receiver.clone()], false, false)?,
+        ExtraAccountMeta::new_with_seeds(&[literal(b"blacklist"), sender], false, false)?,
+        ExtraAccountMeta::new_with_seeds(&[literal(b"blacklist"), receiver], false, false)?,
+        ExtraAccountMeta::new_with_seeds(
+            &[literal(b"jurisdiction"), jurisdiction(sender_whitelist), jurisdiction(receiver_whitelist)],
+            false,
+            false,
+        )?,
+    ])
+}
+
+/// Entry stored at a whitelist or blacklist PDA, or `None` if it was never created
+fn load_entry<T: AccountDeserialize>(info: &AccountInfo) -> Result<Option<T>> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+
+    let data = info.try_borrow_data()?;
+    Ok(Some(T::try_deserialize(&mut &data[..])?))
+}
+
+/// Sender balance before this transfer. Token-2022 debits the source and marks
+/// it as transferring before invoking the hook; direct calls see the balance
+/// untouched.
+fn balance_before_transfer(source: &InterfaceAccount<TokenAccount>, amount: u64) -> Result<u64> {
+    let info = source.to_account_info();
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    let transferring = state
+        .get_extension::<TransferHookAccount>()
+        .map(|extension| bool::from(extension.transferring))
+        .unwrap_or(false);
+
+    Ok(if transferring { source.amount.saturating_add(amount) } else { source.amount })
+}
+
+impl Versioned for ComplianceConfig {
+    type Legacy = LegacyComplianceConfig;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+
+    fn from_legacy(legacy: LegacyComplianceConfig) -> Self {
+        ComplianceConfig {
+            authority: legacy.authority,
+            civic_gatekeeper_network: legacy.civic_gatekeeper_network,
+            max_transfer_amount: legacy.max_transfer_amount,
+            transfer_cooldown: legacy.transfer_cooldown,
+            is_paused: legacy.is_paused,
+            total_whitelisted: legacy.total_whitelisted,
+            total_blacklisted: legacy.total_blacklisted,
+            bump: legacy.bump,
+            version: 0,
+            reserved: [0; 64],
+        }
+    }
+}
+
+impl Versioned for WhitelistEntry {
+    type Legacy = LegacyWhitelistEntry;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+
+    fn from_legacy(legacy: LegacyWhitelistEntry) -> Self {
+        WhitelistEntry {
+            investor: legacy.investor,
+            investor_type: legacy.investor_type,
+            jurisdiction: legacy.jurisdiction,
+            kyc_verified: legacy.kyc_verified,
+            kyc_expiry: legacy.kyc_expiry,
+            added_at: legacy.added_at,
+            last_transfer: legacy.last_transfer,
+            is_active: legacy.is_active,
+            bump: legacy.bump,
+            version: 0,
+            reserved: [0; 64],
+        }
+    }
+}
+
+impl Versioned for BlacklistEntry {
+    type Legacy = LegacyBlacklistEntry;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+
+    fn from_legacy(legacy: LegacyBlacklistEntry) -> Self {
+        BlacklistEntry {
+            address: legacy.address,
+            reason: legacy.reason,
+            added_at: legacy.added_at,
+            added_by: legacy.added_by,
+            is_active: legacy.is_active,
+            bump: legacy.bump,
+            version: 0,
+            reserved: [0; 64],
+        }
+    }
+}
+
+impl Versioned for JurisdictionRule {
+    type Legacy = LegacyJurisdictionRule;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+
+    fn from_legacy(legacy: LegacyJurisdictionRule) -> Self {
+        JurisdictionRule {
+            from_jurisdiction: legacy.from_jurisdiction,
+            to_jurisdiction: legacy.to_jurisdiction,
+            is_allowed: legacy.is_allowed,
+            max_amount: legacy.max_amount,
+            created_at: legacy.created_at,
+            bump: legacy.bump,
+            version: 0,
+            reserved: [0; 64],
+        }
+    }
+}
+
+/// Upgrade an account to `ACCOUNT_VERSION` through the registry's shared migration
+fn migrate<'info, T: Versioned>(
+    info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let from_version = migrate_account::<T>(info, payer, system_program, ACCOUNT_VERSION)?;
+    require!(from_version < ACCOUNT_VERSION, ComplianceError::AccountAlreadyMigrated);
+
+    emit!(AccountMigrated {
+        account: info.key(),
+        from_version,
+        to_version: ACCOUNT_VERSION,
+    });
+    Ok(())
+}
+
 // ============================================================================
 // Account Structures
 // ============================================================================
 
+/// Layout version written to new accounts; new fields are carved out of the
+/// reserved padding at the end of each account and gated on a version bump
+pub const ACCOUNT_VERSION: u8 = 1;
+
 #[account]
-#[derive(Default)]
+#[derive(InitSpace)]
 pub struct ComplianceConfig {
     /// Admin authority
     pub authority: Pubkey,
@@ -347,22 +714,14 @@ pub struct ComplianceConfig {
     pub total_blacklisted: u64,
     /// PDA bump
     pub bump: u8,
-}
-
-impl ComplianceConfig {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // authority
-        32 + // civic_gatekeeper_network
-        8 +  // max_transfer_amount
-        8 +  // transfer_cooldown
-        1 +  // is_paused
-        8 +  // total_whitelisted
-        8 +  // total_blacklisted
-        1;   // bump
+    /// Layout version; 0 for accounts created before versioning
+    pub version: u8,
+    /// Zeroed space for future fields
+    pub reserved: [u8; 64],
 }
 
 #[account]
-#[derive(Default)]
+#[derive(InitSpace)]
 pub struct WhitelistEntry {
     /// Investor address
     pub investor: Pubkey,
@@ -382,26 +741,19 @@ pub struct WhitelistEntry {
     pub is_active: bool,
     /// PDA bump
     pub bump: u8,
-}
-
-impl WhitelistEntry {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // investor
-        1 +  // investor_type
-        2 +  // jurisdiction
-        1 +  // kyc_verified
-        8 +  // kyc_expiry
-        8 +  // added_at
-        8 +  // last_transfer
-        1 +  // is_active
-        1;   // bump
+    /// Layout version; 0 for accounts created before versioning
+    pub version: u8,
+    /// Zeroed space for future fields
+    pub reserved: [u8; 64],
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct BlacklistEntry {
     /// Blacklisted address
     pub address: Pubkey,
     /// Reason for blacklisting
+    #[max_len(128)]
     pub reason: String,
     /// When the address was added
     pub added_at: i64,
@@ -411,20 +763,14 @@ pub struct BlacklistEntry {
     pub is_active: bool,
     /// PDA bump
     pub bump: u8,
-}
-
-impl BlacklistEntry {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // address
-        4 + 128 + // reason (string prefix + max length)
-        8 +  // added_at
-        32 + // added_by
-        1 +  // is_active
-        1;   // bump
+    /// Layout version; 0 for accounts created before versioning
+    pub version: u8,
+    /// Zeroed space for future fields
+    pub reserved: [u8; 64],
 }
 
 #[account]
-#[derive(Default)]
+#[derive(InitSpace)]
 pub struct JurisdictionRule {
     /// Source jurisdiction (ISO 3166-1 alpha-2)
     pub from_jurisdiction: [u8; 2],
@@ -438,23 +784,86 @@ pub struct JurisdictionRule {
     pub created_at: i64,
     /// PDA bump
     pub bump: u8,
+    /// Layout version; 0 for accounts created before versioning
+    pub version: u8,
+    /// Zeroed space for future fields
+    pub reserved: [u8; 64],
 }
 
-impl JurisdictionRule {
-    pub const LEN: usize = 8 + // discriminator
-        2 +  // from_jurisdiction
-        2 +  // to_jurisdiction
-        1 +  // is_allowed
-        1 + 8 + // max_amount (option)
-        8 +  // created_at
-        1;   // bump
+/// `ComplianceConfig` as written before versioning; read only by `migrate_config`
+#[derive(AnchorDeserialize)]
+pub struct LegacyComplianceConfig {
+    pub authority: Pubkey,
+    pub civic_gatekeeper_network: Pubkey,
+    pub max_transfer_amount: u64,
+    pub transfer_cooldown: i64,
+    pub is_paused: bool,
+    pub total_whitelisted: u64,
+    pub total_blacklisted: u64,
+    pub bump: u8,
+}
+
+/// `WhitelistEntry` as written before versioning; read only by `migrate_whitelist_entry`
+#[derive(AnchorDeserialize)]
+pub struct LegacyWhitelistEntry {
+    pub investor: Pubkey,
+    pub investor_type: InvestorType,
+    pub jurisdiction: [u8; 2],
+    pub kyc_verified: bool,
+    pub kyc_expiry: i64,
+    pub added_at: i64,
+    pub last_transfer: i64,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+/// `BlacklistEntry` as written before versioning; read only by `migrate_blacklist_entry`
+#[derive(AnchorDeserialize)]
+pub struct LegacyBlacklistEntry {
+    pub address: Pubkey,
+    pub reason: String,
+    pub added_at: i64,
+    pub added_by: Pubkey,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+/// `JurisdictionRule` as written before versioning; read only by `migrate_jurisdiction_rule`
+#[derive(AnchorDeserialize)]
+pub struct LegacyJurisdictionRule {
+    pub from_jurisdiction: [u8; 2],
+    pub to_jurisdiction: [u8; 2],
+    pub is_allowed: bool,
+    pub max_amount: Option<u64>,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// Transfer limits waiting out the timelock before they replace the config's
+#[account]
+#[derive(InitSpace)]
+pub struct QueuedConfigUpdate {
+    /// Compliance config the update applies to
+    pub config: Pubkey,
+    /// New maximum transfer amount, if changing
+    pub max_transfer_amount: Option<u64>,
+    /// New transfer cooldown, if changing
+    pub transfer_cooldown: Option<i64>,
+    /// Who queued the update; receives its rent back
+    pub queued_by: Pubkey,
+    /// Queue timestamp
+    pub queued_at: i64,
+    /// Earliest moment the update can be applied
+    pub eta: i64,
+    /// PDA bump
+    pub bump: u8,
 }
 
 // ============================================================================
 // Enums
 // ============================================================================
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
 pub enum InvestorType {
     #[default]
     Retail,
@@ -475,7 +884,7 @@ pub struct Initialize<'info> {
     #[account(
         init,
         payer = authority,
-        space = ComplianceConfig::LEN,
+        space = 8 + ComplianceConfig::INIT_SPACE,
         seeds = [b"compliance-config"],
         bump
     )]
@@ -501,7 +910,7 @@ pub struct ManageWhitelist<'info> {
     #[account(
         init,
         payer = authority,
-        space = WhitelistEntry::LEN,
+        space = 8 + WhitelistEntry::INIT_SPACE,
         seeds = [b"whitelist", investor.as_ref()],
         bump
     )]
@@ -548,7 +957,7 @@ pub struct ManageBlacklist<'info> {
     #[account(
         init,
         payer = authority,
-        space = BlacklistEntry::LEN,
+        space = 8 + BlacklistEntry::INIT_SPACE,
         seeds = [b"blacklist", address.as_ref()],
         bump
     )]
@@ -594,7 +1003,7 @@ pub struct ManageJurisdiction<'info> {
     #[account(
         init,
         payer = authority,
-        space = JurisdictionRule::LEN,
+        space = 8 + JurisdictionRule::INIT_SPACE,
         seeds = [b"jurisdiction", from_jurisdiction.as_ref(), to_jurisdiction.as_ref()],
         bump
     )]
@@ -616,50 +1025,191 @@ pub struct UpdateConfig<'info> {
     pub config: Account<'info, ComplianceConfig>,
 }
 
+#[derive(Accounts)]
+pub struct QueueConfigUpdate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"compliance-config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, ComplianceConfig>,
+
+    /// One pending update at a time
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + QueuedConfigUpdate::INIT_SPACE,
+        seeds = [b"timelock", config.key().as_ref()],
+        bump
+    )]
+    pub queued_update: Account<'info, QueuedConfigUpdate>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyConfigUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"compliance-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ComplianceConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"timelock", config.key().as_ref()],
+        bump = queued_update.bump,
+        has_one = config,
+        has_one = queued_by,
+        close = queued_by
+    )]
+    pub queued_update: Account<'info, QueuedConfigUpdate>,
+
+    /// CHECK: Account that queued the update, refunded its rent
+    #[account(mut)]
+    pub queued_by: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigUpdate<'info> {
+    pub guardian: Signer<'info>,
+
+    /// Registry-wide guardian assignment
+    #[account(
+        constraint = guardian_role.authorizes(&guardian.key(), Role::Guardian, None) @ ComplianceError::Unauthorized
+    )]
+    pub guardian_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"compliance-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ComplianceConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"timelock", config.key().as_ref()],
+        bump = queued_update.bump,
+        has_one = config,
+        has_one = queued_by,
+        close = queued_by
+    )]
+    pub queued_update: Account<'info, QueuedConfigUpdate>,
+
+    /// CHECK: Account that queued the update, refunded its rent
+    #[account(mut)]
+    pub queued_by: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Validation account Token-2022 reads the hook's extra accounts from
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts follow the transfer hook interface's `Execute` layout: the four
+/// transfer accounts, the validation account, then `extra_account_metas`
 #[derive(Accounts)]
 pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Owner or delegate authorizing the transfer
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: Validation account listing the extra accounts below
+    #[account(
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
     #[account(
         seeds = [b"compliance-config"],
         bump = config.bump
     )]
     pub config: Account<'info, ComplianceConfig>,
 
-    /// CHECK: Sender account from transfer
-    pub sender: AccountInfo<'info>,
+    pub registry_program: Program<'info, AssetRegistry>,
 
-    /// CHECK: Receiver account from transfer
-    pub receiver: AccountInfo<'info>,
+    /// CHECK: Registry asset for the mint; uninitialized for unregistered mints
+    #[account(
+        seeds = [b"asset", mint.key().as_ref()],
+        bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub asset: UncheckedAccount<'info>,
 
-    /// Sender's whitelist entry (optional for some checks)
+    /// CHECK: Registry mint config for the mint; uninitialized for unregistered mints
     #[account(
-        seeds = [b"whitelist", sender.key().as_ref()],
-        bump = sender_whitelist.bump
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump,
+        seeds::program = asset_registry::ID
     )]
-    pub sender_whitelist: Option<Account<'info, WhitelistEntry>>,
+    pub mint_config: UncheckedAccount<'info>,
 
-    /// Receiver's whitelist entry (optional for some checks)
+    /// CHECK: Sender's registry lockup record; uninitialized if the sender has none
     #[account(
-        seeds = [b"whitelist", receiver.key().as_ref()],
-        bump = receiver_whitelist.bump
+        seeds = [b"lockup", mint.key().as_ref(), source_token_account.owner.as_ref()],
+        bump,
+        seeds::program = asset_registry::ID
     )]
-    pub receiver_whitelist: Option<Account<'info, WhitelistEntry>>,
+    pub sender_lockup: UncheckedAccount<'info>,
 
-    /// Sender's blacklist entry (optional)
+    /// CHECK: Sender's whitelist entry; uninitialized if never whitelisted
     #[account(
-        seeds = [b"blacklist", sender.key().as_ref()],
-        bump = sender_blacklist.bump
+        seeds = [b"whitelist", source_token_account.owner.as_ref()],
+        bump
     )]
-    pub sender_blacklist: Option<Account<'info, BlacklistEntry>>,
+    pub sender_whitelist: UncheckedAccount<'info>,
 
-    /// Receiver's blacklist entry (optional)
+    /// CHECK: Receiver's whitelist entry; uninitialized if never whitelisted
     #[account(
-        seeds = [b"blacklist", receiver.key().as_ref()],
-        bump = receiver_blacklist.bump
+        seeds = [b"whitelist", destination_token_account.owner.as_ref()],
+        bump
     )]
-    pub receiver_blacklist: Option<Account<'info, BlacklistEntry>>,
+    pub receiver_whitelist: UncheckedAccount<'info>,
 
-    /// Jurisdiction rule for this transfer (optional)
-    pub jurisdiction_rule: Option<Account<'info, JurisdictionRule>>,
+    /// CHECK: Sender's blacklist entry; uninitialized if never blacklisted
+    #[account(
+        seeds = [b"blacklist", source_token_account.owner.as_ref()],
+        bump
+    )]
+    pub sender_blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: Receiver's blacklist entry; uninitialized if never blacklisted
+    #[account(
+        seeds = [b"blacklist", destination_token_account.owner.as_ref()],
+        bump
+    )]
+    pub receiver_blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: Rule between the sender's and receiver's whitelisted jurisdictions,
+    /// checked in the handler; uninitialized if no rule was added
+    pub jurisdiction_rule: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -684,6 +1234,102 @@ pub struct VerifyCivicPass<'info> {
     pub gateway_token: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct ThawWhitelistedAccount<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"compliance-config"],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, ComplianceConfig>,
+
+    #[account(
+        seeds = [b"whitelist", whitelist_entry.investor.as_ref()],
+        bump = whitelist_entry.bump,
+        constraint = whitelist_entry.is_active @ ComplianceError::NotWhitelisted
+    )]
+    pub whitelist_entry: Account<'info, WhitelistEntry>,
+
+    /// CHECK: RWA mint, validated by the asset registry
+    pub mint: AccountInfo<'info>,
+
+    /// CHECK: Registry mint authority PDA, validated by the asset registry
+    pub mint_authority: AccountInfo<'info>,
+
+    /// CHECK: Investor's token account, validated by the asset registry
+    #[account(mut)]
+    pub holder_token_account: AccountInfo<'info>,
+
+    /// CHECK: Registry legal hold record, validated by the asset registry
+    pub holder_freeze: AccountInfo<'info>,
+
+    /// CHECK: Registry mint config, validated by the asset registry
+    pub mint_config: AccountInfo<'info>,
+
+    /// CHECK: Registry's latest split for the mint, validated by the asset registry
+    pub last_split: AccountInfo<'info>,
+
+    /// CHECK: Registry split conversion receipt, validated by the asset registry
+    pub split_conversion: AccountInfo<'info>,
+
+    pub registry_program: Program<'info, AssetRegistry>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateComplianceConfig<'info> {
+    /// The compliance config in any earlier layout
+    /// CHECK: Owner is checked here; `migrate_account` checks the discriminator
+    #[account(mut, owner = crate::ID)]
+    pub config: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateWhitelistEntry<'info> {
+    /// A whitelist entry in any earlier layout
+    /// CHECK: Owner is checked here; `migrate_account` checks the discriminator
+    #[account(mut, owner = crate::ID)]
+    pub whitelist_entry: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateBlacklistEntry<'info> {
+    /// A blacklist entry in any earlier layout
+    /// CHECK: Owner is checked here; `migrate_account` checks the discriminator
+    #[account(mut, owner = crate::ID)]
+    pub blacklist_entry: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateJurisdictionRule<'info> {
+    /// A jurisdiction rule in any earlier layout
+    /// CHECK: Owner is checked here; `migrate_account` checks the discriminator
+    #[account(mut, owner = crate::ID)]
+    pub jurisdiction_rule: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 // ============================================================================
 // Events
 // ============================================================================
@@ -735,6 +1381,28 @@ pub struct TransfersResumed {
     pub resumed_by: Pubkey,
 }
 
+#[event]
+pub struct ConfigUpdateQueued {
+    pub queued_update: Pubkey,
+    pub max_transfer_amount: Option<u64>,
+    pub transfer_cooldown: Option<i64>,
+    pub queued_by: Pubkey,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ConfigUpdateApplied {
+    pub queued_update: Pubkey,
+    pub max_transfer_amount: Option<u64>,
+    pub transfer_cooldown: Option<i64>,
+}
+
+#[event]
+pub struct ConfigUpdateCancelled {
+    pub queued_update: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
 #[event]
 pub struct TransferValidated {
     pub sender: Pubkey,
@@ -749,6 +1417,13 @@ pub struct CivicPassVerified {
     pub expiry: i64,
 }
 
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
 // ============================================================================
 // Errors
 // ============================================================================
@@ -781,4 +1456,20 @@ pub enum ComplianceError {
     ReasonTooLong,
     #[msg("Unauthorized")]
     Unauthorized,
+    #[msg("Asset is not active in the registry")]
+    AssetNotTradeable,
+    #[msg("Mint is frozen in the registry")]
+    MintFrozen,
+    #[msg("Investor is not whitelisted")]
+    NotWhitelisted,
+    #[msg("Transfer exceeds the sender's unlocked balance")]
+    TransferExceedsUnlocked,
+    #[msg("Account is already at the current layout version")]
+    AccountAlreadyMigrated,
+    #[msg("Config update changes nothing")]
+    EmptyConfigUpdate,
+    #[msg("Queued update cannot be applied before its ETA")]
+    TimelockNotElapsed,
+    #[msg("Jurisdiction rule does not match the holders' jurisdictions")]
+    InvalidJurisdictionRule,
 }
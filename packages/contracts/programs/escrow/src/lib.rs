@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::{self, Token2022, TransferChecked};
+use anchor_spl::token_interface::{Mint, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
+use asset_registry::{calculate_fee, load_asset_status, Config, FeeCollected, FeeOverride, FeeSource};
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnT");
 
@@ -25,6 +27,7 @@ pub mod escrow {
             expires_at > Clock::get()?.unix_timestamp,
             EscrowError::InvalidExpiration
         );
+        require_tradeable(&ctx.accounts.asset)?;
 
         let escrow = &mut ctx.accounts.escrow;
         escrow.buyer = ctx.accounts.buyer.key();
@@ -130,6 +133,7 @@ pub mod escrow {
     pub fn release(ctx: Context<Release>) -> Result<()> {
         let escrow = &ctx.accounts.escrow;
         require!(escrow.status == EscrowStatus::FullyFunded, EscrowError::InvalidStatus);
+        require_tradeable(&ctx.accounts.asset)?;
 
         let escrow_seeds = &[
             b"escrow",
@@ -157,6 +161,13 @@ pub mod escrow {
             ctx.accounts.asset_mint.decimals,
         )?;
 
+        // Platform fee is deducted from the seller's proceeds
+        let fee_bps = ctx
+            .accounts
+            .config
+            .fee_bps_for(ctx.accounts.fee_override.as_deref());
+        let fee = calculate_fee(escrow.payment_amount, fee_bps).ok_or(EscrowError::MathOverflow)?;
+
         // Transfer payment tokens to seller
         let transfer_payment_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
@@ -171,10 +182,39 @@ pub mod escrow {
 
         token_2022::transfer_checked(
             transfer_payment_ctx,
-            escrow.payment_amount,
+            escrow.payment_amount - fee,
             ctx.accounts.payment_mint.decimals,
         )?;
 
+        // Transfer platform fee to the registry fee treasury
+        if fee > 0 {
+            let transfer_fee_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_payment_vault.to_account_info(),
+                    mint: ctx.accounts.payment_mint.to_account_info(),
+                    to: ctx.accounts.treasury_payment_account.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                },
+                signer_seeds,
+            );
+
+            token_2022::transfer_checked(
+                transfer_fee_ctx,
+                fee,
+                ctx.accounts.payment_mint.decimals,
+            )?;
+
+            emit!(FeeCollected {
+                source: FeeSource::EscrowRelease,
+                asset_mint: escrow.asset_mint,
+                fee_mint: escrow.payment_mint,
+                amount: fee,
+                fee_bps,
+                treasury_account: ctx.accounts.treasury_payment_account.key(),
+            });
+        }
+
         emit!(EscrowReleased {
             escrow: escrow.key(),
             released_at: Clock::get()?.unix_timestamp,
@@ -248,6 +288,18 @@ pub mod escrow {
     }
 }
 
+// ===========================================
+// HELPERS
+// ===========================================
+
+/// Refuse registered assets that are defaulted or in liquidation
+fn require_tradeable(asset: &UncheckedAccount) -> Result<()> {
+    if let Some(status) = load_asset_status(asset)? {
+        require!(!status.is_distressed(), EscrowError::AssetNotTradeable);
+    }
+    Ok(())
+}
+
 // ===========================================
 // ACCOUNTS
 // ===========================================
@@ -272,6 +324,14 @@ pub struct CreateEscrow<'info> {
     /// CHECK: Asset token mint
     pub asset_mint: AccountInfo<'info>,
 
+    /// CHECK: Registry asset PDA for the mint; uninitialized if the mint is not a registered asset
+    #[account(
+        seeds = [b"asset", asset_mint.key().as_ref()],
+        bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub asset: UncheckedAccount<'info>,
+
     /// CHECK: Payment token mint (USDC)
     pub payment_mint: AccountInfo<'info>,
 
@@ -299,8 +359,7 @@ pub struct DepositPayment<'info> {
     #[account(mut)]
     pub escrow_payment_vault: AccountInfo<'info>,
 
-    /// CHECK: Payment mint
-    pub payment_mint: AccountInfo<'info>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
 
     pub token_program: Program<'info, Token2022>,
 }
@@ -326,8 +385,7 @@ pub struct DepositAsset<'info> {
     #[account(mut)]
     pub escrow_asset_vault: AccountInfo<'info>,
 
-    /// CHECK: Asset mint
-    pub asset_mint: AccountInfo<'info>,
+    pub asset_mint: InterfaceAccount<'info, Mint>,
 
     pub token_program: Program<'info, Token2022>,
 }
@@ -366,11 +424,46 @@ pub struct Release<'info> {
     #[account(mut)]
     pub seller_payment_account: AccountInfo<'info>,
 
-    /// CHECK: Asset mint
-    pub asset_mint: AccountInfo<'info>,
+    #[account(address = escrow.asset_mint)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
 
-    /// CHECK: Payment mint
-    pub payment_mint: AccountInfo<'info>,
+    #[account(address = escrow.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Registry asset PDA for the mint; uninitialized if the mint is not a registered asset
+    #[account(
+        seeds = [b"asset", asset_mint.key().as_ref()],
+        bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Per-asset fee override, if one is set
+    #[account(
+        seeds = [b"fee-override", asset_mint.key().as_ref()],
+        bump = fee_override.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+
+    /// CHECK: Registry PDA owning the platform fee token accounts
+    #[account(
+        seeds = [b"fee-treasury"],
+        bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub fee_treasury: AccountInfo<'info>,
+
+    /// Fee treasury token account for the payment mint
+    #[account(mut, token::mint = payment_mint, token::authority = fee_treasury)]
+    pub treasury_payment_account: InterfaceAccount<'info, TokenAccount>,
 
     pub token_program: Program<'info, Token2022>,
 }
@@ -409,11 +502,9 @@ pub struct Refund<'info> {
     #[account(mut)]
     pub seller_asset_account: AccountInfo<'info>,
 
-    /// CHECK: Asset mint
-    pub asset_mint: AccountInfo<'info>,
+    pub asset_mint: InterfaceAccount<'info, Mint>,
 
-    /// CHECK: Payment mint
-    pub payment_mint: AccountInfo<'info>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
 
     /// Signer must be buyer, seller, or platform admin
     pub authority: Signer<'info>,
@@ -514,4 +605,8 @@ pub enum EscrowError {
     EscrowExpired,
     #[msg("Unauthorized")]
     Unauthorized,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Asset is defaulted or in liquidation")]
+    AssetNotTradeable,
 }
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnW");
+
+/// Mock Price Feed Program
+///
+/// Writes price accounts in the Pyth v2 price account layout so oracle-fed
+/// valuations can be exercised on localnet. Not for deployment.
+#[program]
+pub mod mock_price_feed {
+    use super::*;
+
+    /// Overwrite the aggregate price of a feed account owned by this program
+    pub fn set_price(
+        ctx: Context<SetPrice>,
+        price: i64,
+        expo: i32,
+        conf: u64,
+        publish_time: i64,
+    ) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let mut data = ctx.accounts.price_feed.try_borrow_mut_data()?;
+        require!(data.len() >= PRICE_ACCOUNT_LEN, MockPriceFeedError::AccountTooSmall);
+
+        data[0..4].copy_from_slice(&MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&VERSION.to_le_bytes());
+        data[8..12].copy_from_slice(&ACCOUNT_TYPE_PRICE.to_le_bytes());
+        data[12..16].copy_from_slice(&(PRICE_ACCOUNT_LEN as u32).to_le_bytes());
+        data[20..24].copy_from_slice(&expo.to_le_bytes());
+        data[32..40].copy_from_slice(&slot.to_le_bytes());
+        data[96..104].copy_from_slice(&publish_time.to_le_bytes());
+        data[208..216].copy_from_slice(&price.to_le_bytes());
+        data[216..224].copy_from_slice(&conf.to_le_bytes());
+        data[224..228].copy_from_slice(&STATUS_TRADING.to_le_bytes());
+        data[232..240].copy_from_slice(&slot.to_le_bytes());
+
+        msg!("Price set: {} x 10^{}", price, expo);
+        Ok(())
+    }
+}
+
+const MAGIC: u32 = 0xa1b2c3d4;
+const VERSION: u32 = 2;
+const ACCOUNT_TYPE_PRICE: u32 = 3;
+const STATUS_TRADING: u32 = 1;
+
+/// Bytes up to and including the aggregate price's publish slot
+pub const PRICE_ACCOUNT_LEN: usize = 240;
+
+#[derive(Accounts)]
+pub struct SetPrice<'info> {
+    /// CHECK: Raw price account, created by the caller with this program as owner
+    #[account(mut, owner = crate::ID)]
+    pub price_feed: AccountInfo<'info>,
+}
+
+#[error_code]
+pub enum MockPriceFeedError {
+    #[msg("Price account is smaller than the Pyth price layout")]
+    AccountTooSmall,
+}
@@ -0,0 +1,1007 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use asset_registry::program::AssetRegistry;
+use asset_registry::{
+    calculate_fee, compliance_program, is_whitelisted, Asset, AssetStatus,
+    Config, FeeOverride, MintConfig, Reserve, Role, RoleAssignment, BPS_DENOMINATOR,
+};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnX");
+
+/// Offering Program
+///
+/// Raises capital for registered assets through a primary subscription window.
+/// Whitelisted investors commit payment tokens into a vault; at close each
+/// subscription is settled by minting asset tokens through the registry, or
+/// refunded in full if the soft cap was missed, the offering was cancelled or
+/// the registry can never issue the allocation. Payments may be made in SPL
+/// Token or Token-2022 mints without transfer fees; the asset mint is always
+/// Token-2022.
+#[program]
+pub mod offering {
+    use super::*;
+
+    /// Open a subscription window for an asset (asset authority only)
+    ///
+    /// The offering PDA must already hold the asset-scoped Minter role so that
+    /// every successful subscription can be settled.
+    #[allow(clippy::too_many_arguments)]
+    pub fn open_offering(
+        ctx: Context<OpenOffering>,
+        round: u16,
+        price_per_token: u64,
+        soft_cap: u64,
+        hard_cap: u64,
+        min_ticket: u64,
+        max_ticket: u64,
+        start_time: i64,
+        end_time: i64,
+        allocation_method: AllocationMethod,
+    ) -> Result<()> {
+        require!(price_per_token > 0, OfferingError::InvalidPrice);
+        require!(soft_cap > 0 && soft_cap <= hard_cap, OfferingError::InvalidCaps);
+        require!(
+            min_ticket > 0 && min_ticket <= max_ticket && min_ticket <= hard_cap,
+            OfferingError::InvalidTicketSize
+        );
+
+        let clock = Clock::get()?;
+        require!(end_time > start_time, OfferingError::InvalidEndTime);
+        require!(end_time > clock.unix_timestamp, OfferingError::InvalidEndTime);
+
+        let asset = &ctx.accounts.asset;
+        require!(asset.status == AssetStatus::Active, OfferingError::AssetNotActive);
+        // The offering PDA mints through `mint_tokens`, which approver sets close
+        require!(
+            asset.approver_set == Pubkey::default(),
+            OfferingError::AssetRequiresApproval
+        );
+        // The vault must receive exactly what each subscription records
+        require!(
+            !has_transfer_fee(&ctx.accounts.payment_mint.to_account_info())?,
+            OfferingError::UnsupportedPaymentMint
+        );
+
+        // A fully subscribed offering, platform fee included, must fit within the
+        // registered supply
+        let decimals = ctx.accounts.asset_mint.decimals;
+        let fee_bps = ctx
+            .accounts
+            .config
+            .fee_bps_for(ctx.accounts.fee_override.as_deref());
+        let max_tokens = gross_up(tokens_for(hard_cap, price_per_token, decimals)?, fee_bps)
+            .ok_or(OfferingError::ExceedsAvailableSupply)?;
+        let available = asset
+            .total_supply
+            .saturating_sub(ctx.accounts.mint_config.outstanding_supply());
+        require!(max_tokens <= available, OfferingError::ExceedsAvailableSupply);
+
+        let offering = &mut ctx.accounts.offering;
+        offering.issuer = ctx.accounts.issuer.key();
+        offering.asset_mint = ctx.accounts.asset_mint.key();
+        offering.payment_mint = ctx.accounts.payment_mint.key();
+        offering.round = round;
+        offering.price_per_token = price_per_token;
+        offering.soft_cap = soft_cap;
+        offering.hard_cap = hard_cap;
+        offering.min_ticket = min_ticket;
+        offering.max_ticket = max_ticket;
+        offering.start_time = start_time;
+        offering.end_time = end_time;
+        offering.allocation_method = allocation_method;
+        offering.status = OfferingStatus::Open;
+        offering.total_committed = 0;
+        offering.total_subscribers = 0;
+        offering.tokens_issued = 0;
+        offering.proceeds = 0;
+        offering.proceeds_withdrawn = 0;
+        offering.created_at = clock.unix_timestamp;
+        offering.closed_at = 0;
+        offering.bump = ctx.bumps.offering;
+
+        emit!(OfferingOpened {
+            offering: offering.key(),
+            issuer: offering.issuer,
+            asset_mint: offering.asset_mint,
+            payment_mint: offering.payment_mint,
+            round,
+            price_per_token,
+            soft_cap,
+            hard_cap,
+            start_time,
+            end_time,
+            allocation_method,
+        });
+
+        msg!("Offering round {} opened for {}", round, offering.asset_mint);
+        Ok(())
+    }
+
+    /// Commit payment tokens to an open offering (whitelisted investors only)
+    ///
+    /// Repeat calls top up the same subscription, subject to the ticket limits.
+    pub fn subscribe(ctx: Context<Subscribe>, amount: u64) -> Result<()> {
+        require!(amount > 0, OfferingError::InvalidAmount);
+
+        let offering = &mut ctx.accounts.offering;
+        let clock = Clock::get()?;
+
+        require!(offering.status == OfferingStatus::Open, OfferingError::OfferingNotOpen);
+        require!(clock.unix_timestamp >= offering.start_time, OfferingError::OfferingNotStarted);
+        require!(clock.unix_timestamp < offering.end_time, OfferingError::OfferingEnded);
+        require!(
+            is_whitelisted(&ctx.accounts.whitelist_entry, clock.unix_timestamp)?,
+            OfferingError::NotWhitelisted
+        );
+
+        let subscription = &mut ctx.accounts.subscription;
+        let committed = subscription
+            .committed
+            .checked_add(amount)
+            .ok_or(OfferingError::MathOverflow)?;
+        require!(
+            committed >= offering.min_ticket && committed <= offering.max_ticket,
+            OfferingError::InvalidTicketSize
+        );
+
+        let total_committed = offering
+            .total_committed
+            .checked_add(amount)
+            .ok_or(OfferingError::MathOverflow)?;
+        // Pro-rata offerings accept oversubscription and scale back at settlement
+        if offering.allocation_method == AllocationMethod::FirstCome {
+            require!(total_committed <= offering.hard_cap, OfferingError::HardCapReached);
+        }
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.investor_payment_account.to_account_info(),
+                    mint: ctx.accounts.payment_mint.to_account_info(),
+                    to: ctx.accounts.offering_payment_vault.to_account_info(),
+                    authority: ctx.accounts.investor.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.payment_mint.decimals,
+        )?;
+
+        if subscription.committed == 0 {
+            subscription.offering = offering.key();
+            subscription.investor = ctx.accounts.investor.key();
+            subscription.subscribed_at = clock.unix_timestamp;
+            subscription.bump = ctx.bumps.subscription;
+            offering.total_subscribers += 1;
+        }
+        subscription.committed = committed;
+        offering.total_committed = total_committed;
+
+        emit!(SubscriptionPlaced {
+            offering: offering.key(),
+            investor: subscription.investor,
+            amount,
+            committed,
+            total_committed,
+        });
+
+        msg!("Subscription of {} by {}", amount, subscription.investor);
+        Ok(())
+    }
+
+    /// Close the subscription window (permissionless)
+    ///
+    /// Allowed once the window has ended, or early when a first-come offering
+    /// has reached its hard cap. The outcome is fixed against the soft cap.
+    pub fn close_offering(ctx: Context<CloseOffering>) -> Result<()> {
+        let offering = &mut ctx.accounts.offering;
+        let clock = Clock::get()?;
+
+        require!(offering.status == OfferingStatus::Open, OfferingError::OfferingNotOpen);
+        let filled = offering.allocation_method == AllocationMethod::FirstCome
+            && offering.total_committed >= offering.hard_cap;
+        require!(
+            clock.unix_timestamp >= offering.end_time || filled,
+            OfferingError::OfferingNotEnded
+        );
+
+        offering.status = if offering.total_committed >= offering.soft_cap {
+            OfferingStatus::Succeeded
+        } else {
+            OfferingStatus::Failed
+        };
+        offering.closed_at = clock.unix_timestamp;
+
+        emit!(OfferingClosed {
+            offering: offering.key(),
+            status: offering.status,
+            total_committed: offering.total_committed,
+            total_subscribers: offering.total_subscribers,
+            closed_at: offering.closed_at,
+        });
+
+        msg!("Offering closed with {} committed", offering.total_committed);
+        Ok(())
+    }
+
+    /// Cancel an offering before it closes (issuer only)
+    ///
+    /// Every subscription is then refunded in full by `settle_subscription`.
+    pub fn cancel_offering(ctx: Context<CancelOffering>) -> Result<()> {
+        let offering = &mut ctx.accounts.offering;
+        require!(offering.status == OfferingStatus::Open, OfferingError::OfferingNotOpen);
+
+        offering.status = OfferingStatus::Cancelled;
+        offering.closed_at = Clock::get()?.unix_timestamp;
+
+        emit!(OfferingCancelled {
+            offering: offering.key(),
+            total_committed: offering.total_committed,
+            cancelled_at: offering.closed_at,
+        });
+
+        msg!("Offering cancelled");
+        Ok(())
+    }
+
+    /// Settle one subscription after close (permissionless)
+    ///
+    /// Successful offerings mint the investor's allocation through the registry
+    /// and refund any unallocated remainder. The registry's platform fee is
+    /// minted on top of the allocation, out of the issuer's supply, so the
+    /// investor receives every token paid for. Failed or cancelled offerings,
+    /// and allocations the registry can never issue, refund the full commitment.
+    /// Settlement reverts while issuance is only paused (a frozen mint or
+    /// investor account, a stale reserve attestation) so it can be retried.
+    /// The subscription account is closed.
+    pub fn settle_subscription(ctx: Context<SettleSubscription>) -> Result<()> {
+        let offering = &ctx.accounts.offering;
+        let subscription = &ctx.accounts.subscription;
+
+        require!(
+            matches!(
+                offering.status,
+                OfferingStatus::Succeeded | OfferingStatus::Failed | OfferingStatus::Cancelled
+            ),
+            OfferingError::OfferingNotClosed
+        );
+
+        let (tokens, issued, cost) = if offering.status == OfferingStatus::Succeeded {
+            let decimals = ctx.accounts.asset_mint.decimals;
+            let allocation = offering.allocation_of(subscription.committed)?;
+            let tokens = tokens_for(allocation, offering.price_per_token, decimals)?;
+            let fee_bps = ctx
+                .accounts
+                .config
+                .fee_bps_for(ctx.accounts.fee_override.as_deref());
+            let issued = gross_up(tokens, fee_bps).ok_or(OfferingError::FeeConsumesAllocation)?;
+            if tokens > 0 && issuance_closed(ctx.accounts, issued) {
+                msg!("Registry can never issue {} tokens; refunding in full", tokens);
+                (0, 0, 0)
+            } else {
+                (tokens, issued, cost_of(tokens, offering.price_per_token, decimals)?)
+            }
+        } else {
+            (0, 0, 0)
+        };
+        let refund = subscription.committed - cost;
+
+        let round = offering.round.to_le_bytes();
+        let seeds = &[
+            b"offering",
+            offering.asset_mint.as_ref(),
+            round.as_ref(),
+            &[offering.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        if issued > 0 {
+            asset_registry::cpi::mint_tokens(
+                CpiContext::new_with_signer(
+                    ctx.accounts.asset_registry_program.to_account_info(),
+                    asset_registry::cpi::accounts::MintTokens {
+                        authority: ctx.accounts.offering.to_account_info(),
+                        role: ctx.accounts.minter_role.to_account_info(),
+                        mint_config: ctx.accounts.mint_config.to_account_info(),
+                        asset: ctx.accounts.asset.to_account_info(),
+                        mint: ctx.accounts.asset_mint.to_account_info(),
+                        mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                        recipient: ctx.accounts.investor.to_account_info(),
+                        recipient_token_account: ctx.accounts.investor_asset_account.to_account_info(),
+                        config: ctx.accounts.config.to_account_info(),
+                        fee_override: ctx
+                            .accounts
+                            .fee_override
+                            .as_ref()
+                            .map(|fee_override| fee_override.to_account_info()),
+                        reserve: ctx
+                            .accounts
+                            .reserve
+                            .as_ref()
+                            .map(|reserve| reserve.to_account_info()),
+                        fee_treasury: ctx.accounts.fee_treasury.to_account_info(),
+                        treasury_token_account: ctx.accounts.treasury_token_account.to_account_info(),
+                        lockup: None,
+                        token_program: ctx.accounts.token_program.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                issued,
+                None,
+            )?;
+        }
+
+        if refund > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.payment_token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.offering_payment_vault.to_account_info(),
+                        mint: ctx.accounts.payment_mint.to_account_info(),
+                        to: ctx.accounts.investor_payment_account.to_account_info(),
+                        authority: ctx.accounts.offering.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                refund,
+                ctx.accounts.payment_mint.decimals,
+            )?;
+        }
+
+        let offering = &mut ctx.accounts.offering;
+        offering.tokens_issued = offering
+            .tokens_issued
+            .checked_add(issued)
+            .ok_or(OfferingError::MathOverflow)?;
+        offering.proceeds = offering
+            .proceeds
+            .checked_add(cost)
+            .ok_or(OfferingError::MathOverflow)?;
+
+        emit!(SubscriptionSettled {
+            offering: offering.key(),
+            investor: subscription.investor,
+            committed: subscription.committed,
+            tokens,
+            fee: issued - tokens,
+            cost,
+            refund,
+        });
+
+        msg!("Subscription settled: {} tokens, {} refunded", tokens, refund);
+        Ok(())
+    }
+
+    /// Withdraw settled proceeds of a successful offering (issuer only)
+    pub fn withdraw_proceeds(ctx: Context<WithdrawProceeds>) -> Result<()> {
+        let offering = &ctx.accounts.offering;
+        require!(
+            offering.status == OfferingStatus::Succeeded,
+            OfferingError::OfferingNotSucceeded
+        );
+
+        let amount = offering.proceeds - offering.proceeds_withdrawn;
+        require!(amount > 0, OfferingError::NothingToWithdraw);
+
+        let round = offering.round.to_le_bytes();
+        let seeds = &[
+            b"offering",
+            offering.asset_mint.as_ref(),
+            round.as_ref(),
+            &[offering.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.offering_payment_vault.to_account_info(),
+                    mint: ctx.accounts.payment_mint.to_account_info(),
+                    to: ctx.accounts.issuer_payment_account.to_account_info(),
+                    authority: ctx.accounts.offering.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.payment_mint.decimals,
+        )?;
+
+        let offering = &mut ctx.accounts.offering;
+        offering.proceeds_withdrawn = offering.proceeds;
+
+        emit!(ProceedsWithdrawn {
+            offering: offering.key(),
+            issuer: offering.issuer,
+            amount,
+        });
+
+        msg!("Withdrew {} in offering proceeds", amount);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Whole asset-token base units bought by `payment` at `price_per_token`,
+/// where the price is quoted per whole token of `decimals` decimals
+fn tokens_for(payment: u64, price_per_token: u64, decimals: u8) -> Result<u64> {
+    let tokens = (payment as u128)
+        .checked_mul(10u128.pow(decimals as u32))
+        .ok_or(OfferingError::MathOverflow)?
+        / price_per_token as u128;
+    u64::try_from(tokens).map_err(|_| error!(OfferingError::MathOverflow))
+}
+
+/// Tokens the registry must issue for `tokens` to reach the investor after it
+/// takes `fee_bps` in kind; `None` when the fee would consume every token
+fn gross_up(tokens: u64, fee_bps: u16) -> Option<u64> {
+    let retained = BPS_DENOMINATOR.checked_sub(fee_bps).filter(|&retained| retained > 0)?;
+    let issued = (tokens as u128 * BPS_DENOMINATOR as u128).div_ceil(retained as u128);
+    let issued = u64::try_from(issued).ok()?;
+    // Rounding the fee down can leave one token too many with the investor
+    let net = issued - calculate_fee(issued, fee_bps)?;
+    Some(if net > tokens { issued - 1 } else { issued })
+}
+
+/// Whether the registry can never mint `amount` more tokens through
+/// `mint_tokens`: the asset has been wound down, is governed by an approver
+/// set, or has no registered supply left. Temporary conditions are left to the
+/// registry, whose refusal reverts settlement so it can be retried. Both
+/// accounts read are pinned to their PDAs, so a settler cannot force a refund.
+fn issuance_closed(accounts: &SettleSubscription, amount: u64) -> bool {
+    let asset = &accounts.asset;
+    asset.status.is_wound_down()
+        || asset.approver_set != Pubkey::default()
+        || !matches!(
+            accounts.mint_config.outstanding_supply().checked_add(amount),
+            Some(outstanding) if outstanding <= asset.total_supply
+        )
+}
+
+/// Whether `mint_info` is a Token-2022 mint charging transfer fees
+fn has_transfer_fee(mint_info: &AccountInfo) -> Result<bool> {
+    if *mint_info.owner != token_2022::ID {
+        return Ok(false);
+    }
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+    Ok(mint.get_extension_types()?.contains(&ExtensionType::TransferFeeConfig))
+}
+
+/// Payment owed for `tokens` base units, rounded up in the issuer's favour
+fn cost_of(tokens: u64, price_per_token: u64, decimals: u8) -> Result<u64> {
+    let unit = 10u128.pow(decimals as u32);
+    let cost = (tokens as u128)
+        .checked_mul(price_per_token as u128)
+        .ok_or(OfferingError::MathOverflow)?
+        .div_ceil(unit);
+    u64::try_from(cost).map_err(|_| error!(OfferingError::MathOverflow))
+}
+
+// ============================================================================
+// Account Structures
+// ============================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct Offering {
+    /// Asset authority that opened the offering
+    pub issuer: Pubkey,
+    /// Asset token mint
+    pub asset_mint: Pubkey,
+    /// Payment token mint (e.g., USDC)
+    pub payment_mint: Pubkey,
+    /// Issuer-chosen round number, unique per asset
+    pub round: u16,
+    /// Payment base units per whole asset token
+    pub price_per_token: u64,
+    /// Minimum commitments for the offering to succeed
+    pub soft_cap: u64,
+    /// Maximum payment accepted
+    pub hard_cap: u64,
+    /// Minimum commitment per investor
+    pub min_ticket: u64,
+    /// Maximum commitment per investor
+    pub max_ticket: u64,
+    /// Subscription window start
+    pub start_time: i64,
+    /// Subscription window end
+    pub end_time: i64,
+    /// How an oversubscribed offering is allocated
+    pub allocation_method: AllocationMethod,
+    /// Offering status
+    pub status: OfferingStatus,
+    /// Payment committed across all subscriptions
+    pub total_committed: u64,
+    /// Number of subscriptions
+    pub total_subscribers: u32,
+    /// Asset tokens minted at settlement, platform fees included
+    pub tokens_issued: u64,
+    /// Payment retained at settlement, owed to the issuer
+    pub proceeds: u64,
+    /// Proceeds already withdrawn by the issuer
+    pub proceeds_withdrawn: u64,
+    /// Creation timestamp
+    pub created_at: i64,
+    /// Close or cancellation timestamp
+    pub closed_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Offering {
+    /// Payment accepted from a commitment once the offering has succeeded.
+    /// Pro-rata offerings scale every commitment back to the hard cap.
+    pub fn allocation_of(&self, committed: u64) -> Result<u64> {
+        if self.allocation_method == AllocationMethod::ProRata
+            && self.total_committed > self.hard_cap
+        {
+            let allocation = (committed as u128)
+                .checked_mul(self.hard_cap as u128)
+                .ok_or(OfferingError::MathOverflow)?
+                / self.total_committed as u128;
+            return Ok(allocation as u64);
+        }
+        Ok(committed)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    /// Offering subscribed to
+    pub offering: Pubkey,
+    /// Subscribing investor
+    pub investor: Pubkey,
+    /// Payment committed
+    pub committed: u64,
+    /// First subscription timestamp
+    pub subscribed_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+// ============================================================================
+// Enums
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AllocationMethod {
+    /// Commitments are accepted in order until the hard cap is reached
+    FirstCome,
+    /// Oversubscription is accepted and scaled back proportionally
+    ProRata,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum OfferingStatus {
+    Open,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+// ============================================================================
+// Context Structs
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(round: u16)]
+pub struct OpenOffering<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = issuer,
+        space = 8 + Offering::INIT_SPACE,
+        seeds = [b"offering", asset_mint.key().as_ref(), &round.to_le_bytes()],
+        bump
+    )]
+    pub offering: Account<'info, Offering>,
+
+    #[account(
+        seeds = [b"asset", asset_mint.key().as_ref()],
+        bump = asset.bump,
+        seeds::program = asset_registry::ID,
+        constraint = asset.authority == issuer.key() @ OfferingError::Unauthorized
+    )]
+    pub asset: Account<'info, Asset>,
+
+    #[account(
+        seeds = [b"mint-config", asset_mint.key().as_ref()],
+        bump = mint_config.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Per-asset fee override, if one is set
+    #[account(
+        seeds = [b"fee-override", asset_mint.key().as_ref()],
+        bump = fee_override.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+
+    /// Minter role the issuer has granted to the offering PDA
+    #[account(
+        constraint = minter_role.authorizes(&offering.key(), Role::Minter, Some(&asset_mint.key())) @ OfferingError::MissingMinterRole
+    )]
+    pub minter_role: Account<'info, RoleAssignment>,
+
+    #[account(mint::token_program = token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mint::token_program = payment_token_program)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = issuer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = offering,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub offering_payment_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub payment_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"offering", offering.asset_mint.as_ref(), &offering.round.to_le_bytes()],
+        bump = offering.bump
+    )]
+    pub offering: Account<'info, Offering>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", offering.key().as_ref(), investor.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// CHECK: Compliance whitelist entry for the investor, parsed by the registry
+    #[account(
+        seeds = [b"whitelist", investor.key().as_ref()],
+        bump,
+        seeds::program = compliance_program::ID
+    )]
+    pub whitelist_entry: UncheckedAccount<'info>,
+
+    #[account(address = offering.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = investor,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub investor_payment_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = offering,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub offering_payment_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub payment_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseOffering<'info> {
+    pub closer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"offering", offering.asset_mint.as_ref(), &offering.round.to_le_bytes()],
+        bump = offering.bump
+    )]
+    pub offering: Account<'info, Offering>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOffering<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"offering", offering.asset_mint.as_ref(), &offering.round.to_le_bytes()],
+        bump = offering.bump,
+        has_one = issuer
+    )]
+    pub offering: Account<'info, Offering>,
+}
+
+#[derive(Accounts)]
+pub struct SettleSubscription<'info> {
+    #[account(mut)]
+    pub settler: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"offering", offering.asset_mint.as_ref(), &offering.round.to_le_bytes()],
+        bump = offering.bump
+    )]
+    pub offering: Account<'info, Offering>,
+
+    #[account(
+        mut,
+        close = investor,
+        seeds = [b"subscription", offering.key().as_ref(), investor.key().as_ref()],
+        bump = subscription.bump,
+        has_one = offering,
+        has_one = investor
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// CHECK: Subscribing investor; receives tokens, refunds and the subscription rent
+    #[account(mut)]
+    pub investor: UncheckedAccount<'info>,
+
+    #[account(address = offering.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = offering,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub offering_payment_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = investor,
+        token::token_program = payment_token_program,
+    )]
+    pub investor_payment_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = offering.asset_mint)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+
+    /// Investor's associated account for the asset mint
+    #[account(
+        mut,
+        associated_token::mint = asset_mint,
+        associated_token::authority = investor,
+        associated_token::token_program = token_program,
+    )]
+    pub investor_asset_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Registry Minter role held by the offering PDA, checked by the registry
+    pub minter_role: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"mint-config", asset_mint.key().as_ref()],
+        bump = mint_config.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    #[account(
+        seeds = [b"asset", asset_mint.key().as_ref()],
+        bump = asset.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub asset: Account<'info, Asset>,
+
+    /// CHECK: Registry mint authority PDA, checked by the registry
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Per-asset fee override, if one is set
+    #[account(
+        seeds = [b"fee-override", asset_mint.key().as_ref()],
+        bump = fee_override.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+
+    /// Custodian reserve record, if the asset is reserve-backed
+    #[account(
+        seeds = [b"reserve", asset_mint.key().as_ref()],
+        bump = reserve.bump,
+        seeds::program = asset_registry::ID
+    )]
+    pub reserve: Option<Account<'info, Reserve>>,
+
+    /// CHECK: Registry PDA owning the platform fee token accounts
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Fee treasury token account for the asset mint, checked by the registry
+    #[account(mut)]
+    pub treasury_token_account: UncheckedAccount<'info>,
+
+    pub asset_registry_program: Program<'info, AssetRegistry>,
+    pub token_program: Program<'info, Token2022>,
+    pub payment_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProceeds<'info> {
+    pub issuer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"offering", offering.asset_mint.as_ref(), &offering.round.to_le_bytes()],
+        bump = offering.bump,
+        has_one = issuer
+    )]
+    pub offering: Account<'info, Offering>,
+
+    #[account(address = offering.payment_mint)]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = offering,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub offering_payment_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = issuer,
+        token::token_program = payment_token_program,
+    )]
+    pub issuer_payment_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub payment_token_program: Interface<'info, TokenInterface>,
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[event]
+pub struct OfferingOpened {
+    pub offering: Pubkey,
+    pub issuer: Pubkey,
+    pub asset_mint: Pubkey,
+    pub payment_mint: Pubkey,
+    pub round: u16,
+    pub price_per_token: u64,
+    pub soft_cap: u64,
+    pub hard_cap: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub allocation_method: AllocationMethod,
+}
+
+#[event]
+pub struct SubscriptionPlaced {
+    pub offering: Pubkey,
+    pub investor: Pubkey,
+    pub amount: u64,
+    pub committed: u64,
+    pub total_committed: u64,
+}
+
+#[event]
+pub struct OfferingClosed {
+    pub offering: Pubkey,
+    pub status: OfferingStatus,
+    pub total_committed: u64,
+    pub total_subscribers: u32,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct OfferingCancelled {
+    pub offering: Pubkey,
+    pub total_committed: u64,
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct SubscriptionSettled {
+    pub offering: Pubkey,
+    pub investor: Pubkey,
+    pub committed: u64,
+    pub tokens: u64,
+    pub fee: u64,
+    pub cost: u64,
+    pub refund: u64,
+}
+
+#[event]
+pub struct ProceedsWithdrawn {
+    pub offering: Pubkey,
+    pub issuer: Pubkey,
+    pub amount: u64,
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[error_code]
+pub enum OfferingError {
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Invalid price")]
+    InvalidPrice,
+    #[msg("Soft cap must be positive and no greater than the hard cap")]
+    InvalidCaps,
+    #[msg("Commitment is outside the offering's ticket limits")]
+    InvalidTicketSize,
+    #[msg("Invalid end time")]
+    InvalidEndTime,
+    #[msg("Asset is not active")]
+    AssetNotActive,
+    #[msg("Hard cap exceeds the asset's remaining supply")]
+    ExceedsAvailableSupply,
+    #[msg("Offering PDA has not been granted the Minter role")]
+    MissingMinterRole,
+    #[msg("Offering is not open")]
+    OfferingNotOpen,
+    #[msg("Offering has not started")]
+    OfferingNotStarted,
+    #[msg("Offering has ended")]
+    OfferingEnded,
+    #[msg("Offering has not ended")]
+    OfferingNotEnded,
+    #[msg("Offering has not closed")]
+    OfferingNotClosed,
+    #[msg("Offering did not succeed")]
+    OfferingNotSucceeded,
+    #[msg("Offering hard cap reached")]
+    HardCapReached,
+    #[msg("Investor is not whitelisted")]
+    NotWhitelisted,
+    #[msg("No proceeds to withdraw")]
+    NothingToWithdraw,
+    #[msg("Assets governed by an approver set cannot be offered")]
+    AssetRequiresApproval,
+    #[msg("Payment mints with transfer fees are not supported")]
+    UnsupportedPaymentMint,
+    #[msg("Platform fee would consume the whole allocation")]
+    FeeConsumesAllocation,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
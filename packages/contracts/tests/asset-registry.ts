@@ -1,20 +1,34 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { AssetRegistry } from "../target/types/asset_registry";
+import { MockPriceFeed } from "../target/types/mock_price_feed";
 import { 
   Keypair, 
   PublicKey, 
   SystemProgram,
-  LAMPORTS_PER_SOL 
+  LAMPORTS_PER_SOL,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
 import { 
   TOKEN_2022_PROGRAM_ID,
   createMint,
   getAssociatedTokenAddress,
   createAssociatedTokenAccount,
+  getMint,
+  getTransferHook,
+  getPermanentDelegate,
+  getMetadataPointerState,
+  getTokenMetadata,
+  getOrCreateAssociatedTokenAccount,
+  getAccount,
+  createAccount,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { expect } from "chai";
 
+const COMPLIANCE_PROGRAM_ID = new PublicKey("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnV");
+
 describe("asset-registry", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
@@ -24,17 +38,179 @@ describe("asset-registry", () => {
   const authority = provider.wallet as anchor.Wallet;
   let configPda: PublicKey;
   let configBump: number;
-  let assetMint: Keypair;
+  let assetMint: PublicKey;
   let assetPda: PublicKey;
 
+  const findMintConfig = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("mint-config"), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  // Accounts the thaw paths check for a balance awaiting split conversion
+  const splitGuardAccounts = async (mint: PublicKey, tokenAccount: PublicKey) => {
+    const mintConfig = findMintConfig(mint);
+    const { splitCount } = await program.account.mintConfig.fetch(mintConfig);
+    const [lastSplit] = PublicKey.findProgramAddressSync(
+      [Buffer.from("split"), mint.toBuffer(), new anchor.BN(Math.max(splitCount - 1, 0)).toArrayLike(Buffer, "le", 4)],
+      program.programId
+    );
+    const [splitConversion] = PublicKey.findProgramAddressSync(
+      [Buffer.from("split-conversion"), lastSplit.toBuffer(), tokenAccount.toBuffer()],
+      program.programId
+    );
+    return { mintConfig, lastSplit, splitConversion };
+  };
+
+  const findMintAuthority = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("mint-authority"), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  // Role discriminants, matching the on-chain Role enum order
+  const Role = {
+    registryAdmin: 0,
+    issuer: 1,
+    minter: 2,
+    pauser: 3,
+    valuationOfficer: 4,
+    complianceOfficer: 5,
+    kycOperator: 6,
+    guardian: 7,
+  };
+
+  // Registry-wide roles are scoped to the program ID, asset roles to the mint
+  const findRole = (scope: PublicKey, role: number, holder: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), scope.toBuffer(), Buffer.from([role]), holder.toBuffer()],
+      program.programId
+    )[0];
+
+  const findPermanentDelegate = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("permanent-delegate"), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  const [feeTreasury] = PublicKey.findProgramAddressSync(
+    [Buffer.from("fee-treasury")],
+    program.programId
+  );
+
+  // Fee treasury token account for a mint, created on first use
+  const findLockup = (mint: PublicKey, holder: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("lockup"), mint.toBuffer(), holder.toBuffer()],
+      program.programId
+    )[0];
+  const treasuryAccount = async (mint: PublicKey) =>
+    (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        feeTreasury,
+        true,
+        "confirmed",
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      )
+    ).address;
+
+  const registryRole = (role: number) =>
+    findRole(program.programId, role, authority.publicKey);
+
+  // Creates a registry-controlled Token-2022 mint and returns its address
+  const createRwaMint = async (defaultAccountFrozen = false): Promise<PublicKey> => {
+    const mintKeypair = Keypair.generate();
+
+    await program.methods
+      .createTokenMint("Test Token", "TEST", "https://test.com", 0, null, defaultAccountFrozen)
+      .accounts({
+        authority: authority.publicKey,
+        permanentDelegate: findPermanentDelegate(mintKeypair.publicKey),
+        mint: mintKeypair.publicKey,
+        mintConfig: findMintConfig(mintKeypair.publicKey),
+        mintAuthority: findMintAuthority(mintKeypair.publicKey),
+        issuerRole: registryRole(Role.issuer),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([mintKeypair])
+      .rpc();
+
+    return mintKeypair.publicKey;
+  };
+
+  // AssetType discriminants, matching the on-chain enum order
+  const AssetType = {
+    realEstate: 0,
+    equipment: 1,
+    receivables: 2,
+    securities: 3,
+    commodities: 4,
+    intellectualProperty: 5,
+    other: 6,
+  };
+
+  // Paged asset indexes maintained by register_asset and burn_asset
+  const ASSET_INDEX_PAGE_LEN = 32;
+  const findAssetIndex = (...scope: Buffer[]) =>
+    PublicKey.findProgramAddressSync([Buffer.from("asset-index"), ...scope], program.programId)[0];
+  const findIndexPage = (index: PublicKey, position: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("asset-index-page"),
+        index.toBuffer(),
+        new anchor.BN(Math.floor(position / ASSET_INDEX_PAGE_LEN)).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+  const indexes = (issuer: PublicKey, assetType: number) => ({
+    globalIndex: findAssetIndex(),
+    issuerIndex: findAssetIndex(Buffer.from("issuer"), issuer.toBuffer()),
+    typeIndex: findAssetIndex(Buffer.from("type"), Buffer.from([assetType])),
+  });
+
+  // Index accounts the wallet's next registration of `assetType` lands in
+  const registerIndexAccounts = async (assetType = AssetType.realEstate) => {
+    const { globalIndex, issuerIndex, typeIndex } = indexes(authority.publicKey, assetType);
+    const nextPage = async (index: PublicKey) =>
+      findIndexPage(index, (await program.account.assetIndex.fetchNullable(index))?.count.toNumber() ?? 0);
+
+    return {
+      globalIndex,
+      globalIndexPage: await nextPage(globalIndex),
+      issuerIndex,
+      issuerIndexPage: await nextPage(issuerIndex),
+      typeIndex,
+      typeIndexPage: await nextPage(typeIndex),
+    };
+  };
+
+  // Index accounts holding an existing asset
+  const burnIndexAccounts = async (assetPda: PublicKey) => {
+    const asset = await program.account.asset.fetch(assetPda);
+    const assetType = Object.keys(AssetType).indexOf(Object.keys(asset.assetType)[0]);
+    const { globalIndex, issuerIndex, typeIndex } = indexes(asset.issuer, assetType);
+
+    return {
+      globalIndex,
+      globalIndexPage: findIndexPage(globalIndex, asset.sequence.toNumber()),
+      issuerIndex,
+      issuerIndexPage: findIndexPage(issuerIndex, asset.issuerSequence.toNumber()),
+      typeIndex,
+      typeIndexPage: findIndexPage(typeIndex, asset.typeSequence.toNumber()),
+    };
+  };
+
   before(async () => {
     // Derive config PDA
     [configPda, configBump] = PublicKey.findProgramAddressSync(
       [Buffer.from("config")],
       program.programId
     );
-    
-    assetMint = Keypair.generate();
   });
 
   describe("initialize", () => {
@@ -45,6 +221,7 @@ describe("asset-registry", () => {
         .initialize(platformFeeBps)
         .accounts({
           config: configPda,
+          adminRole: registryRole(Role.registryAdmin),
           authority: authority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -56,14 +233,89 @@ describe("asset-registry", () => {
       expect(config.platformFeeBps).to.equal(platformFeeBps);
       expect(config.totalAssets.toNumber()).to.equal(0);
       expect(config.bump).to.equal(configBump);
+
+      const adminRole = await program.account.roleAssignment.fetch(
+        registryRole(Role.registryAdmin)
+      );
+      expect(adminRole.holder.toString()).to.equal(authority.publicKey.toString());
+      expect(adminRole.role).to.deep.equal({ registryAdmin: {} });
+    });
+  });
+
+  describe("roles", () => {
+    const grant = (role: object, index: number, holder: PublicKey) =>
+      program.methods
+        .grantRegistryRole(role as any, holder)
+        .accounts({
+          authority: authority.publicKey,
+          adminRole: registryRole(Role.registryAdmin),
+          roleAssignment: findRole(program.programId, index, holder),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    it("grants registry-wide operational roles", async () => {
+      await grant({ issuer: {} }, Role.issuer, authority.publicKey);
+      await grant({ minter: {} }, Role.minter, authority.publicKey);
+      await grant({ pauser: {} }, Role.pauser, authority.publicKey);
+      await grant({ valuationOfficer: {} }, Role.valuationOfficer, authority.publicKey);
+      await grant({ complianceOfficer: {} }, Role.complianceOfficer, authority.publicKey);
+      await grant({ kycOperator: {} }, Role.kycOperator, authority.publicKey);
+
+      const minterRole = await program.account.roleAssignment.fetch(registryRole(Role.minter));
+      expect(minterRole.scope.toString()).to.equal(program.programId.toString());
+      expect(minterRole.grantedBy.toString()).to.equal(authority.publicKey.toString());
+    });
+
+    it("revokes a registry-wide role", async () => {
+      const holder = Keypair.generate().publicKey;
+      const roleAssignment = findRole(program.programId, Role.pauser, holder);
+      await grant({ pauser: {} }, Role.pauser, holder);
+
+      await program.methods
+        .revokeRegistryRole()
+        .accounts({
+          authority: authority.publicKey,
+          adminRole: registryRole(Role.registryAdmin),
+          roleAssignment,
+        })
+        .rpc();
+
+      const info = await provider.connection.getAccountInfo(roleAssignment);
+      expect(info).to.equal(null);
+    });
+
+    it("fails to grant roles without the registry admin role", async () => {
+      const outsider = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(outsider.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+
+      try {
+        await program.methods
+          .grantRegistryRole({ minter: {} }, outsider.publicKey)
+          .accounts({
+            authority: outsider.publicKey,
+            adminRole: registryRole(Role.registryAdmin),
+            roleAssignment: findRole(program.programId, Role.minter, outsider.publicKey),
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([outsider])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+      }
     });
   });
 
   describe("register_asset", () => {
     it("registers a new asset", async () => {
+      assetMint = await createRwaMint();
+
       // Derive asset PDA
       [assetPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("asset"), assetMint.publicKey.toBuffer()],
+        [Buffer.from("asset"), assetMint.toBuffer()],
         program.programId
       );
 
@@ -78,16 +330,19 @@ describe("asset-registry", () => {
         .accounts({
           config: configPda,
           asset: assetPda,
-          mint: assetMint.publicKey,
+          mint: assetMint,
+          mintConfig: findMintConfig(assetMint),
           authority: authority.publicKey,
+          issuerRole: registryRole(Role.issuer),
           systemProgram: SystemProgram.programId,
+          ...(await registerIndexAccounts()),
         })
         .rpc();
 
       const asset = await program.account.asset.fetch(assetPda);
       
       expect(asset.authority.toString()).to.equal(authority.publicKey.toString());
-      expect(asset.mint.toString()).to.equal(assetMint.publicKey.toString());
+      expect(asset.mint.toString()).to.equal(assetMint.toString());
       expect(asset.name).to.equal(assetName);
       expect(asset.totalValue.toNumber()).to.equal(totalValue.toNumber());
       expect(asset.totalSupply.toNumber()).to.equal(totalSupply.toNumber());
@@ -97,12 +352,37 @@ describe("asset-registry", () => {
       // Check config was updated
       const config = await program.account.config.fetch(configPda);
       expect(config.totalAssets.toNumber()).to.equal(1);
+
+      // Check the mint config is linked to the asset
+      const mintConfig = await program.account.mintConfig.fetch(findMintConfig(assetMint));
+      expect(mintConfig.asset.toString()).to.equal(assetPda.toString());
+    });
+
+    it("indexes the asset by sequence, issuer and asset type", async () => {
+      const asset = await program.account.asset.fetch(assetPda);
+      expect(asset.issuer.toString()).to.equal(authority.publicKey.toString());
+
+      const accounts = await burnIndexAccounts(assetPda);
+      for (const [index, page, position] of [
+        [accounts.globalIndex, accounts.globalIndexPage, asset.sequence],
+        [accounts.issuerIndex, accounts.issuerIndexPage, asset.issuerSequence],
+        [accounts.typeIndex, accounts.typeIndexPage, asset.typeSequence],
+      ] as [PublicKey, PublicKey, anchor.BN][]) {
+        const indexAccount = await program.account.assetIndex.fetch(index);
+        expect(indexAccount.count.toNumber()).to.be.greaterThan(position.toNumber());
+
+        const pageAccount = await program.account.assetIndexPage.fetch(page);
+        expect(pageAccount.index.toString()).to.equal(index.toString());
+        expect(pageAccount.assets[position.toNumber() % ASSET_INDEX_PAGE_LEN].toString()).to.equal(
+          assetPda.toString()
+        );
+      }
     });
 
     it("fails with name too long", async () => {
-      const badMint = Keypair.generate();
+      const badMint = await createRwaMint();
       const [badAssetPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("asset"), badMint.publicKey.toBuffer()],
+        [Buffer.from("asset"), badMint.toBuffer()],
         program.programId
       );
 
@@ -120,9 +400,12 @@ describe("asset-registry", () => {
           .accounts({
             config: configPda,
             asset: badAssetPda,
-            mint: badMint.publicKey,
+            mint: badMint,
+            mintConfig: findMintConfig(badMint),
             authority: authority.publicKey,
+            issuerRole: registryRole(Role.issuer),
             systemProgram: SystemProgram.programId,
+            ...(await registerIndexAccounts()),
           })
           .rpc();
         
@@ -133,9 +416,9 @@ describe("asset-registry", () => {
     });
 
     it("fails with zero value", async () => {
-      const badMint = Keypair.generate();
+      const badMint = await createRwaMint();
       const [badAssetPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("asset"), badMint.publicKey.toBuffer()],
+        [Buffer.from("asset"), badMint.toBuffer()],
         program.programId
       );
 
@@ -151,9 +434,12 @@ describe("asset-registry", () => {
           .accounts({
             config: configPda,
             asset: badAssetPda,
-            mint: badMint.publicKey,
+            mint: badMint,
+            mintConfig: findMintConfig(badMint),
             authority: authority.publicKey,
+            issuerRole: registryRole(Role.issuer),
             systemProgram: SystemProgram.programId,
+            ...(await registerIndexAccounts()),
           })
           .rpc();
         
@@ -169,7 +455,7 @@ describe("asset-registry", () => {
       const newUri = "https://arweave.net/updated-metadata-hash";
 
       await program.methods
-        .updateAsset(newUri, null)
+        .updateAsset(newUri)
         .accounts({
           asset: assetPda,
           authority: authority.publicKey,
@@ -180,193 +466,2898 @@ describe("asset-registry", () => {
       expect(asset.metadataUri).to.equal(newUri);
     });
 
-    it("updates asset total value", async () => {
-      const newValue = new anchor.BN(15_000_000_00); // $15M
+  });
 
-      await program.methods
-        .updateAsset(null, newValue)
+  describe("typed attributes", () => {
+    let attributes: PublicKey;
+
+    before(() => {
+      [attributes] = PublicKey.findProgramAddressSync(
+        [Buffer.from("attributes"), assetPda.toBuffer()],
+        program.programId
+      );
+    });
+
+    const setRealEstate = (occupancyBps: number) =>
+      program.methods
+        .setRealEstateAttributes(Array(32).fill(5), "APN-123-456-789", new anchor.BN(250_000), occupancyBps)
         .accounts({
           asset: assetPda,
           authority: authority.publicKey,
+          attributes,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      const asset = await program.account.asset.fetch(assetPda);
-      expect(asset.totalValue.toNumber()).to.equal(newValue.toNumber());
+    it("fails to set attributes for a different asset type", async () => {
+      try {
+        await program.methods
+          .setSecurityAttributes({ isin: {} }, "US0378331005", new anchor.BN(2_000_000_000), 450)
+          .accounts({
+            asset: assetPda,
+            authority: authority.publicKey,
+            attributes,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AssetTypeMismatch");
+      }
+    });
+
+    it("sets real estate attributes", async () => {
+      await setRealEstate(9_200);
+
+      const record = await program.account.realEstateAttributes.fetch(attributes);
+      expect(record.parcelId).to.equal("APN-123-456-789");
+      expect(record.squareFootage.toNumber()).to.equal(250_000);
+      expect(record.occupancyBps).to.equal(9_200);
+    });
+
+    it("fails with occupancy above 100%", async () => {
+      try {
+        await setRealEstate(10_001);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidAttributes");
+      }
     });
   });
 
-  describe("activate_asset", () => {
-    it("activates a pending asset", async () => {
+  describe("documents", () => {
+    const deedV1 = Buffer.alloc(32, 21);
+    const deedV2 = Buffer.alloc(32, 22);
+    const findDocument = (hash: Buffer) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("document"), assetPda.toBuffer(), hash],
+        program.programId
+      )[0];
+
+    it("adds a title deed", async () => {
       await program.methods
-        .activateAsset()
+        .addDocument({ titleDeed: {} }, Array.from(deedV1), "ipfs://deed-v1", new anchor.BN(1_700_000_000))
         .accounts({
           asset: assetPda,
           authority: authority.publicKey,
+          document: findDocument(deedV1),
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      const asset = await program.account.asset.fetch(assetPda);
-      expect(asset.status).to.deep.equal({ active: {} });
+      const document = await program.account.assetDocument.fetch(findDocument(deedV1));
+      expect(document.version).to.equal(1);
+      expect(document.status).to.deep.equal({ active: {} });
+      expect(Buffer.from(document.contentHash)).to.deep.equal(deedV1);
     });
 
-    it("fails to activate an already active asset", async () => {
+    it("supersedes the deed with a new version", async () => {
+      const effectiveAt = new anchor.BN(1_710_000_000);
+      await program.methods
+        .supersedeDocument(Array.from(deedV2), "ipfs://deed-v2", effectiveAt)
+        .accounts({
+          asset: assetPda,
+          authority: authority.publicKey,
+          previousDocument: findDocument(deedV1),
+          document: findDocument(deedV2),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const previous = await program.account.assetDocument.fetch(findDocument(deedV1));
+      expect(previous.status).to.deep.equal({ superseded: {} });
+      expect(previous.supersededBy.toString()).to.equal(findDocument(deedV2).toString());
+      expect(previous.endedAt.toNumber()).to.equal(effectiveAt.toNumber());
+
+      const current = await program.account.assetDocument.fetch(findDocument(deedV2));
+      expect(current.version).to.equal(2);
+      expect(current.documentType).to.deep.equal({ titleDeed: {} });
+    });
+
+    it("fails to revoke a superseded document", async () => {
       try {
         await program.methods
-          .activateAsset()
+          .revokeDocument()
           .accounts({
             asset: assetPda,
             authority: authority.publicKey,
+            document: findDocument(deedV1),
           })
           .rpc();
-        
         expect.fail("Should have thrown an error");
       } catch (error) {
-        expect(error.message).to.include("InvalidStatus");
+        expect(error.message).to.include("DocumentNotActive");
       }
     });
-  });
 
-  describe("freeze_asset", () => {
-    it("freezes an active asset", async () => {
+    it("revokes the current document", async () => {
       await program.methods
-        .freezeAsset()
+        .revokeDocument()
         .accounts({
           asset: assetPda,
           authority: authority.publicKey,
+          document: findDocument(deedV2),
         })
         .rpc();
 
-      const asset = await program.account.asset.fetch(assetPda);
-      expect(asset.status).to.deep.equal({ frozen: {} });
+      const document = await program.account.assetDocument.fetch(findDocument(deedV2));
+      expect(document.status).to.deep.equal({ revoked: {} });
+      expect(document.endedAt).to.not.be.null;
     });
   });
 
-  describe("unfreeze_asset", () => {
-    it("unfreezes a frozen asset", async () => {
-      await program.methods
-        .unfreezeAsset()
-        .accounts({
-          asset: assetPda,
-          authority: authority.publicKey,
-        })
-        .rpc();
+  describe("asset roles", () => {
+    it("reports NAV per token", async () => {
+      // $10M over 1M whole tokens = 1000 cents, scaled by 10^6
+      const nav = await program.methods
+        .getNavPerToken()
+        .accounts({ asset: assetPda, mintConfig: findMintConfig(assetMint) })
+        .view();
 
-      const asset = await program.account.asset.fetch(assetPda);
-      expect(asset.status).to.deep.equal({ active: {} });
+      expect(nav.toNumber()).to.equal(1000 * 1_000_000);
     });
-  });
-
-  describe("create_token_mint", () => {
-    let mintPda: PublicKey;
-    let mintConfigPda: PublicKey;
-    let mintAuthorityPda: PublicKey;
-    let permanentDelegate: Keypair;
 
-    before(() => {
-      permanentDelegate = Keypair.generate();
-      mintPda = Keypair.generate().publicKey;
+    it("fails to grant a registry-only role on an asset", async () => {
+      const holder = Keypair.generate().publicKey;
 
-      [mintConfigPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("mint-config"), mintPda.toBuffer()],
-        program.programId
-      );
+      try {
+        await program.methods
+          .grantAssetRole({ issuer: {} }, holder)
+          .accounts({
+            authority: authority.publicKey,
+            asset: assetPda,
+            roleAssignment: findRole(assetMint, Role.issuer, holder),
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
 
-      [mintAuthorityPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("mint-authority"), mintPda.toBuffer()],
-        program.programId
-      );
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidRoleScope");
+      }
     });
+  });
 
-    it("creates a token mint configuration", async () => {
-      const name = "RWA Token";
-      const symbol = "RWAT";
-      const uri = "https://arweave.net/token-metadata";
-      const decimals = 6;
-      const transferHookProgram = null;
+  describe("submit_valuation", () => {
+    const appraiser = Keypair.generate();
+    const reportHash = Buffer.alloc(32, 9);
+    let appraiserRecord: PublicKey;
+    let valuationHistory: PublicKey;
 
-      await program.methods
-        .createTokenMint(name, symbol, uri, decimals, transferHookProgram)
+    // Message the appraiser signs: asset || value || timestamp || report hash
+    const valuationMessage = (value: anchor.BN, timestamp: anchor.BN) =>
+      Buffer.concat([
+        assetPda.toBuffer(),
+        value.toArrayLike(Buffer, "le", 8),
+        timestamp.toArrayLike(Buffer, "le", 8),
+        reportHash,
+      ]);
+
+    const submit = (value: anchor.BN, timestamp: anchor.BN, signedValue: anchor.BN) =>
+      program.methods
+        .submitValuation(value, { appraisal: {} }, timestamp, Array.from(reportHash))
         .accounts({
-          authority: authority.publicKey,
-          permanentDelegate: permanentDelegate.publicKey,
-          mint: mintPda,
-          mintConfig: mintConfigPda,
-          mintAuthority: mintAuthorityPda,
-          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          payer: authority.publicKey,
+          asset: assetPda,
+          appraiserRecord,
+          valuationHistory,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
           systemProgram: SystemProgram.programId,
         })
+        .preInstructions([
+          Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: appraiser.secretKey,
+            message: valuationMessage(signedValue, timestamp),
+          }),
+        ])
         .rpc();
 
-      const mintConfig = await program.account.mintConfig.fetch(mintConfigPda);
-      
-      expect(mintConfig.mint.toString()).to.equal(mintPda.toString());
-      expect(mintConfig.authority.toString()).to.equal(authority.publicKey.toString());
-      expect(mintConfig.permanentDelegate.toString()).to.equal(permanentDelegate.publicKey.toString());
-      expect(mintConfig.name).to.equal(name);
-      expect(mintConfig.symbol).to.equal(symbol);
-      expect(mintConfig.uri).to.equal(uri);
-      expect(mintConfig.decimals).to.equal(decimals);
-      expect(mintConfig.isFrozen).to.equal(false);
-    });
-  });
-
-  describe("freeze_mint / unfreeze_mint", () => {
-    let mintPda: PublicKey;
-    let mintConfigPda: PublicKey;
+    const now = async () =>
+      new anchor.BN(await provider.connection.getBlockTime(await provider.connection.getSlot()));
 
     before(async () => {
-      mintPda = Keypair.generate().publicKey;
-      
-      [mintConfigPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("mint-config"), mintPda.toBuffer()],
+      [appraiserRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("appraiser"), appraiser.publicKey.toBuffer()],
         program.programId
       );
-
-      const [mintAuthorityPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("mint-authority"), mintPda.toBuffer()],
+      [valuationHistory] = PublicKey.findProgramAddressSync(
+        [Buffer.from("valuation-history"), assetPda.toBuffer()],
         program.programId
       );
 
-      // Create mint config first
+      const licenseExpiry = new anchor.BN(Math.floor(Date.now() / 1000) + 365 * 24 * 60 * 60);
       await program.methods
-        .createTokenMint("Test Token", "TEST", "https://test.com", 6, null)
+        .registerAppraiser(appraiser.publicKey, "RICS-1234567", licenseExpiry, [{ realEstate: {} }])
         .accounts({
           authority: authority.publicKey,
-          permanentDelegate: authority.publicKey,
-          mint: mintPda,
-          mintConfig: mintConfigPda,
-          mintAuthority: mintAuthorityPda,
-          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          adminRole: registryRole(Role.registryAdmin),
+          appraiserRecord,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
     });
 
-    it("freezes a mint", async () => {
-      await program.methods
-        .freezeMint()
+    it("records a valuation signed by a registered appraiser", async () => {
+      const value = new anchor.BN(14_000_000_00); // $14M
+      await submit(value, await now(), value);
+
+      const asset = await program.account.asset.fetch(assetPda);
+      expect(asset.totalValue.toNumber()).to.equal(value.toNumber());
+
+      const history = await program.account.valuationHistory.fetch(valuationHistory);
+      const latest = history.entries[history.entries.length - 1];
+      expect(latest.appraiser.toString()).to.equal(appraiser.publicKey.toString());
+    });
+
+    it("fails when the signed value differs from the submitted value", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 1500));
+
+      try {
+        await submit(new anchor.BN(99_000_000_00), await now(), new anchor.BN(14_000_000_00));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidSignatureVerification");
+      }
+    });
+  });
+
+  describe("refresh_valuation", () => {
+    const priceFeedProgram = anchor.workspace.MockPriceFeed as Program<MockPriceFeed>;
+    const priceFeed = Keypair.generate();
+    const expo = -5;
+    let priceFeedBinding: PublicKey;
+    let valuationHistory: PublicKey;
+    let blockTime: number;
+
+    // Price per unit in dollars, at 5 decimal places
+    const setPrice = (dollars: number, publishedSecondsAgo: number) =>
+      priceFeedProgram.methods
+        .setPrice(
+          new anchor.BN(dollars).mul(new anchor.BN(100_000)),
+          expo,
+          new anchor.BN(1_000),
+          new anchor.BN(blockTime - publishedSecondsAgo)
+        )
+        .accounts({ priceFeed: priceFeed.publicKey })
+        .rpc();
+
+    const refresh = () =>
+      program.methods
+        .refreshValuation()
+        .accounts({
+          cranker: authority.publicKey,
+          asset: assetPda,
+          priceFeedBinding,
+          priceFeed: priceFeed.publicKey,
+          valuationHistory,
+          officer: null,
+          officerRole: null,
+          systemProgram: SystemProgram.programId,
+        });
+
+    const bind = (feed: PublicKey) =>
+      program.methods
+        .bindPriceFeed(new anchor.BN(1), new anchor.BN(300), 500)
         .accounts({
+          asset: assetPda,
           authority: authority.publicKey,
-          mintConfig: mintConfigPda,
-        })
-        .rpc();
+          role: registryRole(Role.valuationOfficer),
+          config: configPda,
+          priceFeed: feed,
+          priceFeedBinding,
+          systemProgram: SystemProgram.programId,
+        });
 
-      const mintConfig = await program.account.mintConfig.fetch(mintConfigPda);
-      expect(mintConfig.isFrozen).to.equal(true);
-    });
+    before(async () => {
+      blockTime = await provider.connection.getBlockTime(await provider.connection.getSlot());
+      [priceFeedBinding] = PublicKey.findProgramAddressSync(
+        [Buffer.from("price-feed"), assetPda.toBuffer()],
+        program.programId
+      );
+      [valuationHistory] = PublicKey.findProgramAddressSync(
+        [Buffer.from("valuation-history"), assetPda.toBuffer()],
+        program.programId
+      );
 
-    it("unfreezes a mint", async () => {
+      const space = 240;
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          SystemProgram.createAccount({
+            fromPubkey: authority.publicKey,
+            newAccountPubkey: priceFeed.publicKey,
+            space,
+            lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+            programId: priceFeedProgram.programId,
+          })
+        ),
+        [priceFeed]
+      );
+      await setPrice(14_000_000, 60);
+
+      // The mock feed program stands in for Pyth on localnet
       await program.methods
-        .unfreezeMint()
+        .setPythProgram(priceFeedProgram.programId)
         .accounts({
           authority: authority.publicKey,
-          mintConfig: mintConfigPda,
+          adminRole: registryRole(Role.registryAdmin),
+          config: configPda,
         })
         .rpc();
 
-      const mintConfig = await program.account.mintConfig.fetch(mintConfigPda);
-      expect(mintConfig.isFrozen).to.equal(false);
+      await bind(priceFeed.publicKey).rpc();
+    });
+
+    it("refuses to bind a feed owned by a program other than the pinned one", async () => {
+      const foreignFeed = Keypair.generate();
+      const space = 240;
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          SystemProgram.createAccount({
+            fromPubkey: authority.publicKey,
+            newAccountPubkey: foreignFeed.publicKey,
+            space,
+            lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+            programId: COMPLIANCE_PROGRAM_ID,
+          })
+        ),
+        [foreignFeed]
+      );
+
+      try {
+        await bind(foreignFeed.publicKey).rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("UnapprovedFeedProgram");
+      }
+
+      const binding = await program.account.priceFeedBinding.fetch(priceFeedBinding);
+      expect(binding.priceFeed.toString()).to.equal(priceFeed.publicKey.toString());
+      expect(binding.feedProgram.toString()).to.equal(priceFeedProgram.programId.toString());
+    });
+
+    it("refreshes the valuation from the bound feed", async () => {
+      await setPrice(14_280_000, 50); // +2%
+      await refresh().rpc();
+
+      const asset = await program.account.asset.fetch(assetPda);
+      expect(asset.totalValue.toNumber()).to.equal(14_280_000_00);
+
+      const binding = await program.account.priceFeedBinding.fetch(priceFeedBinding);
+      expect(binding.lastPublishTime.toNumber()).to.equal(blockTime - 50);
+      expect(binding.lastUpdateSlot.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("fails to apply the same price twice", async () => {
+      try {
+        await refresh().rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("StalePrice");
+      }
+    });
+
+    it("fails on a price older than the staleness window", async () => {
+      await setPrice(14_300_000, 3600);
+
+      try {
+        await refresh().rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("StalePrice");
+      }
+    });
+
+    it("requires a valuation officer co-signer beyond the deviation cap", async () => {
+      await setPrice(17_136_000, 40); // +20%
+
+      try {
+        await refresh().rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("PriceDeviationExceeded");
+      }
+
+      // An asset-scoped valuation officer can co-sign
+      const officer = Keypair.generate();
+      const officerRole = findRole(assetMint, Role.valuationOfficer, officer.publicKey);
+      await program.methods
+        .grantAssetRole({ valuationOfficer: {} }, officer.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+          asset: assetPda,
+          roleAssignment: officerRole,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await refresh()
+        .accounts({ officer: officer.publicKey, officerRole })
+        .signers([officer])
+        .rpc();
+
+      const asset = await program.account.asset.fetch(assetPda);
+      expect(asset.totalValue.toNumber()).to.equal(17_136_000_00);
+
+      await program.methods
+        .revokeAssetRole()
+        .accounts({ authority: authority.publicKey, asset: assetPda, roleAssignment: officerRole })
+        .rpc();
+    });
+  });
+
+  describe("activate_asset", () => {
+    it("activates a pending asset", async () => {
+      await program.methods
+        .activateAsset()
+        .accounts({
+          asset: assetPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      const asset = await program.account.asset.fetch(assetPda);
+      expect(asset.status).to.deep.equal({ active: {} });
+    });
+
+    it("fails to activate an already active asset", async () => {
+      try {
+        await program.methods
+          .activateAsset()
+          .accounts({
+            asset: assetPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+        
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidStatus");
+      }
+    });
+  });
+
+  describe("freeze_asset", () => {
+    it("freezes an active asset", async () => {
+      await program.methods
+        .freezeAsset()
+        .accounts({
+          asset: assetPda,
+          authority: authority.publicKey,
+          role: registryRole(Role.pauser),
+        })
+        .rpc();
+
+      const asset = await program.account.asset.fetch(assetPda);
+      expect(asset.status).to.deep.equal({ frozen: {} });
+    });
+  });
+
+  describe("unfreeze_asset", () => {
+    it("unfreezes a frozen asset", async () => {
+      await program.methods
+        .unfreezeAsset()
+        .accounts({
+          asset: assetPda,
+          authority: authority.publicKey,
+          role: registryRole(Role.pauser),
+        })
+        .rpc();
+
+      const asset = await program.account.asset.fetch(assetPda);
+      expect(asset.status).to.deep.equal({ active: {} });
+    });
+  });
+
+  describe("lifecycle", () => {
+    const setStatus = (status: object, reason: object) =>
+      program.methods
+        .setLifecycleStatus(status as any, reason as any)
+        .accounts({
+          asset: assetPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+    it("fails to redeem an asset that has not matured", async () => {
+      try {
+        await setStatus({ redeemed: {} }, { repayment: {} });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidStatusTransition");
+      }
+    });
+
+    it("declares a default", async () => {
+      await setStatus({ defaulted: {} }, { paymentDefault: {} });
+
+      const asset = await program.account.asset.fetch(assetPda);
+      expect(asset.status).to.deep.equal({ defaulted: {} });
+      expect(asset.statusReason).to.deep.equal({ paymentDefault: {} });
+      expect(asset.defaultedAt.toNumber()).to.equal(asset.statusChangedAt.toNumber());
+    });
+
+    it("cures the default back to active", async () => {
+      await setStatus({ active: {} }, { defaultCured: {} });
+
+      const asset = await program.account.asset.fetch(assetPda);
+      expect(asset.status).to.deep.equal({ active: {} });
+      expect(asset.defaultedAt).to.not.be.null;
+    });
+  });
+
+  describe("mint_tokens", () => {
+    let recipient: Keypair;
+    let recipientTokenAccount: PublicKey;
+
+    let treasuryTokenAccount: PublicKey;
+
+    const mintTokens = (amount: anchor.BN) =>
+      program.methods
+        .mintTokens(amount, null)
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.minter),
+          mintConfig: findMintConfig(assetMint),
+          asset: assetPda,
+          mint: assetMint,
+          mintAuthority: findMintAuthority(assetMint),
+          recipient: recipient.publicKey,
+          recipientTokenAccount,
+          config: configPda,
+          feeOverride: null,
+          reserve: null,
+          feeTreasury,
+          treasuryTokenAccount,
+          lockup: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    before(async () => {
+      recipient = Keypair.generate();
+      recipientTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        assetMint,
+        recipient.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+      treasuryTokenAccount = await treasuryAccount(assetMint);
+    });
+
+    it("mints within the registered supply and tracks the ledger", async () => {
+      await mintTokens(new anchor.BN(600_000));
+
+      const mintConfig = await program.account.mintConfig.fetch(findMintConfig(assetMint));
+      expect(mintConfig.mintedSupply.toNumber()).to.equal(600_000);
+      expect(mintConfig.burnedSupply.toNumber()).to.equal(0);
+    });
+
+    it("takes the platform fee in kind on issuance", async () => {
+      // 600,000 issued at 250 bps
+      const fee = 15_000;
+
+      const recipientBalance = await getAccount(
+        provider.connection,
+        recipientTokenAccount,
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(Number(recipientBalance.amount)).to.equal(600_000 - fee);
+
+      const treasuryBalance = await getAccount(
+        provider.connection,
+        treasuryTokenAccount,
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(Number(treasuryBalance.amount)).to.equal(fee);
+    });
+
+    it("applies a per-asset fee override", async () => {
+      const feeOverride = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee-override"), assetMint.toBuffer()],
+        program.programId
+      )[0];
+
+      await program.methods
+        .setFeeOverride(0)
+        .accounts({
+          authority: authority.publicKey,
+          adminRole: registryRole(Role.registryAdmin),
+          mint: assetMint,
+          feeOverride,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .mintTokens(new anchor.BN(1_000), null)
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.minter),
+          mintConfig: findMintConfig(assetMint),
+          asset: assetPda,
+          mint: assetMint,
+          mintAuthority: findMintAuthority(assetMint),
+          recipient: recipient.publicKey,
+          recipientTokenAccount,
+          config: configPda,
+          feeOverride,
+          reserve: null,
+          feeTreasury,
+          treasuryTokenAccount,
+          lockup: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const treasuryBalance = await getAccount(
+        provider.connection,
+        treasuryTokenAccount,
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(Number(treasuryBalance.amount)).to.equal(15_000);
+
+      await program.methods
+        .clearFeeOverride()
+        .accounts({
+          authority: authority.publicKey,
+          adminRole: registryRole(Role.registryAdmin),
+          feeOverride,
+        })
+        .rpc();
+    });
+
+    it("batch mints to existing and newly created token accounts", async () => {
+      const subscriber = Keypair.generate();
+      const subscriberTokenAccount = await getAssociatedTokenAddress(
+        assetMint,
+        subscriber.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID
+      );
+      const holdingPeriod = new anchor.BN(365 * 24 * 60 * 60);
+
+      await program.methods
+        .batchMintTokens(
+          [
+            { recipient: recipient.publicKey, amount: new anchor.BN(2_000), lockup: null },
+            {
+              recipient: subscriber.publicKey,
+              amount: new anchor.BN(4_000),
+              lockup: { cliffDuration: holdingPeriod, vestingDuration: holdingPeriod },
+            },
+          ],
+          true
+        )
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.minter),
+          mintConfig: findMintConfig(assetMint),
+          asset: assetPda,
+          mint: assetMint,
+          mintAuthority: findMintAuthority(assetMint),
+          config: configPda,
+          feeOverride: null,
+          reserve: null,
+          feeTreasury,
+          treasuryTokenAccount,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: recipient.publicKey, isSigner: false, isWritable: false },
+          { pubkey: recipientTokenAccount, isSigner: false, isWritable: true },
+          { pubkey: findLockup(assetMint, recipient.publicKey), isSigner: false, isWritable: true },
+          { pubkey: subscriber.publicKey, isSigner: false, isWritable: false },
+          { pubkey: subscriberTokenAccount, isSigner: false, isWritable: true },
+          { pubkey: findLockup(assetMint, subscriber.publicKey), isSigner: false, isWritable: true },
+        ])
+        .rpc();
+
+      const subscriberBalance = await getAccount(
+        provider.connection,
+        subscriberTokenAccount,
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      // 250 bps taken in kind
+      expect(Number(subscriberBalance.amount)).to.equal(3_900);
+
+      const treasuryBalance = await getAccount(
+        provider.connection,
+        treasuryTokenAccount,
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(Number(treasuryBalance.amount)).to.equal(15_000 + 50 + 100);
+
+      const mintConfig = await program.account.mintConfig.fetch(findMintConfig(assetMint));
+      expect(mintConfig.mintedSupply.toNumber()).to.equal(607_000);
+
+      // The subscriber's allocation is locked for the holding period
+      const lockup = await program.account.lockup.fetch(findLockup(assetMint, subscriber.publicKey));
+      expect(lockup.holder.toString()).to.equal(subscriber.publicKey.toString());
+      expect(lockup.lots.length).to.equal(1);
+      expect(lockup.lots[0].amount.toNumber()).to.equal(3_900);
+    });
+
+    it("fails to batch mint to a missing account without creation", async () => {
+      const subscriber = Keypair.generate();
+
+      try {
+        await program.methods
+          .batchMintTokens([{ recipient: subscriber.publicKey, amount: new anchor.BN(1_000), lockup: null }], false)
+          .accounts({
+            authority: authority.publicKey,
+            role: registryRole(Role.minter),
+            mintConfig: findMintConfig(assetMint),
+            asset: assetPda,
+            mint: assetMint,
+            mintAuthority: findMintAuthority(assetMint),
+            config: configPda,
+            feeOverride: null,
+            reserve: null,
+            feeTreasury,
+            treasuryTokenAccount,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            { pubkey: subscriber.publicKey, isSigner: false, isWritable: false },
+            {
+              pubkey: await getAssociatedTokenAddress(
+                assetMint,
+                subscriber.publicKey,
+                false,
+                TOKEN_2022_PROGRAM_ID
+              ),
+              isSigner: false,
+              isWritable: true,
+            },
+            { pubkey: findLockup(assetMint, subscriber.publicKey), isSigner: false, isWritable: true },
+          ])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidBatchAccounts");
+      }
+    });
+
+    it("fails to create accounts while batch minting on a default-frozen mint", async () => {
+      const frozenMint = await createRwaMint(true);
+      const frozenAsset = PublicKey.findProgramAddressSync(
+        [Buffer.from("asset"), frozenMint.toBuffer()],
+        program.programId
+      )[0];
+      const subscriber = Keypair.generate();
+
+      await program.methods
+        .registerAsset("Frozen Asset", { realEstate: {} }, new anchor.BN(1000), new anchor.BN(10_000), "https://test.com")
+        .accounts({
+          config: configPda,
+          asset: frozenAsset,
+          mint: frozenMint,
+          mintConfig: findMintConfig(frozenMint),
+          authority: authority.publicKey,
+          issuerRole: registryRole(Role.issuer),
+          systemProgram: SystemProgram.programId,
+          ...(await registerIndexAccounts()),
+        })
+        .rpc();
+      await program.methods
+        .activateAsset()
+        .accounts({ asset: frozenAsset, authority: authority.publicKey })
+        .rpc();
+
+      try {
+        await program.methods
+          .batchMintTokens([{ recipient: subscriber.publicKey, amount: new anchor.BN(1_000), lockup: null }], true)
+          .accounts({
+            authority: authority.publicKey,
+            role: registryRole(Role.minter),
+            mintConfig: findMintConfig(frozenMint),
+            asset: frozenAsset,
+            mint: frozenMint,
+            mintAuthority: findMintAuthority(frozenMint),
+            config: configPda,
+            feeOverride: null,
+            reserve: null,
+            feeTreasury,
+            treasuryTokenAccount: await treasuryAccount(frozenMint),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            { pubkey: subscriber.publicKey, isSigner: false, isWritable: false },
+            {
+              pubkey: await getAssociatedTokenAddress(
+                frozenMint,
+                subscriber.publicKey,
+                false,
+                TOKEN_2022_PROGRAM_ID
+              ),
+              isSigner: false,
+              isWritable: true,
+            },
+            { pubkey: findLockup(frozenMint, subscriber.publicKey), isSigner: false, isWritable: true },
+          ])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("FrozenAccountCreation");
+      }
+    });
+
+    it("records a lockup at issuance and reports the unlocked balance", async () => {
+      const holder = Keypair.generate().publicKey;
+      const holderTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        assetMint,
+        holder,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+      const lockup = PublicKey.findProgramAddressSync(
+        [Buffer.from("lockup"), assetMint.toBuffer(), holder.toBuffer()],
+        program.programId
+      )[0];
+      const year = 365 * 24 * 60 * 60;
+
+      await program.methods
+        .mintTokens(new anchor.BN(1_000), {
+          cliffDuration: new anchor.BN(year),
+          vestingDuration: new anchor.BN(2 * year),
+        })
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.minter),
+          mintConfig: findMintConfig(assetMint),
+          asset: assetPda,
+          mint: assetMint,
+          mintAuthority: findMintAuthority(assetMint),
+          recipient: holder,
+          recipientTokenAccount: holderTokenAccount,
+          config: configPda,
+          feeOverride: null,
+          reserve: null,
+          feeTreasury,
+          treasuryTokenAccount,
+          lockup,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const record = await program.account.lockup.fetch(lockup);
+      expect(record.holder.toString()).to.equal(holder.toString());
+      expect(record.lots.length).to.equal(1);
+      // The lot covers what the holder received after the issuance fee
+      expect(record.lots[0].amount.toNumber()).to.equal(975);
+      expect(record.lots[0].cliffEnd.sub(record.lots[0].start).toNumber()).to.equal(year);
+
+      const unlocked = await program.methods
+        .getUnlockedBalance()
+        .accounts({ holderTokenAccount, lockup })
+        .view();
+      expect(unlocked.toNumber()).to.equal(0);
+    });
+
+    it("fails to mint a recipient's lockup into someone else's token account", async () => {
+      const year = new anchor.BN(365 * 24 * 60 * 60);
+      const holder = Keypair.generate().publicKey;
+
+      try {
+        await program.methods
+          .mintTokens(new anchor.BN(1_000), { cliffDuration: year, vestingDuration: year })
+          .accounts({
+            authority: authority.publicKey,
+            role: registryRole(Role.minter),
+            mintConfig: findMintConfig(assetMint),
+            asset: assetPda,
+            mint: assetMint,
+            mintAuthority: findMintAuthority(assetMint),
+            recipient: holder,
+            recipientTokenAccount,
+            config: configPda,
+            feeOverride: null,
+            reserve: null,
+            feeTreasury,
+            treasuryTokenAccount,
+            lockup: findLockup(assetMint, holder),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ConstraintTokenOwner");
+      }
+    });
+
+    it("fails to mint beyond the registered supply", async () => {
+      try {
+        await mintTokens(new anchor.BN(400_001));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("SupplyCapExceeded");
+      }
+    });
+
+    it("fails to mint while the asset is frozen", async () => {
+      await program.methods
+        .freezeAsset()
+        .accounts({ asset: assetPda, authority: authority.publicKey, role: registryRole(Role.pauser) })
+        .rpc();
+
+      try {
+        await mintTokens(new anchor.BN(1));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AssetNotActive");
+      }
+
+      await program.methods
+        .unfreezeAsset()
+        .accounts({ asset: assetPda, authority: authority.publicKey, role: registryRole(Role.pauser) })
+        .rpc();
+    });
+
+    it("fails to force transfer without a document hash", async () => {
+      const quarantine = Keypair.generate().publicKey;
+
+      try {
+        await program.methods
+          .forceTransfer(new anchor.BN(1), { courtOrder: {} }, Array(32).fill(0))
+          .accounts({
+            authority: authority.publicKey,
+            role: registryRole(Role.complianceOfficer),
+            mintConfig: findMintConfig(assetMint),
+            mint: assetMint,
+            permanentDelegate: findPermanentDelegate(assetMint),
+            sourceTokenAccount: recipientTokenAccount,
+            destinationTokenAccount: await createAssociatedTokenAccount(
+              provider.connection,
+              authority.payer,
+              assetMint,
+              quarantine,
+              { commitment: "confirmed" },
+              TOKEN_2022_PROGRAM_ID
+            ),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidDocumentHash");
+      }
+    });
+  });
+
+  describe("freeze_holder / thaw_holder", () => {
+    const holder = Keypair.generate();
+    let holderTokenAccount: PublicKey;
+    let holderFreeze: PublicKey;
+
+    before(async () => {
+      holderTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        assetMint,
+        holder.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+      [holderFreeze] = PublicKey.findProgramAddressSync(
+        [Buffer.from("holder-freeze"), assetMint.toBuffer(), holderTokenAccount.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("freezes a single holder under a legal hold", async () => {
+      await program.methods
+        .freezeHolder({ legalHold: {} }, "CASE-2024-0042")
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.complianceOfficer),
+          mint: assetMint,
+          mintAuthority: findMintAuthority(assetMint),
+          holderTokenAccount,
+          holderFreeze,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const account = await getAccount(provider.connection, holderTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+      expect(account.isFrozen).to.be.true;
+
+      const record = await program.account.holderFreeze.fetch(holderFreeze);
+      expect(record.owner.toString()).to.equal(holder.publicKey.toString());
+      expect(record.caseReference).to.equal("CASE-2024-0042");
+    });
+
+    it("thaws the holder and closes the record", async () => {
+      await program.methods
+        .thawHolder()
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.complianceOfficer),
+          mint: assetMint,
+          mintAuthority: findMintAuthority(assetMint),
+          holderTokenAccount,
+          holderFreeze,
+          ...(await splitGuardAccounts(assetMint, holderTokenAccount)),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      const account = await getAccount(provider.connection, holderTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+      expect(account.isFrozen).to.be.false;
+      expect(await provider.connection.getAccountInfo(holderFreeze)).to.be.null;
+    });
+  });
+
+  describe("create_token_mint", () => {
+    let mintKeypair: Keypair;
+    let mintPda: PublicKey;
+    let mintConfigPda: PublicKey;
+    let mintAuthorityPda: PublicKey;
+    let permanentDelegate: PublicKey;
+
+    before(() => {
+      mintKeypair = Keypair.generate();
+      mintPda = mintKeypair.publicKey;
+      permanentDelegate = findPermanentDelegate(mintPda);
+
+      [mintConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint-config"), mintPda.toBuffer()],
+        program.programId
+      );
+
+      [mintAuthorityPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint-authority"), mintPda.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("creates a Token-2022 mint with RWA extensions", async () => {
+      const name = "RWA Token";
+      const symbol = "RWAT";
+      const uri = "https://arweave.net/token-metadata";
+      const decimals = 6;
+      const transferHookProgram = null;
+
+      await program.methods
+        .createTokenMint(name, symbol, uri, decimals, transferHookProgram, false)
+        .accounts({
+          authority: authority.publicKey,
+          permanentDelegate,
+          mint: mintPda,
+          mintConfig: mintConfigPda,
+          mintAuthority: mintAuthorityPda,
+          issuerRole: registryRole(Role.issuer),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([mintKeypair])
+        .rpc();
+
+      const mintConfig = await program.account.mintConfig.fetch(mintConfigPda);
+      
+      expect(mintConfig.mint.toString()).to.equal(mintPda.toString());
+      expect(mintConfig.authority.toString()).to.equal(authority.publicKey.toString());
+      expect(mintConfig.permanentDelegate.toString()).to.equal(permanentDelegate.toString());
+      expect(mintConfig.name).to.equal(name);
+      expect(mintConfig.symbol).to.equal(symbol);
+      expect(mintConfig.uri).to.equal(uri);
+      expect(mintConfig.decimals).to.equal(decimals);
+      expect(mintConfig.isFrozen).to.equal(false);
+      expect(mintConfig.transferHookProgram.toString()).to.equal(COMPLIANCE_PROGRAM_ID.toString());
+
+      const mint = await getMint(provider.connection, mintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(mint.mintAuthority.toString()).to.equal(mintAuthorityPda.toString());
+      expect(mint.freezeAuthority.toString()).to.equal(mintAuthorityPda.toString());
+      expect(mint.decimals).to.equal(decimals);
+
+      const transferHook = getTransferHook(mint);
+      expect(transferHook.programId.toString()).to.equal(COMPLIANCE_PROGRAM_ID.toString());
+
+      const delegate = getPermanentDelegate(mint);
+      expect(delegate.delegate.toString()).to.equal(permanentDelegate.toString());
+
+      const metadataPointer = getMetadataPointerState(mint);
+      expect(metadataPointer.metadataAddress.toString()).to.equal(mintPda.toString());
+
+      const metadata = await getTokenMetadata(provider.connection, mintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(metadata.name).to.equal(name);
+      expect(metadata.symbol).to.equal(symbol);
+      expect(metadata.uri).to.equal(uri);
+    });
+  });
+
+  describe("freeze_mint / unfreeze_mint", () => {
+    let mintPda: PublicKey;
+    let mintConfigPda: PublicKey;
+
+    before(async () => {
+      const mintKeypair = Keypair.generate();
+      mintPda = mintKeypair.publicKey;
+      
+      [mintConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint-config"), mintPda.toBuffer()],
+        program.programId
+      );
+
+      const [mintAuthorityPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint-authority"), mintPda.toBuffer()],
+        program.programId
+      );
+
+      // Create mint config first
+      await program.methods
+        .createTokenMint("Test Token", "TEST", "https://test.com", 6, null, false)
+        .accounts({
+          authority: authority.publicKey,
+          permanentDelegate: findPermanentDelegate(mintPda),
+          mint: mintPda,
+          mintConfig: mintConfigPda,
+          mintAuthority: mintAuthorityPda,
+          issuerRole: registryRole(Role.issuer),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([mintKeypair])
+        .rpc();
+    });
+
+    it("freezes a mint", async () => {
+      await program.methods
+        .freezeMint()
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.pauser),
+          mintConfig: mintConfigPda,
+          asset: null,
+        })
+        .rpc();
+
+      const mintConfig = await program.account.mintConfig.fetch(mintConfigPda);
+      expect(mintConfig.isFrozen).to.equal(true);
+    });
+
+    it("unfreezes a mint", async () => {
+      await program.methods
+        .unfreezeMint()
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.pauser),
+          mintConfig: mintConfigPda,
+        })
+        .rpc();
+
+      const mintConfig = await program.account.mintConfig.fetch(mintConfigPda);
+      expect(mintConfig.isFrozen).to.equal(false);
+    });
+  });
+
+  describe("retirement", () => {
+    let mint: PublicKey;
+    let retiringAsset: PublicKey;
+    let holder: Keypair;
+    let holderTokenAccount: PublicKey;
+
+    const burnAsset = async () =>
+      program.methods
+        .burnAsset()
+        .accounts({
+          asset: retiringAsset,
+          config: configPda,
+          mintConfig: findMintConfig(mint),
+          mint,
+          authority: authority.publicKey,
+          ...(await burnIndexAccounts(retiringAsset)),
+        })
+        .rpc();
+
+    before(async () => {
+      mint = await createRwaMint();
+      [retiringAsset] = PublicKey.findProgramAddressSync(
+        [Buffer.from("asset"), mint.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .registerAsset("Retiring Asset", { realEstate: {} }, new anchor.BN(1000), new anchor.BN(100), "https://test.com")
+        .accounts({
+          config: configPda,
+          asset: retiringAsset,
+          mint,
+          mintConfig: findMintConfig(mint),
+          authority: authority.publicKey,
+          issuerRole: registryRole(Role.issuer),
+          systemProgram: SystemProgram.programId,
+          ...(await registerIndexAccounts()),
+        })
+        .rpc();
+
+      await program.methods
+        .activateAsset()
+        .accounts({ asset: retiringAsset, authority: authority.publicKey })
+        .rpc();
+
+      holder = Keypair.generate();
+      holderTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        holder.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      await program.methods
+        .mintTokens(new anchor.BN(100), null)
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.minter),
+          mintConfig: findMintConfig(mint),
+          asset: retiringAsset,
+          mint,
+          mintAuthority: findMintAuthority(mint),
+          recipient: holder.publicKey,
+          recipientTokenAccount: holderTokenAccount,
+          config: configPda,
+          feeOverride: null,
+          reserve: null,
+          feeTreasury,
+          treasuryTokenAccount: await treasuryAccount(mint),
+          lockup: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("fails to burn an asset that is not retiring", async () => {
+      try {
+        await burnAsset();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AssetNotRetiring");
+      }
+    });
+
+    it("lets holders redeem tokens once retirement starts", async () => {
+      await program.methods
+        .beginRetirement()
+        .accounts({ asset: retiringAsset, mintConfig: findMintConfig(mint), authority: authority.publicKey })
+        .rpc();
+
+      await program.methods
+        .redeemTokens(new anchor.BN(40))
+        .accounts({
+          holder: holder.publicKey,
+          asset: retiringAsset,
+          mintConfig: findMintConfig(mint),
+          mint,
+          holderTokenAccount,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([holder])
+        .rpc();
+
+      const mintConfig = await program.account.mintConfig.fetch(findMintConfig(mint));
+      expect(mintConfig.burnedSupply.toNumber()).to.equal(40);
+    });
+
+    it("fails to burn the asset while supply is outstanding", async () => {
+      try {
+        await burnAsset();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("SupplyOutstanding");
+      }
+    });
+
+    it("retires remaining holder tokens, burns and closes the asset", async () => {
+      // Issuance fees sit in the treasury and are retired like any other holding
+      for (const account of [holderTokenAccount, await treasuryAccount(mint)]) {
+        await program.methods
+          .retireHolderTokens()
+          .accounts({
+            authority: authority.publicKey,
+            asset: retiringAsset,
+            mintConfig: findMintConfig(mint),
+            mint,
+            permanentDelegate: findPermanentDelegate(mint),
+            holderTokenAccount: account,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+      }
+
+      const tokenMint = await getMint(provider.connection, mint, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(Number(tokenMint.supply)).to.equal(0);
+
+      await burnAsset();
+      const asset = await program.account.asset.fetch(retiringAsset);
+      expect(asset.status).to.deep.equal({ burned: {} });
+
+      // The asset leaves a tombstone at its index position
+      const { globalIndexPage } = await burnIndexAccounts(retiringAsset);
+      const page = await program.account.assetIndexPage.fetch(globalIndexPage);
+      expect(page.assets[asset.sequence.toNumber() % ASSET_INDEX_PAGE_LEN].toString()).to.equal(
+        PublicKey.default.toString()
+      );
+
+      await program.methods
+        .closeAsset()
+        .accounts({
+          authority: authority.publicKey,
+          asset: retiringAsset,
+          mintConfig: findMintConfig(mint),
+        })
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(retiringAsset)).to.equal(null);
+      expect(await provider.connection.getAccountInfo(findMintConfig(mint))).to.equal(null);
+    });
+  });
+
+  describe("authority transfer", () => {
+    const successor = Keypair.generate();
+    let transferPda: PublicKey;
+
+    before(() => {
+      [transferPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("authority-transfer"), assetPda.toBuffer()],
+        program.programId
+      );
+    });
+
+    const propose = () =>
+      program.methods
+        .proposeAssetAuthority(successor.publicKey, null)
+        .accounts({
+          authority: authority.publicKey,
+          asset: assetPda,
+          authorityTransfer: transferPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    it("cancels a pending asset authority transfer", async () => {
+      await propose();
+
+      const transfer = await program.account.authorityTransfer.fetch(transferPda);
+      expect(transfer.proposedAuthority.toString()).to.equal(successor.publicKey.toString());
+      expect(transfer.kind).to.deep.equal({ asset: {} });
+
+      await program.methods
+        .cancelAuthorityTransfer()
+        .accounts({
+          currentAuthority: authority.publicKey,
+          authorityTransfer: transferPda,
+        })
+        .rpc();
+
+      const info = await provider.connection.getAccountInfo(transferPda);
+      expect(info).to.equal(null);
+    });
+
+    it("fails to accept from a key other than the proposed authority", async () => {
+      await propose();
+      const impostor = Keypair.generate();
+
+      try {
+        await program.methods
+          .acceptAssetAuthority()
+          .accounts({
+            newAuthority: impostor.publicKey,
+            asset: assetPda,
+            authorityTransfer: transferPda,
+            currentAuthority: authority.publicKey,
+          })
+          .signers([impostor])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+      }
+    });
+
+    it("hands the asset to the proposed authority on acceptance", async () => {
+      await program.methods
+        .acceptAssetAuthority()
+        .accounts({
+          newAuthority: successor.publicKey,
+          asset: assetPda,
+          authorityTransfer: transferPda,
+          currentAuthority: authority.publicKey,
+        })
+        .signers([successor])
+        .rpc();
+
+      const asset = await program.account.asset.fetch(assetPda);
+      expect(asset.authority.toString()).to.equal(successor.publicKey.toString());
+
+      const info = await provider.connection.getAccountInfo(transferPda);
+      expect(info).to.equal(null);
+    });
+
+    it("moves the registry admin role with the config authority", async () => {
+      const sig = await provider.connection.requestAirdrop(successor.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+
+      const [configTransfer] = PublicKey.findProgramAddressSync(
+        [Buffer.from("authority-transfer"), configPda.toBuffer()],
+        program.programId
+      );
+      const handOver = async (from: PublicKey, to: Keypair | anchor.Wallet) => {
+        await program.methods
+          .proposeConfigAuthority(to.publicKey, null)
+          .accounts({
+            authority: from,
+            config: configPda,
+            authorityTransfer: configTransfer,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers(from.equals(authority.publicKey) ? [] : [successor])
+          .rpc();
+
+        await program.methods
+          .acceptConfigAuthority()
+          .accounts({
+            newAuthority: to.publicKey,
+            config: configPda,
+            authorityTransfer: configTransfer,
+            currentAuthority: from,
+            oldAdminRole: findRole(program.programId, Role.registryAdmin, from),
+            newAdminRole: findRole(program.programId, Role.registryAdmin, to.publicKey),
+            systemProgram: SystemProgram.programId,
+          })
+          .signers(to instanceof Keypair ? [to] : [])
+          .rpc();
+      };
+
+      await handOver(authority.publicKey, successor);
+
+      let config = await program.account.config.fetch(configPda);
+      expect(config.authority.toString()).to.equal(successor.publicKey.toString());
+
+      const successorRole = await program.account.roleAssignment.fetch(
+        findRole(program.programId, Role.registryAdmin, successor.publicKey)
+      );
+      expect(successorRole.holder.toString()).to.equal(successor.publicKey.toString());
+      expect(successorRole.grantedBy.toString()).to.equal(authority.publicKey.toString());
+      expect(
+        await provider.connection.getAccountInfo(findRole(program.programId, Role.registryAdmin, authority.publicKey))
+      ).to.equal(null);
+
+      // Hand the registry back so the remaining suites keep their admin
+      await handOver(successor.publicKey, authority);
+
+      config = await program.account.config.fetch(configPda);
+      expect(config.authority.toString()).to.equal(authority.publicKey.toString());
+      expect(
+        await provider.connection.getAccountInfo(findRole(program.programId, Role.registryAdmin, successor.publicKey))
+      ).to.equal(null);
+      const restoredRole = await program.account.roleAssignment.fetch(
+        findRole(program.programId, Role.registryAdmin, authority.publicKey)
+      );
+      expect(restoredRole.holder.toString()).to.equal(authority.publicKey.toString());
+    });
+
+    it("hands the mint config to the proposed authority on acceptance", async () => {
+      const mintConfig = findMintConfig(assetMint);
+      const [mintConfigTransfer] = PublicKey.findProgramAddressSync(
+        [Buffer.from("authority-transfer"), mintConfig.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .proposeMintConfigAuthority(successor.publicKey, null)
+        .accounts({
+          authority: authority.publicKey,
+          mintConfig,
+          authorityTransfer: mintConfigTransfer,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await program.methods
+        .acceptMintConfigAuthority()
+        .accounts({
+          newAuthority: successor.publicKey,
+          mintConfig,
+          authorityTransfer: mintConfigTransfer,
+          currentAuthority: authority.publicKey,
+        })
+        .signers([successor])
+        .rpc();
+
+      let config = await program.account.mintConfig.fetch(mintConfig);
+      expect(config.authority.toString()).to.equal(successor.publicKey.toString());
+      expect(await provider.connection.getAccountInfo(mintConfigTransfer)).to.equal(null);
+
+      // The outgoing authority can no longer propose on the mint config
+      try {
+        await program.methods
+          .proposeMintConfigAuthority(authority.publicKey, null)
+          .accounts({
+            authority: authority.publicKey,
+            mintConfig,
+            authorityTransfer: mintConfigTransfer,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ConstraintHasOne");
+      }
+
+      // Hand the mint config back for the remaining suites
+      await program.methods
+        .proposeMintConfigAuthority(authority.publicKey, null)
+        .accounts({
+          authority: successor.publicKey,
+          mintConfig,
+          authorityTransfer: mintConfigTransfer,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([successor])
+        .rpc();
+
+      await program.methods
+        .acceptMintConfigAuthority()
+        .accounts({
+          newAuthority: authority.publicKey,
+          mintConfig,
+          authorityTransfer: mintConfigTransfer,
+          currentAuthority: successor.publicKey,
+        })
+        .rpc();
+
+      config = await program.account.mintConfig.fetch(mintConfig);
+      expect(config.authority.toString()).to.equal(authority.publicKey.toString());
+    });
+  });
+
+  describe("approvals", () => {
+    const secondApprover = Keypair.generate();
+    const thirdApprover = Keypair.generate();
+    let mint: PublicKey;
+    let approvedAsset: PublicKey;
+    let approverSet: PublicKey;
+
+    const [registryApproverSet] = PublicKey.findProgramAddressSync(
+      [Buffer.from("approver-set"), program.programId.toBuffer()],
+      program.programId
+    );
+
+    const findProposal = (id: number, set = approverSet) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal"), set.toBuffer(), new anchor.BN(id).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    const expiresAt = () => new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+    const propose = async (action: any, set = approverSet) => {
+      const { proposalCount } = await program.account.approverSet.fetch(set);
+      const proposal = findProposal(proposalCount.toNumber(), set);
+      await program.methods
+        .createProposal(action, expiresAt())
+        .accounts({
+          proposer: authority.publicKey,
+          approverSet: set,
+          proposal,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      return proposal;
+    };
+
+    const approve = (proposal: PublicKey, approver: Keypair, set = approverSet) =>
+      program.methods
+        .approveProposal()
+        .accounts({ approver: approver.publicKey, approverSet: set, proposal })
+        .signers([approver])
+        .rpc();
+
+    const executeActivation = (proposal: PublicKey, set = approverSet, asset = approvedAsset) =>
+      program.methods
+        .executeActivateAsset()
+        .accounts({
+          executor: authority.publicKey,
+          approverSet: set,
+          proposal,
+          proposer: authority.publicKey,
+          asset,
+        })
+        .rpc();
+
+    before(async () => {
+      mint = await createRwaMint();
+      [approvedAsset] = PublicKey.findProgramAddressSync(
+        [Buffer.from("asset"), mint.toBuffer()],
+        program.programId
+      );
+      [approverSet] = PublicKey.findProgramAddressSync(
+        [Buffer.from("approver-set"), mint.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .registerAsset("Approved Asset", { realEstate: {} }, new anchor.BN(1000), new anchor.BN(100), "https://test.com")
+        .accounts({
+          config: configPda,
+          asset: approvedAsset,
+          mint,
+          mintConfig: findMintConfig(mint),
+          authority: authority.publicKey,
+          issuerRole: registryRole(Role.issuer),
+          systemProgram: SystemProgram.programId,
+          ...(await registerIndexAccounts()),
+        })
+        .rpc();
+    });
+
+    it("installs a 2-of-3 approver set on the asset", async () => {
+      await program.methods
+        .createAssetApproverSet(
+          [authority.publicKey, secondApprover.publicKey, thirdApprover.publicKey],
+          2
+        )
+        .accounts({
+          authority: authority.publicKey,
+          asset: approvedAsset,
+          approverSet,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const set = await program.account.approverSet.fetch(approverSet);
+      expect(set.scope.toString()).to.equal(mint.toString());
+      expect(set.approvers.length).to.equal(3);
+      expect(set.threshold).to.equal(2);
+
+      const asset = await program.account.asset.fetch(approvedAsset);
+      expect(asset.approverSet.toString()).to.equal(approverSet.toString());
+    });
+
+    it("refuses single-signature activation", async () => {
+      try {
+        await program.methods
+          .activateAsset()
+          .accounts({ asset: approvedAsset, authority: authority.publicKey })
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ApprovalRequired");
+      }
+    });
+
+    it("activates the asset once the threshold is met", async () => {
+      const proposal = await propose({ activateAsset: { mint } });
+
+      try {
+        await executeActivation(proposal);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ThresholdNotMet");
+      }
+
+      await approve(proposal, secondApprover);
+      await executeActivation(proposal);
+
+      const asset = await program.account.asset.fetch(approvedAsset);
+      expect(asset.status).to.deep.equal({ active: {} });
+      expect(await provider.connection.getAccountInfo(proposal)).to.equal(null);
+    });
+
+    it("refuses single-signature minting and mint freezes", async () => {
+      const recipient = Keypair.generate().publicKey;
+      const recipientTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        recipient,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      try {
+        await program.methods
+          .mintTokens(new anchor.BN(100), null)
+          .accounts({
+            authority: authority.publicKey,
+            role: registryRole(Role.minter),
+            mintConfig: findMintConfig(mint),
+            asset: approvedAsset,
+            mint,
+            mintAuthority: findMintAuthority(mint),
+            recipient,
+            recipientTokenAccount,
+            config: configPda,
+            feeOverride: null,
+            reserve: null,
+            feeTreasury,
+            treasuryTokenAccount: await treasuryAccount(mint),
+            lockup: null,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ApprovalRequired");
+      }
+
+      const freezeMint = (asset: PublicKey | null) =>
+        program.methods
+          .freezeMint()
+          .accounts({
+            authority: authority.publicKey,
+            role: registryRole(Role.pauser),
+            mintConfig: findMintConfig(mint),
+            asset,
+          })
+          .rpc();
+
+      try {
+        await freezeMint(approvedAsset);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ApprovalRequired");
+      }
+
+      try {
+        await freezeMint(null);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AssetAccountMissing");
+      }
+    });
+
+    it("mints through an approved proposal into the recipient's own account only", async () => {
+      await program.methods
+        .grantAssetRole({ minter: {} }, approverSet)
+        .accounts({
+          authority: authority.publicKey,
+          asset: approvedAsset,
+          roleAssignment: findRole(mint, Role.minter, approverSet),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const recipient = Keypair.generate().publicKey;
+      const [recipientTokenAccount, strangerTokenAccount] = await Promise.all(
+        [recipient, Keypair.generate().publicKey].map((owner) =>
+          createAssociatedTokenAccount(
+            provider.connection,
+            authority.payer,
+            mint,
+            owner,
+            { commitment: "confirmed" },
+            TOKEN_2022_PROGRAM_ID
+          )
+        )
+      );
+      const treasuryTokenAccount = await treasuryAccount(mint);
+
+      const proposal = await propose({
+        mintTokens: { mint, recipient, amount: new anchor.BN(400), lockup: null },
+      });
+      await approve(proposal, secondApprover);
+
+      const executeMint = (tokenAccount: PublicKey) =>
+        program.methods
+          .executeMintTokens()
+          .accounts({
+            executor: authority.publicKey,
+            approverSet,
+            proposal,
+            proposer: authority.publicKey,
+            role: findRole(mint, Role.minter, approverSet),
+            mintConfig: findMintConfig(mint),
+            asset: approvedAsset,
+            mint,
+            mintAuthority: findMintAuthority(mint),
+            recipient,
+            recipientTokenAccount: tokenAccount,
+            config: configPda,
+            feeOverride: null,
+            reserve: null,
+            feeTreasury,
+            treasuryTokenAccount,
+            lockup: null,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+      try {
+        await executeMint(strangerTokenAccount);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ConstraintTokenOwner");
+      }
+
+      await executeMint(recipientTokenAccount);
+
+      const account = await getAccount(provider.connection, recipientTokenAccount, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(Number(account.amount)).to.equal(390);
+    });
+
+    it("activates an asset assigned to the registry-wide set", async () => {
+      const globalMint = await createRwaMint();
+      const [globalAsset] = PublicKey.findProgramAddressSync(
+        [Buffer.from("asset"), globalMint.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .registerAsset("Globally Approved Asset", { realEstate: {} }, new anchor.BN(1000), new anchor.BN(100), "https://test.com")
+        .accounts({
+          config: configPda,
+          asset: globalAsset,
+          mint: globalMint,
+          mintConfig: findMintConfig(globalMint),
+          authority: authority.publicKey,
+          issuerRole: registryRole(Role.issuer),
+          systemProgram: SystemProgram.programId,
+          ...(await registerIndexAccounts()),
+        })
+        .rpc();
+
+      await program.methods
+        .createRegistryApproverSet([authority.publicKey, secondApprover.publicKey], 2)
+        .accounts({
+          authority: authority.publicKey,
+          adminRole: registryRole(Role.registryAdmin),
+          approverSet: registryApproverSet,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      await program.methods
+        .assignRegistryApproverSet()
+        .accounts({
+          authority: authority.publicKey,
+          adminRole: registryRole(Role.registryAdmin),
+          approverSet: registryApproverSet,
+          asset: globalAsset,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .activateAsset()
+          .accounts({ asset: globalAsset, authority: authority.publicKey })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ApprovalRequired");
+      }
+
+      // Another asset's set cannot act on it
+      try {
+        await propose({ activateAsset: { mint: globalMint } });
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ApproverSetMismatch");
+      }
+
+      const proposal = await propose({ activateAsset: { mint: globalMint } }, registryApproverSet);
+      await approve(proposal, secondApprover, registryApproverSet);
+      await executeActivation(proposal, registryApproverSet, globalAsset);
+
+      const asset = await program.account.asset.fetch(globalAsset);
+      expect(asset.status).to.deep.equal({ active: {} });
+    });
+
+    it("fails to approve twice from the same approver", async () => {
+      const proposal = await propose({ freezeMint: { mint } });
+
+      try {
+        await program.methods
+          .approveProposal()
+          .accounts({ approver: authority.publicKey, approverSet, proposal })
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AlreadyApproved");
+      }
+    });
+
+    it("invalidates open proposals when the approvers change", async () => {
+      const { proposalCount } = await program.account.approverSet.fetch(approverSet);
+      const openProposal = findProposal(proposalCount.toNumber() - 1);
+
+      const update = await propose({
+        updateApprovers: { approvers: [authority.publicKey, secondApprover.publicKey], threshold: 2 },
+      });
+      await approve(update, secondApprover);
+      await program.methods
+        .executeUpdateApprovers()
+        .accounts({
+          executor: authority.publicKey,
+          approverSet,
+          proposal: update,
+          proposer: authority.publicKey,
+        })
+        .rpc();
+
+      const set = await program.account.approverSet.fetch(approverSet);
+      expect(set.approvers.length).to.equal(2);
+
+      try {
+        await approve(openProposal, secondApprover);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ProposalStale");
+      }
+    });
+  });
+
+  describe("timelock", () => {
+    const guardian = Keypair.generate();
+    let hookMint: PublicKey;
+    let queuedChange: PublicKey;
+
+    before(async () => {
+      hookMint = await createRwaMint();
+      [queuedChange] = PublicKey.findProgramAddressSync(
+        [Buffer.from("timelock"), hookMint.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .grantRegistryRole({ guardian: {} } as any, guardian.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+          adminRole: registryRole(Role.registryAdmin),
+          roleAssignment: findRole(program.programId, Role.guardian, guardian.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    const queueHook = (transferHookProgram: PublicKey) =>
+      program.methods
+        .queueTransferHook(transferHookProgram)
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.complianceOfficer),
+          mintConfig: findMintConfig(hookMint),
+          asset: null,
+          queuedChange,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    it("queues a transfer hook change behind the delay", async () => {
+      const hookProgram = Keypair.generate().publicKey;
+      await queueHook(hookProgram);
+
+      const queued = await program.account.queuedChange.fetch(queuedChange);
+      expect(queued.target.toString()).to.equal(hookMint.toString());
+      expect(queued.change.transferHook.transferHookProgram.toString()).to.equal(
+        hookProgram.toString()
+      );
+      expect(queued.queuedBy.toString()).to.equal(authority.publicKey.toString());
+      expect(queued.eta.sub(queued.queuedAt).toNumber()).to.equal(2 * 24 * 60 * 60);
+
+      const mintConfig = await program.account.mintConfig.fetch(findMintConfig(hookMint));
+      expect(mintConfig.transferHookProgram.toString()).to.not.equal(hookProgram.toString());
+    });
+
+    it("refuses to apply before the ETA", async () => {
+      try {
+        await program.methods
+          .applyTransferHook()
+          .accounts({
+            queuedChange,
+            queuedBy: authority.publicKey,
+            mintConfig: findMintConfig(hookMint),
+            mint: hookMint,
+            mintAuthority: findMintAuthority(hookMint),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("TimelockNotElapsed");
+      }
+    });
+
+    it("rejects cancellation by a non-guardian", async () => {
+      try {
+        await program.methods
+          .cancelQueuedChange()
+          .accounts({
+            guardian: authority.publicKey,
+            guardianRole: registryRole(Role.complianceOfficer),
+            queuedChange,
+            queuedBy: authority.publicKey,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+      }
+    });
+
+    it("lets a guardian cancel during the delay", async () => {
+      await program.methods
+        .cancelQueuedChange()
+        .accounts({
+          guardian: guardian.publicKey,
+          guardianRole: findRole(program.programId, Role.guardian, guardian.publicKey),
+          queuedChange,
+          queuedBy: authority.publicKey,
+        })
+        .signers([guardian])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(queuedChange)).to.be.null;
+
+      // A new change can be queued once the old one is gone
+      await queueHook(Keypair.generate().publicKey);
+      expect(await provider.connection.getAccountInfo(queuedChange)).to.not.be.null;
+    });
+  });
+
+  describe("splits", () => {
+    const holder = Keypair.generate();
+    let mint: PublicKey;
+    let splitAsset: PublicKey;
+    let holderTokenAccount: PublicKey;
+    let lockup: PublicKey;
+
+    const findSplit = (index: number) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("split"), mint.toBuffer(), new anchor.BN(index).toArrayLike(Buffer, "le", 4)],
+        program.programId
+      )[0];
+
+    const declareSplit = (oldUnits: number, newUnits: number, effectiveAt: number, index: number) =>
+      program.methods
+        .declareSplit(oldUnits, newUnits, new anchor.BN(effectiveAt))
+        .accounts({
+          authority: authority.publicKey,
+          asset: splitAsset,
+          mintConfig: findMintConfig(mint),
+          split: findSplit(index),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    const findLockup = (owner: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("lockup"), mint.toBuffer(), owner.toBuffer()],
+        program.programId
+      )[0];
+
+    const findConversion = (split: PublicKey, tokenAccount: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("split-conversion"), split.toBuffer(), tokenAccount.toBuffer()],
+        program.programId
+      )[0];
+
+    const convert = (split: PublicKey, owner: PublicKey, tokenAccount: PublicKey) =>
+      program.methods
+        .convertSplitBalance()
+        .accounts({
+          payer: authority.publicKey,
+          mintConfig: findMintConfig(mint),
+          split,
+          mint,
+          permanentDelegate: findPermanentDelegate(mint),
+          mintAuthority: findMintAuthority(mint),
+          holderTokenAccount: tokenAccount,
+          lockup: findLockup(owner),
+          conversion: findConversion(split, tokenAccount),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    const setMintFrozen = (frozen: boolean) =>
+      (frozen ? program.methods.freezeMint() : program.methods.unfreezeMint())
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.pauser),
+          mintConfig: findMintConfig(mint),
+          ...(frozen ? { asset: splitAsset } : {}),
+        })
+        .rpc();
+
+    const amountOf = async (tokenAccount: PublicKey) =>
+      getAccount(provider.connection, tokenAccount, "confirmed", TOKEN_2022_PROGRAM_ID);
+
+    const completeSplit = (split: PublicKey) =>
+      program.methods
+        .completeSplit()
+        .accounts({ asset: splitAsset, mintConfig: findMintConfig(mint), split, mint })
+        .rpc();
+
+    const navPerToken = async () =>
+      (
+        await program.methods
+          .getNavPerToken()
+          .accounts({ asset: splitAsset, mintConfig: findMintConfig(mint) })
+          .view()
+      ).toNumber();
+
+    const blockTime = async () =>
+      provider.connection.getBlockTime(await provider.connection.getSlot());
+
+    before(async () => {
+      mint = await createRwaMint();
+      [splitAsset] = PublicKey.findProgramAddressSync(
+        [Buffer.from("asset"), mint.toBuffer()],
+        program.programId
+      );
+      lockup = PublicKey.findProgramAddressSync(
+        [Buffer.from("lockup"), mint.toBuffer(), holder.publicKey.toBuffer()],
+        program.programId
+      )[0];
+
+      await program.methods
+        .registerAsset("Split Asset", { realEstate: {} }, new anchor.BN(1_000_000), new anchor.BN(1000), "https://test.com")
+        .accounts({
+          config: configPda,
+          asset: splitAsset,
+          mint,
+          mintConfig: findMintConfig(mint),
+          authority: authority.publicKey,
+          issuerRole: registryRole(Role.issuer),
+          systemProgram: SystemProgram.programId,
+          ...(await registerIndexAccounts()),
+        })
+        .rpc();
+
+      await program.methods
+        .activateAsset()
+        .accounts({ asset: splitAsset, authority: authority.publicKey })
+        .rpc();
+
+      holderTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        holder.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const year = 365 * 24 * 60 * 60;
+      await program.methods
+        .mintTokens(new anchor.BN(400), {
+          cliffDuration: new anchor.BN(year),
+          vestingDuration: new anchor.BN(year),
+        })
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.minter),
+          mintConfig: findMintConfig(mint),
+          asset: splitAsset,
+          mint,
+          mintAuthority: findMintAuthority(mint),
+          recipient: holder.publicKey,
+          recipientTokenAccount: holderTokenAccount,
+          config: configPda,
+          feeOverride: null,
+          reserve: null,
+          feeTreasury,
+          treasuryTokenAccount: await treasuryAccount(mint),
+          lockup,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("rejects a ratio that changes nothing", async () => {
+      try {
+        await declareSplit(10, 10, (await blockTime()) + 60, 0);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidSplitRatio");
+      }
+    });
+
+    it("lets the authority cancel a split before it takes effect", async () => {
+      await declareSplit(1, 10, (await blockTime()) + 3600, 0);
+
+      const mintConfig = await program.account.mintConfig.fetch(findMintConfig(mint));
+      expect(mintConfig.activeSplit.toString()).to.equal(findSplit(0).toString());
+      expect(mintConfig.splitCount).to.equal(1);
+
+      await program.methods
+        .cancelSplit()
+        .accounts({
+          authority: authority.publicKey,
+          asset: splitAsset,
+          mintConfig: findMintConfig(mint),
+          split: findSplit(0),
+        })
+        .rpc();
+
+      const cleared = await program.account.mintConfig.fetch(findMintConfig(mint));
+      expect(cleared.activeSplit.toString()).to.equal(PublicKey.default.toString());
+      expect(await provider.connection.getAccountInfo(findSplit(0))).to.be.null;
+    });
+
+    it("refuses conversions before the split takes effect", async () => {
+      await declareSplit(1, 10, (await blockTime()) + 3, 1);
+
+      try {
+        await convert(findSplit(1), holder.publicKey, holderTokenAccount);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("SplitNotEffective");
+      }
+    });
+
+    it("keeps the mint frozen until every balance is converted", async () => {
+      await program.methods
+        .freezeMint()
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.pauser),
+          mintConfig: findMintConfig(mint),
+          asset: splitAsset,
+        })
+        .rpc();
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+
+      await convert(findSplit(1), holder.publicKey, holderTokenAccount);
+
+      // 2.5% of the 400 issued went to the treasury, which still holds old tokens
+      const account = await getAccount(provider.connection, holderTokenAccount, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(Number(account.amount)).to.equal(3900);
+      const record = await program.account.lockup.fetch(lockup);
+      expect(record.lots[0].amount.toNumber()).to.equal(3900);
+
+      try {
+        await completeSplit(findSplit(1));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("SplitConversionIncomplete");
+      }
+
+      try {
+        await program.methods
+          .unfreezeMint()
+          .accounts({
+            authority: authority.publicKey,
+            role: registryRole(Role.pauser),
+            mintConfig: findMintConfig(mint),
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("SplitInProgress");
+      }
+    });
+
+    it("completes the split and rescales supply and NAV per token", async () => {
+      const navBefore = await navPerToken();
+
+      await convert(findSplit(1), feeTreasury, await treasuryAccount(mint));
+      await completeSplit(findSplit(1));
+
+      const split = await program.account.split.fetch(findSplit(1));
+      expect(split.holdersConverted.toNumber()).to.equal(2);
+      expect(split.convertedOldSupply.toNumber()).to.equal(400);
+      expect(split.convertedNewSupply.toNumber()).to.equal(4000);
+      expect(split.completedAt).to.not.be.null;
+
+      const asset = await program.account.asset.fetch(splitAsset);
+      expect(asset.totalSupply.toNumber()).to.equal(10_000);
+      expect(await navPerToken()).to.equal(navBefore / 10);
+
+      const mintConfig = await program.account.mintConfig.fetch(findMintConfig(mint));
+      expect(mintConfig.activeSplit.toString()).to.equal(PublicKey.default.toString());
+      expect(mintConfig.mintedSupply.sub(mintConfig.burnedSupply).toNumber()).to.equal(4000);
+    });
+
+    it("rejects conversions once the split has completed", async () => {
+      try {
+        await convert(findSplit(1), holder.publicKey, holderTokenAccount);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("SplitNotActive");
+      }
+    });
+
+    it("lets the authority abort an effective split nobody has converted under", async () => {
+      await declareSplit(1, 2, (await blockTime()) + 2, 2);
+      await new Promise((resolve) => setTimeout(resolve, 3000));
+
+      await program.methods
+        .cancelSplit()
+        .accounts({
+          authority: authority.publicKey,
+          asset: splitAsset,
+          mintConfig: findMintConfig(mint),
+          split: findSplit(2),
+        })
+        .rpc();
+
+      const mintConfig = await program.account.mintConfig.fetch(findMintConfig(mint));
+      expect(mintConfig.activeSplit.toString()).to.equal(PublicKey.default.toString());
+    });
+
+    describe("reverse split with frozen, secondary and unconverted accounts", () => {
+      const secondaryAccount = Keypair.generate();
+      let treasuryTokenAccount: PublicKey;
+
+      before(async () => {
+        treasuryTokenAccount = await treasuryAccount(mint);
+        await setMintFrozen(false);
+
+        // A second, non-associated account for the same holder
+        await createAccount(
+          provider.connection,
+          authority.payer,
+          mint,
+          holder.publicKey,
+          secondaryAccount,
+          { commitment: "confirmed" },
+          TOKEN_2022_PROGRAM_ID
+        );
+        await program.methods
+          .mintTokens(new anchor.BN(100), null)
+          .accounts({
+            authority: authority.publicKey,
+            role: registryRole(Role.minter),
+            mintConfig: findMintConfig(mint),
+            asset: splitAsset,
+            mint,
+            mintAuthority: findMintAuthority(mint),
+            recipient: holder.publicKey,
+            recipientTokenAccount: secondaryAccount.publicKey,
+            config: configPda,
+            feeOverride: null,
+            reserve: null,
+            feeTreasury,
+            treasuryTokenAccount,
+            lockup: null,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        await program.methods
+          .freezeHolder({ legalHold: {} }, "CASE-SPLIT-3")
+          .accounts({
+            authority: authority.publicKey,
+            role: registryRole(Role.complianceOfficer),
+            mint,
+            mintAuthority: findMintAuthority(mint),
+            holderTokenAccount,
+            holderFreeze: PublicKey.findProgramAddressSync(
+              [Buffer.from("holder-freeze"), mint.toBuffer(), holderTokenAccount.toBuffer()],
+              program.programId
+            )[0],
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        await declareSplit(3, 1, (await blockTime()) + 2, 3);
+        await setMintFrozen(true);
+        await new Promise((resolve) => setTimeout(resolve, 3000));
+      });
+
+      it("converts a frozen account and leaves it frozen", async () => {
+        await convert(findSplit(3), holder.publicKey, holderTokenAccount);
+
+        const account = await amountOf(holderTokenAccount);
+        expect(Number(account.amount)).to.equal(1300);
+        expect(account.isFrozen).to.be.true;
+        const record = await program.account.lockup.fetch(lockup);
+        expect(record.lots[0].amount.toNumber()).to.equal(1300);
+      });
+
+      it("converts a non-associated account without rescaling the lockup twice", async () => {
+        await convert(findSplit(3), holder.publicKey, secondaryAccount.publicKey);
+
+        // 98 of the 100 minted reached the holder; 98 old tokens back only 32 new ones
+        expect(Number((await amountOf(secondaryAccount.publicKey)).amount)).to.equal(32);
+        const record = await program.account.lockup.fetch(lockup);
+        expect(record.lots[0].amount.toNumber()).to.equal(1300);
+
+        const split = await program.account.split.fetch(findSplit(3));
+        expect(split.destroyedRemainder.toNumber()).to.equal(2);
+      });
+
+      it("refuses to abort once balances have converted", async () => {
+        try {
+          await program.methods
+            .cancelSplit()
+            .accounts({
+              authority: authority.publicKey,
+              asset: splitAsset,
+              mintConfig: findMintConfig(mint),
+              split: findSplit(3),
+            })
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("SplitConversionStarted");
+        }
+      });
+
+      it("refuses to start retirement while the split is being converted", async () => {
+        try {
+          await program.methods
+            .beginRetirement()
+            .accounts({ asset: splitAsset, mintConfig: findMintConfig(mint), authority: authority.publicKey })
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("SplitInProgress");
+        }
+      });
+
+      it("finalizes with an unconverted account left pending", async () => {
+        const receipt = findConversion(findSplit(3), treasuryTokenAccount);
+        await program.methods
+          .finalizeSplit()
+          .accounts({
+            authority: authority.publicKey,
+            asset: splitAsset,
+            mintConfig: findMintConfig(mint),
+            split: findSplit(3),
+            mint,
+            mintAuthority: findMintAuthority(mint),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            { pubkey: treasuryTokenAccount, isSigner: false, isWritable: true },
+            { pubkey: receipt, isSigner: false, isWritable: true },
+            { pubkey: findLockup(feeTreasury), isSigner: false, isWritable: true },
+          ])
+          .rpc();
+
+        const split = await program.account.split.fetch(findSplit(3));
+        expect(split.completedAt).to.not.be.null;
+        const asset = await program.account.asset.fetch(splitAsset);
+        expect(asset.totalSupply.toNumber()).to.equal(3333);
+        const mintConfig = await program.account.mintConfig.fetch(findMintConfig(mint));
+        expect(mintConfig.activeSplit.toString()).to.equal(PublicKey.default.toString());
+        expect(mintConfig.pendingSplitConversions).to.equal(1);
+
+        const conversion = await program.account.splitConversion.fetch(receipt);
+        expect(conversion.pending).to.be.true;
+        expect(conversion.oldAmount.toNumber()).to.equal(102);
+        expect((await amountOf(treasuryTokenAccount)).isFrozen).to.be.true;
+      });
+
+      it("refuses to thaw an account whose balance is pending conversion", async () => {
+        try {
+          await program.methods
+            .thawKycAccount()
+            .accounts({
+              authority: authority.publicKey,
+              kycRole: registryRole(Role.kycOperator),
+              mint,
+              mintAuthority: findMintAuthority(mint),
+              holderTokenAccount: treasuryTokenAccount,
+              whitelistEntry: PublicKey.findProgramAddressSync(
+                [Buffer.from("whitelist"), feeTreasury.toBuffer()],
+                COMPLIANCE_PROGRAM_ID
+              )[0],
+              holderFreeze: PublicKey.findProgramAddressSync(
+                [Buffer.from("holder-freeze"), mint.toBuffer(), treasuryTokenAccount.toBuffer()],
+                program.programId
+              )[0],
+              ...(await splitGuardAccounts(mint, treasuryTokenAccount)),
+              tokenProgram: TOKEN_2022_PROGRAM_ID,
+            })
+            .rpc();
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("SplitBalancePending");
+        }
+        expect((await amountOf(treasuryTokenAccount)).isFrozen).to.be.true;
+      });
+
+      it("holds off the next split until pending balances convert", async () => {
+        try {
+          await declareSplit(1, 2, (await blockTime()) + 60, 4);
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.message).to.include("SplitConversionsPending");
+        }
+
+        await program.methods
+          .convertPendingSplitBalance()
+          .accounts({
+            mintConfig: findMintConfig(mint),
+            split: findSplit(3),
+            mint,
+            permanentDelegate: findPermanentDelegate(mint),
+            mintAuthority: findMintAuthority(mint),
+            holderTokenAccount: treasuryTokenAccount,
+            conversion: findConversion(findSplit(3), treasuryTokenAccount),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+
+        const account = await amountOf(treasuryTokenAccount);
+        expect(Number(account.amount)).to.equal(34);
+        expect(account.isFrozen).to.be.false;
+        const mintConfig = await program.account.mintConfig.fetch(findMintConfig(mint));
+        expect(mintConfig.pendingSplitConversions).to.equal(0);
+        const split = await program.account.split.fetch(findSplit(3));
+        expect(split.holdersConverted.toNumber()).to.equal(3);
+        expect(split.convertedNewSupply.toNumber()).to.equal(1366);
+      });
+    });
+  });
+
+  describe("reserves", () => {
+    const custodian = Keypair.generate();
+    const reportHash = Buffer.alloc(32, 7);
+    const holder = Keypair.generate();
+    let mint: PublicKey;
+    let reserveAsset: PublicKey;
+    let holderTokenAccount: PublicKey;
+    let custodianRecord: PublicKey;
+    let reserve: PublicKey;
+
+    const now = async () =>
+      new anchor.BN(await provider.connection.getBlockTime(await provider.connection.getSlot()));
+
+    // Message the custodian signs: "reserve" || asset || amount || timestamp || report hash
+    const attest = (amount: anchor.BN, timestamp: anchor.BN) =>
+      program.methods
+        .attestReserve(amount, timestamp, Array.from(reportHash))
+        .accounts({
+          payer: authority.publicKey,
+          asset: reserveAsset,
+          mintConfig: findMintConfig(mint),
+          reserve,
+          custodianRecord,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions([
+          Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: custodian.secretKey,
+            message: Buffer.concat([
+              Buffer.from("reserve"),
+              reserveAsset.toBuffer(),
+              amount.toArrayLike(Buffer, "le", 8),
+              timestamp.toArrayLike(Buffer, "le", 8),
+              reportHash,
+            ]),
+          }),
+        ])
+        .rpc({ commitment: "confirmed" });
+
+    const mintTokens = async (amount: number, reserveAccount: PublicKey | null) =>
+      program.methods
+        .mintTokens(new anchor.BN(amount), null)
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(Role.minter),
+          mintConfig: findMintConfig(mint),
+          asset: reserveAsset,
+          mint,
+          mintAuthority: findMintAuthority(mint),
+          recipient: holder.publicKey,
+          recipientTokenAccount: holderTokenAccount,
+          config: configPda,
+          feeOverride: null,
+          reserve: reserveAccount,
+          feeTreasury,
+          treasuryTokenAccount: await treasuryAccount(mint),
+          lockup: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    before(async () => {
+      mint = await createRwaMint();
+      [reserveAsset] = PublicKey.findProgramAddressSync(
+        [Buffer.from("asset"), mint.toBuffer()],
+        program.programId
+      );
+      [custodianRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("custodian"), custodian.publicKey.toBuffer()],
+        program.programId
+      );
+      [reserve] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reserve"), mint.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .registerAsset("Custodied Asset", { realEstate: {} }, new anchor.BN(1_000_000), new anchor.BN(1000), "https://test.com")
+        .accounts({
+          config: configPda,
+          asset: reserveAsset,
+          mint,
+          mintConfig: findMintConfig(mint),
+          authority: authority.publicKey,
+          issuerRole: registryRole(Role.issuer),
+          systemProgram: SystemProgram.programId,
+          ...(await registerIndexAccounts()),
+        })
+        .rpc();
+
+      await program.methods
+        .activateAsset()
+        .accounts({ asset: reserveAsset, authority: authority.publicKey })
+        .rpc();
+
+      holderTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        holder.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      await program.methods
+        .registerCustodian(custodian.publicKey, "Anchorage Digital")
+        .accounts({
+          authority: authority.publicKey,
+          adminRole: registryRole(Role.registryAdmin),
+          custodianRecord,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("assigns a custodian and requires reserves from then on", async () => {
+      await program.methods
+        .assignCustodian()
+        .accounts({
+          authority: authority.publicKey,
+          asset: reserveAsset,
+          mintConfig: findMintConfig(mint),
+          custodianRecord,
+          reserve,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const mintConfig = await program.account.mintConfig.fetch(findMintConfig(mint));
+      expect(mintConfig.requiresReserve).to.equal(true);
+
+      try {
+        await mintTokens(100, null);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("ReserveAccountMissing");
+      }
+
+      try {
+        await mintTokens(100, reserve);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("StaleReserveAttestation");
+      }
+    });
+
+    it("mints up to the attested reserve", async () => {
+      await attest(new anchor.BN(500), await now());
+
+      const record = await program.account.reserve.fetch(reserve);
+      expect(record.reserveAmount.toNumber()).to.equal(500);
+      expect(record.attestationCount.toNumber()).to.equal(1);
+
+      await mintTokens(400, reserve);
+
+      try {
+        await mintTokens(200, reserve);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InsufficientReserves");
+      }
+    });
+
+    it("signals when reserve coverage drops", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 1500));
+      const signature = await attest(new anchor.BN(300), await now());
+
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      const events = [...parser.parseLogs(tx.meta.logMessages)];
+      const dropped = events.find((event) => event.name === "ReserveCoverageDropped");
+      expect(dropped).to.not.be.undefined;
+      expect(dropped.data.previousReserve.toNumber()).to.equal(500);
+      expect(dropped.data.outstandingSupply.toNumber()).to.equal(400);
+    });
+
+    it("rejects an attestation not signed by the custodian", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 1500));
+
+      try {
+        await program.methods
+          .attestReserve(new anchor.BN(1000), await now(), Array.from(reportHash))
+          .accounts({
+            payer: authority.publicKey,
+            asset: reserveAsset,
+            mintConfig: findMintConfig(mint),
+            reserve,
+            custodianRecord,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .preInstructions([
+            Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: Keypair.generate().secretKey,
+              message: Buffer.alloc(87),
+            }),
+          ])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidSignatureVerification");
+      }
+    });
+  });
+
+  describe("migrations", () => {
+    it("writes the current layout version on creation", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const asset = await program.account.asset.fetch(assetPda);
+      const mintConfig = await program.account.mintConfig.fetch(findMintConfig(assetMint));
+
+      for (const account of [config, asset, mintConfig]) {
+        expect(account.version).to.equal(6);
+        expect(account.reserved.every((byte: number) => byte === 0)).to.equal(true);
+      }
+    });
+
+    // Written by the pre-versioning program; see tests/fixtures
+    const legacyAsset = new PublicKey("AdfCHYNqGxKJB2yyewph2pXk1PrHQeFs9SZf1XLKr7GJ");
+    const legacyMintConfig = new PublicKey("94rwfyMAmW3apSdDGyY4tYFfDikA435hpVwKEhHqT54r");
+
+    it("migrates an asset written before versioning", async () => {
+      const before = await provider.connection.getAccountInfo(legacyAsset);
+      expect(before.data.length).to.equal(435);
+
+      await program.methods
+        .migrateAsset()
+        .accounts({
+          asset: legacyAsset,
+          payer: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const after = await provider.connection.getAccountInfo(legacyAsset);
+      expect(after.data.length).to.be.greaterThan(before.data.length);
+      expect(after.lamports).to.equal(
+        await provider.connection.getMinimumBalanceForRentExemption(after.data.length)
+      );
+
+      const asset = await program.account.asset.fetch(legacyAsset);
+      expect(asset.version).to.equal(6);
+      expect(asset.authority.toString()).to.equal("6ZSf2f4vVKhvuVkiZumSiXKncLHepFDqmH8YLLQqSGe3");
+      expect(asset.issuer.toString()).to.equal(asset.authority.toString());
+      expect(asset.mint.toString()).to.equal("FSiKFra4RB3MihBgWjt9Qu1KVnQ2biSm25kJSF48Z55D");
+      expect(asset.name).to.equal("Legacy Tower");
+      expect(asset.assetType).to.deep.equal({ realEstate: {} });
+      expect(asset.totalValue.toNumber()).to.equal(500_000_000);
+      expect(asset.totalSupply.toNumber()).to.equal(1_000_000);
+      expect(asset.metadataUri).to.equal("https://legacy.example/asset.json");
+      expect(asset.status).to.deep.equal({ active: {} });
+      expect(asset.createdAt.toNumber()).to.equal(1_700_000_000);
+      expect(asset.updatedAt.toNumber()).to.equal(1_700_086_400);
+      expect(asset.statusChangedAt.toNumber()).to.equal(1_700_086_400);
+      expect(asset.statusReason).to.equal(null);
+      expect(asset.bump).to.equal(254);
+      expect(asset.approverSet.toString()).to.equal(PublicKey.default.toString());
+      expect(asset.reserved.every((byte: number) => byte === 0)).to.equal(true);
+    });
+
+    it("migrates a mint configuration written before versioning", async () => {
+      await program.methods
+        .migrateMintConfig()
+        .accounts({
+          mintConfig: legacyMintConfig,
+          payer: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const mintConfig = await program.account.mintConfig.fetch(legacyMintConfig);
+      expect(mintConfig.version).to.equal(6);
+      expect(mintConfig.mint.toString()).to.equal("FSiKFra4RB3MihBgWjt9Qu1KVnQ2biSm25kJSF48Z55D");
+      expect(mintConfig.transferHookProgram).to.equal(null);
+      expect(mintConfig.name).to.equal("Legacy Tower");
+      expect(mintConfig.symbol).to.equal("LGT");
+      expect(mintConfig.uri).to.equal("https://legacy.example/mint.json");
+      expect(mintConfig.decimals).to.equal(6);
+      expect(mintConfig.isFrozen).to.equal(false);
+      expect(mintConfig.createdAt.toNumber()).to.equal(1_700_000_000);
+      expect(mintConfig.bump).to.equal(253);
+      expect(mintConfig.asset.toString()).to.equal(PublicKey.default.toString());
+      expect(mintConfig.mintedSupply.toNumber()).to.equal(0);
+      expect(mintConfig.activeSplit.toString()).to.equal(PublicKey.default.toString());
+    });
+
+    it("fails to migrate an account already at the current version", async () => {
+      try {
+        await program.methods
+          .migrateAsset()
+          .accounts({
+            asset: assetPda,
+            payer: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AccountAlreadyMigrated");
+      }
+    });
+
+    it("fails to migrate an account of a different type", async () => {
+      try {
+        await program.methods
+          .migrateAsset()
+          .accounts({
+            asset: findMintConfig(assetMint),
+            payer: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AccountDiscriminatorMismatch");
+      }
     });
   });
 });
@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Auction } from "../target/types/auction";
+import { AssetRegistry } from "../target/types/asset_registry";
 import { 
   Keypair, 
   PublicKey, 
@@ -14,6 +15,7 @@ import {
   createAssociatedTokenAccount,
   mintTo,
   getAccount,
+  getOrCreateAssociatedTokenAccount,
   ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { expect } from "chai";
@@ -23,6 +25,10 @@ describe("auction", () => {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.Auction as Program<Auction>;
+  const registry = anchor.workspace.AssetRegistry as Program<AssetRegistry>;
+  const registryProgramId = new PublicKey("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+  const findAsset = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("asset"), mint.toBuffer()], registryProgramId)[0];
   
   const seller = Keypair.generate();
   const bidder1 = Keypair.generate();
@@ -188,6 +194,7 @@ describe("auction", () => {
           seller: seller.publicKey,
           auction: auctionPda,
           assetMint: assetMint,
+          asset: findAsset(assetMint),
           paymentMint: paymentMint,
           sellerAssetAccount: sellerAssetAccount,
           auctionAssetVault: auctionAssetVault,
@@ -260,6 +267,7 @@ describe("auction", () => {
             seller: seller.publicKey,
             auction: badAuctionPda,
             assetMint: newAssetMint,
+            asset: findAsset(newAssetMint),
             paymentMint: paymentMint,
             sellerAssetAccount: sellerAssetAccount,
             auctionAssetVault: await getAssociatedTokenAddress(newAssetMint, badAuctionPda, true, TOKEN_2022_PROGRAM_ID),
@@ -316,6 +324,7 @@ describe("auction", () => {
             seller: seller.publicKey,
             auction: badAuctionPda,
             assetMint: newAssetMint,
+            asset: findAsset(newAssetMint),
             paymentMint: paymentMint,
             sellerAssetAccount: sellerAssetAccount,
             auctionAssetVault: await getAssociatedTokenAddress(newAssetMint, badAuctionPda, true, TOKEN_2022_PROGRAM_ID),
@@ -352,6 +361,7 @@ describe("auction", () => {
           bidder: bidder1.publicKey,
           auction: auctionPda,
           bid: bid1Pda,
+          asset: findAsset(assetMint),
           paymentMint: paymentMint,
           bidderPaymentAccount: bidder1PaymentAccount,
           auctionPaymentVault: auctionPaymentVault,
@@ -388,6 +398,7 @@ describe("auction", () => {
             bidder: bidder2.publicKey,
             auction: auctionPda,
             bid: bid2Pda,
+            asset: findAsset(assetMint),
             paymentMint: paymentMint,
             bidderPaymentAccount: bidder2PaymentAccount,
             auctionPaymentVault: auctionPaymentVault,
@@ -426,6 +437,7 @@ describe("auction", () => {
           bidder: bidder2.publicKey,
           auction: auctionPda,
           bid: bid2Pda,
+          asset: findAsset(assetMint),
           paymentMint: paymentMint,
           bidderPaymentAccount: bidder2PaymentAccount,
           auctionPaymentVault: auctionPaymentVault,
@@ -572,6 +584,7 @@ describe("auction", () => {
           seller: seller.publicKey,
           auction: cancelAuctionPda,
           assetMint: cancelAssetMint,
+          asset: findAsset(cancelAssetMint),
           paymentMint: paymentMint,
           sellerAssetAccount: cancelSellerAssetAccount,
           auctionAssetVault: cancelAuctionAssetVault,
@@ -620,4 +633,116 @@ describe("auction", () => {
       );
     });
   });
+
+  describe("settle_auction", () => {
+    // Auctions run for at least an hour, so an already-ended auction with a
+    // winning bid of 6 USDC is preloaded from tests/fixtures
+    const endedAuction = new PublicKey("B4fYtu7zUZ5temW9mRx7aTHg4QZ3WsGESpm2weQkFS4z");
+    const endedSeller = new PublicKey("HuVxHGFZor3YbpUPZnV2RvdeqX3xLMjXwGfaFbg4ajkw");
+    const endedWinner = new PublicKey("FEjuAjPUF7Du8jxXN4HZY5CHrhstZM14P42eeFUBW6g4");
+    const endedAssetMint = new PublicKey("6VmgaDy5TYS7eS1EeZhwJpDLrXtTbzYsHNqqzH4K9Py7");
+    const endedPaymentMint = new PublicKey("6hz72ywNegzfVksYfurkDdroDE4YTErNbEgRm9oFK8vK");
+    const winningBid = 6_000_000;
+
+    let winnerAssetAccount: PublicKey;
+    let sellerPaymentAccount: PublicKey;
+    let treasuryPaymentAccount: PublicKey;
+
+    const [registryConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      registryProgramId
+    );
+    const [feeTreasury] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee-treasury")],
+      registryProgramId
+    );
+
+    const tokenAccount = async (mint: PublicKey, owner: PublicKey) =>
+      (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          seller,
+          mint,
+          owner,
+          true,
+          "confirmed",
+          { commitment: "confirmed" },
+          TOKEN_2022_PROGRAM_ID
+        )
+      ).address;
+
+    before(async () => {
+      winnerAssetAccount = await tokenAccount(endedAssetMint, endedWinner);
+      sellerPaymentAccount = await tokenAccount(endedPaymentMint, endedSeller);
+      treasuryPaymentAccount = await tokenAccount(endedPaymentMint, feeTreasury);
+    });
+
+    it("pays the seller net of the platform fee and the treasury the fee", async () => {
+      const auctionAssetVault = await getAssociatedTokenAddress(
+        endedAssetMint,
+        endedAuction,
+        true,
+        TOKEN_2022_PROGRAM_ID
+      );
+      const auctionPaymentVault = await getAssociatedTokenAddress(
+        endedPaymentMint,
+        endedAuction,
+        true,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      await program.methods
+        .settleAuction()
+        .accounts({
+          settler: provider.wallet.publicKey,
+          auction: endedAuction,
+          assetMint: endedAssetMint,
+          paymentMint: endedPaymentMint,
+          auctionAssetVault,
+          auctionPaymentVault,
+          winnerAssetAccount,
+          winnerPaymentAccount: winnerAssetAccount, // Unused when the reserve is met
+          sellerAssetAccount: winnerAssetAccount, // Unused when the reserve is met
+          sellerPaymentAccount,
+          config: registryConfig,
+          feeOverride: null,
+          feeTreasury,
+          treasuryPaymentAccount,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      const auction = await program.account.auction.fetch(endedAuction);
+      expect(auction.status).to.deep.equal({ settled: {} });
+
+      // Check the winner received the assets
+      const winnerAssets = await getAccount(
+        provider.connection,
+        winnerAssetAccount,
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(Number(winnerAssets.amount)).to.equal(assetAmount.toNumber());
+
+      // Check seller received the winning bid net of the registry platform fee
+      const config = await registry.account.config.fetch(registryConfig);
+      const fee = winningBid * config.platformFeeBps / 10_000;
+      const sellerPayment = await getAccount(
+        provider.connection,
+        sellerPaymentAccount,
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(Number(sellerPayment.amount)).to.equal(winningBid - fee);
+
+      // Check the fee reached the treasury
+      const treasuryPayment = await getAccount(
+        provider.connection,
+        treasuryPaymentAccount,
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(Number(treasuryPayment.amount)).to.equal(fee);
+    });
+  });
 });
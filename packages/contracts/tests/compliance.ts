@@ -1,12 +1,21 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Compliance } from "../target/types/compliance";
+import { AssetRegistry } from "../target/types/asset_registry";
 import { 
   Keypair, 
   PublicKey, 
   SystemProgram,
   LAMPORTS_PER_SOL 
 } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  createAssociatedTokenAccount,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  transferCheckedWithTransferHook,
+} from "@solana/spl-token";
 import { expect } from "chai";
 
 describe("compliance", () => {
@@ -14,6 +23,7 @@ describe("compliance", () => {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.Compliance as Program<Compliance>;
+  const registry = anchor.workspace.AssetRegistry as Program<AssetRegistry>;
   
   const authority = provider.wallet as anchor.Wallet;
   let configPda: PublicKey;
@@ -23,6 +33,58 @@ describe("compliance", () => {
   const maxTransferAmount = new anchor.BN(1_000_000_000); // 1000 USDC
   const transferCooldown = new anchor.BN(3600); // 1 hour
 
+  const registryPda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, registry.programId)[0];
+
+  // Accounts the registry's thaw paths check for a balance awaiting split conversion
+  const splitGuardAccounts = async (mint: PublicKey, tokenAccount: PublicKey) => {
+    const mintConfig = registryPda(Buffer.from("mint-config"), mint.toBuffer());
+    const { splitCount } = await registry.account.mintConfig.fetch(mintConfig);
+    const lastSplit = registryPda(
+      Buffer.from("split"),
+      mint.toBuffer(),
+      new anchor.BN(Math.max(splitCount - 1, 0)).toArrayLike(Buffer, "le", 4)
+    );
+    return {
+      mintConfig,
+      lastSplit,
+      splitConversion: registryPda(Buffer.from("split-conversion"), lastSplit.toBuffer(), tokenAccount.toBuffer()),
+    };
+  };
+
+  // Registry index accounts the wallet's next real estate registration lands in
+  const registerIndexAccounts = async () => {
+    const findIndex = (...scope: Buffer[]) =>
+      PublicKey.findProgramAddressSync([Buffer.from("asset-index"), ...scope], registry.programId)[0];
+    const nextPage = async (index: PublicKey) => {
+      const count = (await registry.account.assetIndex.fetchNullable(index))?.count.toNumber() ?? 0;
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("asset-index-page"), index.toBuffer(), new anchor.BN(Math.floor(count / 32)).toArrayLike(Buffer, "le", 8)],
+        registry.programId
+      )[0];
+    };
+    const globalIndex = findIndex();
+    const issuerIndex = findIndex(Buffer.from("issuer"), provider.wallet.publicKey.toBuffer());
+    const typeIndex = findIndex(Buffer.from("type"), Buffer.from([0]));
+
+    return {
+      globalIndex,
+      globalIndexPage: await nextPage(globalIndex),
+      issuerIndex,
+      issuerIndexPage: await nextPage(issuerIndex),
+      typeIndex,
+      typeIndexPage: await nextPage(typeIndex),
+    };
+  };
+
+  const registryRole = (role: number) =>
+    registryPda(
+      Buffer.from("role"),
+      registry.programId.toBuffer(),
+      Buffer.from([role]),
+      authority.publicKey.toBuffer()
+    );
+
   before(async () => {
     [configPda, configBump] = PublicKey.findProgramAddressSync(
       [Buffer.from("compliance-config")],
@@ -281,40 +343,96 @@ describe("compliance", () => {
   });
 
   describe("config updates", () => {
-    it("updates max transfer amount", async () => {
+    const guardian = Keypair.generate();
+    let queuedUpdate: PublicKey;
+
+    before(async () => {
+      [queuedUpdate] = PublicKey.findProgramAddressSync(
+        [Buffer.from("timelock"), configPda.toBuffer()],
+        program.programId
+      );
+
+      await registry.methods
+        .grantRegistryRole({ guardian: {} } as any, guardian.publicKey)
+        .accounts({
+          authority: authority.publicKey,
+          adminRole: registryRole(0),
+          roleAssignment: registryPda(
+            Buffer.from("role"),
+            registry.programId.toBuffer(),
+            Buffer.from([7]),
+            guardian.publicKey.toBuffer()
+          ),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("queues transfer limit changes behind the delay", async () => {
       const newMaxAmount = new anchor.BN(2_000_000_000); // 2000 USDC
+      const newCooldown = new anchor.BN(7200); // 2 hours
 
       await program.methods
-        .updateConfig(newMaxAmount, null, null)
+        .queueConfigUpdate(newMaxAmount, newCooldown)
         .accounts({
           authority: authority.publicKey,
           config: configPda,
+          queuedUpdate,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
+      const queued = await program.account.queuedConfigUpdate.fetch(queuedUpdate);
+      expect(queued.maxTransferAmount.toNumber()).to.equal(newMaxAmount.toNumber());
+      expect(queued.transferCooldown.toNumber()).to.equal(newCooldown.toNumber());
+      expect(queued.eta.sub(queued.queuedAt).toNumber()).to.equal(2 * 24 * 60 * 60);
+
       const config = await program.account.complianceConfig.fetch(configPda);
-      expect(config.maxTransferAmount.toNumber()).to.equal(newMaxAmount.toNumber());
+      expect(config.maxTransferAmount.toNumber()).to.equal(maxTransferAmount.toNumber());
+      expect(config.transferCooldown.toNumber()).to.equal(transferCooldown.toNumber());
     });
 
-    it("updates transfer cooldown", async () => {
-      const newCooldown = new anchor.BN(7200); // 2 hours
+    it("refuses to apply before the ETA", async () => {
+      try {
+        await program.methods
+          .applyConfigUpdate()
+          .accounts({
+            config: configPda,
+            queuedUpdate,
+            queuedBy: authority.publicKey,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("TimelockNotElapsed");
+      }
+    });
 
+    it("lets a guardian cancel during the delay", async () => {
       await program.methods
-        .updateConfig(null, newCooldown, null)
+        .cancelConfigUpdate()
         .accounts({
-          authority: authority.publicKey,
+          guardian: guardian.publicKey,
+          guardianRole: registryPda(
+            Buffer.from("role"),
+            registry.programId.toBuffer(),
+            Buffer.from([7]),
+            guardian.publicKey.toBuffer()
+          ),
           config: configPda,
+          queuedUpdate,
+          queuedBy: authority.publicKey,
         })
+        .signers([guardian])
         .rpc();
 
-      const config = await program.account.complianceConfig.fetch(configPda);
-      expect(config.transferCooldown.toNumber()).to.equal(newCooldown.toNumber());
+      expect(await provider.connection.getAccountInfo(queuedUpdate)).to.be.null;
     });
 
     it("pauses and resumes transfers", async () => {
       // Pause
       await program.methods
-        .updateConfig(null, null, true)
+        .updateConfig(true)
         .accounts({
           authority: authority.publicKey,
           config: configPda,
@@ -326,7 +444,7 @@ describe("compliance", () => {
 
       // Resume
       await program.methods
-        .updateConfig(null, null, false)
+        .updateConfig(false)
         .accounts({
           authority: authority.publicKey,
           config: configPda,
@@ -343,8 +461,98 @@ describe("compliance", () => {
     const validReceiver = Keypair.generate().publicKey;
     let senderWhitelistPda: PublicKey;
     let receiverWhitelistPda: PublicKey;
+    let mint: PublicKey;
+    let assetPda: PublicKey;
+    let mintConfigPda: PublicKey;
+    let senderTokenAccount: PublicKey;
+    let receiverTokenAccount: PublicKey;
+
+    const transferHook = (amount: anchor.BN) =>
+      program.methods.transferHook(amount).accounts({
+        sourceTokenAccount: senderTokenAccount,
+        mint,
+        destinationTokenAccount: receiverTokenAccount,
+        authority: validSender,
+        extraAccountMetaList: PublicKey.findProgramAddressSync(
+          [Buffer.from("extra-account-metas"), mint.toBuffer()],
+          program.programId
+        )[0],
+        config: configPda,
+        registryProgram: registry.programId,
+        asset: assetPda,
+        mintConfig: mintConfigPda,
+        senderLockup: registryPda(Buffer.from("lockup"), mint.toBuffer(), validSender.toBuffer()),
+        senderWhitelist: senderWhitelistPda,
+        receiverWhitelist: receiverWhitelistPda,
+        senderBlacklist: PublicKey.findProgramAddressSync(
+          [Buffer.from("blacklist"), validSender.toBuffer()],
+          program.programId
+        )[0],
+        receiverBlacklist: PublicKey.findProgramAddressSync(
+          [Buffer.from("blacklist"), validReceiver.toBuffer()],
+          program.programId
+        )[0],
+        // Both holders are whitelisted in the US
+        jurisdictionRule: PublicKey.findProgramAddressSync(
+          [Buffer.from("jurisdiction"), Buffer.from("US"), Buffer.from("US")],
+          program.programId
+        )[0],
+      });
 
     before(async () => {
+      // Registry asset backing the hooked mint; the wallet's registry roles
+      // are granted by the asset-registry suite
+      const mintKeypair = Keypair.generate();
+      mint = mintKeypair.publicKey;
+      assetPda = registryPda(Buffer.from("asset"), mint.toBuffer());
+      mintConfigPda = registryPda(Buffer.from("mint-config"), mint.toBuffer());
+
+      await registry.methods
+        .createTokenMint("Hook Token", "HOOK", "https://test.com", 6, program.programId, false)
+        .accounts({
+          authority: authority.publicKey,
+          permanentDelegate: registryPda(Buffer.from("permanent-delegate"), mint.toBuffer()),
+          mint,
+          mintConfig: mintConfigPda,
+          mintAuthority: registryPda(Buffer.from("mint-authority"), mint.toBuffer()),
+          issuerRole: registryRole(1),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([mintKeypair])
+        .rpc();
+
+      await registry.methods
+        .registerAsset("Hook Asset", { realEstate: {} }, new anchor.BN(1000), new anchor.BN(1000), "https://test.com")
+        .accounts({
+          config: registryPda(Buffer.from("config")),
+          asset: assetPda,
+          mint,
+          mintConfig: mintConfigPda,
+          authority: authority.publicKey,
+          issuerRole: registryRole(1),
+          systemProgram: SystemProgram.programId,
+          ...(await registerIndexAccounts()),
+        })
+        .rpc();
+
+      senderTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        validSender,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+      receiverTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        validReceiver,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+
       [senderWhitelistPda] = PublicKey.findProgramAddressSync(
         [Buffer.from("whitelist"), validSender.toBuffer()],
         program.programId
@@ -379,22 +587,24 @@ describe("compliance", () => {
         .rpc();
     });
 
+    it("fails while the registry asset is pending", async () => {
+      try {
+        await transferHook(new anchor.BN(100_000_000)).rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AssetNotTradeable");
+      }
+
+      await registry.methods
+        .activateAsset()
+        .accounts({ asset: assetPda, authority: authority.publicKey })
+        .rpc();
+    });
+
     it("validates a compliant transfer", async () => {
       const transferAmount = new anchor.BN(100_000_000); // 100 USDC
 
-      await program.methods
-        .transferHook(transferAmount)
-        .accounts({
-          config: configPda,
-          sender: validSender,
-          receiver: validReceiver,
-          senderWhitelist: senderWhitelistPda,
-          receiverWhitelist: receiverWhitelistPda,
-          senderBlacklist: null,
-          receiverBlacklist: null,
-          jurisdictionRule: null,
-        })
-        .rpc();
+      await transferHook(transferAmount).rpc();
 
       // If we get here without error, the transfer is validated
     });
@@ -402,7 +612,7 @@ describe("compliance", () => {
     it("fails when transfers are paused", async () => {
       // Pause transfers
       await program.methods
-        .updateConfig(null, null, true)
+        .updateConfig(true)
         .accounts({
           authority: authority.publicKey,
           config: configPda,
@@ -410,19 +620,7 @@ describe("compliance", () => {
         .rpc();
 
       try {
-        await program.methods
-          .transferHook(new anchor.BN(100_000_000))
-          .accounts({
-            config: configPda,
-            sender: validSender,
-            receiver: validReceiver,
-            senderWhitelist: senderWhitelistPda,
-            receiverWhitelist: receiverWhitelistPda,
-            senderBlacklist: null,
-            receiverBlacklist: null,
-            jurisdictionRule: null,
-          })
-          .rpc();
+        await transferHook(new anchor.BN(100_000_000)).rpc();
         
         expect.fail("Should have thrown an error");
       } catch (error) {
@@ -431,7 +629,7 @@ describe("compliance", () => {
 
       // Resume for other tests
       await program.methods
-        .updateConfig(null, null, false)
+        .updateConfig(false)
         .accounts({
           authority: authority.publicKey,
           config: configPda,
@@ -440,26 +638,463 @@ describe("compliance", () => {
     });
 
     it("fails when transfer amount exceeds max", async () => {
-      const excessiveAmount = new anchor.BN(5_000_000_000); // 5000 USDC (max is 2000)
+      const excessiveAmount = new anchor.BN(5_000_000_000); // 5000 USDC (max is 1000)
+
+      try {
+        await transferHook(excessiveAmount).rpc();
+        
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("TransferAmountExceeded");
+      }
+    });
+
+    it("refuses transfers beyond the sender's unlocked balance", async () => {
+      const feeTreasury = registryPda(Buffer.from("fee-treasury"));
+      const treasuryTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          authority.payer,
+          mint,
+          feeTreasury,
+          true,
+          "confirmed",
+          { commitment: "confirmed" },
+          TOKEN_2022_PROGRAM_ID
+        )
+      ).address;
+
+      // A one-year Reg D holding period on the whole issuance
+      const holdingPeriod = new anchor.BN(365 * 24 * 60 * 60);
+      await registry.methods
+        .mintTokens(new anchor.BN(1000), { cliffDuration: holdingPeriod, vestingDuration: holdingPeriod })
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(2),
+          mintConfig: mintConfigPda,
+          asset: assetPda,
+          mint,
+          mintAuthority: registryPda(Buffer.from("mint-authority"), mint.toBuffer()),
+          recipient: validSender,
+          recipientTokenAccount: senderTokenAccount,
+          config: registryPda(Buffer.from("config")),
+          feeOverride: null,
+          reserve: null,
+          feeTreasury,
+          treasuryTokenAccount,
+          lockup: registryPda(Buffer.from("lockup"), mint.toBuffer(), validSender.toBuffer()),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
 
       try {
+        await transferHook(new anchor.BN(1)).rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("TransferExceedsUnlocked");
+      }
+    });
+  });
+
+  describe("token transfers", () => {
+    const holder = Keypair.generate();
+    const counterparty = Keypair.generate();
+    let mint: PublicKey;
+    let assetPda: PublicKey;
+    let holderTokenAccount: PublicKey;
+    let counterpartyTokenAccount: PublicKey;
+
+    const transfer = (amount: number) =>
+      transferCheckedWithTransferHook(
+        provider.connection,
+        authority.payer,
+        holderTokenAccount,
+        mint,
+        counterpartyTokenAccount,
+        holder,
+        BigInt(amount),
+        6,
+        [],
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+
+    before(async () => {
+      const mintKeypair = Keypair.generate();
+      mint = mintKeypair.publicKey;
+      assetPda = registryPda(Buffer.from("asset"), mint.toBuffer());
+      const mintConfig = registryPda(Buffer.from("mint-config"), mint.toBuffer());
+
+      await registry.methods
+        .createTokenMint("Transfer Token", "XFER", "https://test.com", 6, program.programId, false)
+        .accounts({
+          authority: authority.publicKey,
+          permanentDelegate: registryPda(Buffer.from("permanent-delegate"), mint.toBuffer()),
+          mint,
+          mintConfig,
+          mintAuthority: registryPda(Buffer.from("mint-authority"), mint.toBuffer()),
+          issuerRole: registryRole(1),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([mintKeypair])
+        .rpc();
+
+      await registry.methods
+        .registerAsset("Transfer Asset", { realEstate: {} }, new anchor.BN(1000), new anchor.BN(1_000_000), "https://test.com")
+        .accounts({
+          config: registryPda(Buffer.from("config")),
+          asset: assetPda,
+          mint,
+          mintConfig,
+          authority: authority.publicKey,
+          issuerRole: registryRole(1),
+          systemProgram: SystemProgram.programId,
+          ...(await registerIndexAccounts()),
+        })
+        .rpc();
+      await registry.methods
+        .activateAsset()
+        .accounts({ asset: assetPda, authority: authority.publicKey })
+        .rpc();
+
+      await program.methods
+        .initializeExtraAccountMetaList()
+        .accounts({
+          payer: authority.publicKey,
+          extraAccountMetaList: PublicKey.findProgramAddressSync(
+            [Buffer.from("extra-account-metas"), mint.toBuffer()],
+            program.programId
+          )[0],
+          mint,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const kycExpiry = new anchor.BN(Math.floor(Date.now() / 1000) + 365 * 24 * 60 * 60);
+      for (const investor of [holder.publicKey, counterparty.publicKey]) {
         await program.methods
-          .transferHook(excessiveAmount)
+          .addToWhitelist(investor, { retail: {} }, [0x55, 0x53], kycExpiry)
           .accounts({
+            authority: authority.publicKey,
             config: configPda,
-            sender: validSender,
-            receiver: validReceiver,
-            senderWhitelist: senderWhitelistPda,
-            receiverWhitelist: receiverWhitelistPda,
-            senderBlacklist: null,
-            receiverBlacklist: null,
-            jurisdictionRule: null,
+            whitelistEntry: PublicKey.findProgramAddressSync(
+              [Buffer.from("whitelist"), investor.toBuffer()],
+              program.programId
+            )[0],
+            systemProgram: SystemProgram.programId,
           })
           .rpc();
-        
+      }
+
+      holderTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        holder.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+      counterpartyTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        counterparty.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const feeTreasury = registryPda(Buffer.from("fee-treasury"));
+      await registry.methods
+        .mintTokens(new anchor.BN(10_000), null)
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(2),
+          mintConfig,
+          asset: assetPda,
+          mint,
+          mintAuthority: registryPda(Buffer.from("mint-authority"), mint.toBuffer()),
+          recipient: holder.publicKey,
+          recipientTokenAccount: holderTokenAccount,
+          config: registryPda(Buffer.from("config")),
+          feeOverride: null,
+          reserve: null,
+          feeTreasury,
+          treasuryTokenAccount: (
+            await getOrCreateAssociatedTokenAccount(
+              provider.connection,
+              authority.payer,
+              mint,
+              feeTreasury,
+              true,
+              "confirmed",
+              { commitment: "confirmed" },
+              TOKEN_2022_PROGRAM_ID
+            )
+          ).address,
+          lockup: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("runs the compliance hook on a real transfer", async () => {
+      await transfer(1_000);
+
+      const received = await getAccount(provider.connection, counterpartyTokenAccount, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(Number(received.amount)).to.equal(1_000);
+    });
+
+    it("enforces the rule between the holders' jurisdictions on a real transfer", async () => {
+      // The US -> CN rule added above disallows the transfer
+      const cnHolder = Keypair.generate();
+      await program.methods
+        .addToWhitelist(
+          cnHolder.publicKey,
+          { retail: {} },
+          [0x43, 0x4E],
+          new anchor.BN(Math.floor(Date.now() / 1000) + 365 * 24 * 60 * 60)
+        )
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          whitelistEntry: PublicKey.findProgramAddressSync(
+            [Buffer.from("whitelist"), cnHolder.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      const cnTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        cnHolder.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      try {
+        await transferCheckedWithTransferHook(
+          provider.connection,
+          authority.payer,
+          holderTokenAccount,
+          mint,
+          cnTokenAccount,
+          holder,
+          BigInt(1),
+          6,
+          [],
+          { commitment: "confirmed" },
+          TOKEN_2022_PROGRAM_ID
+        );
         expect.fail("Should have thrown an error");
       } catch (error) {
-        expect(error.message).to.include("TransferAmountExceeded");
+        expect(error.logs.join("\n")).to.include("JurisdictionNotAllowed");
+      }
+    });
+
+    it("holds back batch-minted locked tokens on a real transfer", async () => {
+      const holdingPeriod = new anchor.BN(365 * 24 * 60 * 60);
+      const lockup = registryPda(Buffer.from("lockup"), mint.toBuffer(), holder.publicKey.toBuffer());
+      const feeTreasury = registryPda(Buffer.from("fee-treasury"));
+
+      await registry.methods
+        .batchMintTokens(
+          [
+            {
+              recipient: holder.publicKey,
+              amount: new anchor.BN(1_000),
+              lockup: { cliffDuration: holdingPeriod, vestingDuration: holdingPeriod },
+            },
+          ],
+          false
+        )
+        .accounts({
+          authority: authority.publicKey,
+          role: registryRole(2),
+          mintConfig: registryPda(Buffer.from("mint-config"), mint.toBuffer()),
+          asset: assetPda,
+          mint,
+          mintAuthority: registryPda(Buffer.from("mint-authority"), mint.toBuffer()),
+          config: registryPda(Buffer.from("config")),
+          feeOverride: null,
+          reserve: null,
+          feeTreasury,
+          treasuryTokenAccount: (
+            await getOrCreateAssociatedTokenAccount(
+              provider.connection,
+              authority.payer,
+              mint,
+              feeTreasury,
+              true,
+              "confirmed",
+              { commitment: "confirmed" },
+              TOKEN_2022_PROGRAM_ID
+            )
+          ).address,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: holder.publicKey, isSigner: false, isWritable: false },
+          { pubkey: holderTokenAccount, isSigner: false, isWritable: true },
+          { pubkey: lockup, isSigner: false, isWritable: true },
+        ])
+        .rpc();
+
+      const unlocked = await registry.methods
+        .getUnlockedBalance()
+        .accounts({ holderTokenAccount, lockup })
+        .view();
+
+      try {
+        await transfer(unlocked.toNumber() + 1);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.logs.join("\n")).to.include("TransferExceedsUnlocked");
+      }
+
+      await transfer(unlocked.toNumber());
+    });
+
+    it("refuses a real transfer while the registry asset is frozen", async () => {
+      await registry.methods
+        .freezeAsset()
+        .accounts({ asset: assetPda, authority: authority.publicKey, role: registryRole(3) })
+        .rpc();
+
+      try {
+        await transfer(1_000);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.logs.join("\n")).to.include("AssetNotTradeable");
+      }
+
+      await registry.methods
+        .unfreezeAsset()
+        .accounts({ asset: assetPda, authority: authority.publicKey, role: registryRole(3) })
+        .rpc();
+    });
+  });
+
+  describe("kyc thaw", () => {
+    const investor = Keypair.generate();
+    const outsider = Keypair.generate();
+    let mint: PublicKey;
+    let investorTokenAccount: PublicKey;
+    let outsiderTokenAccount: PublicKey;
+    let investorWhitelistPda: PublicKey;
+
+    const isFrozen = async (tokenAccount: PublicKey) =>
+      (await getAccount(provider.connection, tokenAccount, "confirmed", TOKEN_2022_PROGRAM_ID)).isFrozen;
+
+    before(async () => {
+      const mintKeypair = Keypair.generate();
+      mint = mintKeypair.publicKey;
+
+      await registry.methods
+        .createTokenMint("KYC Token", "KYC", "https://test.com", 6, program.programId, true)
+        .accounts({
+          authority: authority.publicKey,
+          permanentDelegate: registryPda(Buffer.from("permanent-delegate"), mint.toBuffer()),
+          mint,
+          mintConfig: registryPda(Buffer.from("mint-config"), mint.toBuffer()),
+          mintAuthority: registryPda(Buffer.from("mint-authority"), mint.toBuffer()),
+          issuerRole: registryRole(1),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([mintKeypair])
+        .rpc();
+
+      investorTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        investor.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+      outsiderTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        mint,
+        outsider.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      [investorWhitelistPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("whitelist"), investor.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .addToWhitelist(
+          investor.publicKey,
+          { accredited: {} },
+          [0x55, 0x53],
+          new anchor.BN(Math.floor(Date.now() / 1000) + 365 * 24 * 60 * 60)
+        )
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          whitelistEntry: investorWhitelistPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("creates token accounts frozen by default", async () => {
+      expect(await isFrozen(investorTokenAccount)).to.be.true;
+      expect(await isFrozen(outsiderTokenAccount)).to.be.true;
+    });
+
+    it("thaws a whitelisted investor's account through the compliance program", async () => {
+      await program.methods
+        .thawWhitelistedAccount()
+        .accounts({
+          authority: authority.publicKey,
+          config: configPda,
+          whitelistEntry: investorWhitelistPda,
+          mint,
+          mintAuthority: registryPda(Buffer.from("mint-authority"), mint.toBuffer()),
+          holderTokenAccount: investorTokenAccount,
+          holderFreeze: registryPda(Buffer.from("holder-freeze"), mint.toBuffer(), investorTokenAccount.toBuffer()),
+          ...(await splitGuardAccounts(mint, investorTokenAccount)),
+          registryProgram: registry.programId,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      expect(await isFrozen(investorTokenAccount)).to.be.false;
+    });
+
+    it("fails to thaw an account whose owner is not whitelisted", async () => {
+      try {
+        await registry.methods
+          .thawKycAccount()
+          .accounts({
+            authority: authority.publicKey,
+            kycRole: registryRole(6),
+            mint,
+            mintAuthority: registryPda(Buffer.from("mint-authority"), mint.toBuffer()),
+            holderTokenAccount: outsiderTokenAccount,
+            whitelistEntry: PublicKey.findProgramAddressSync(
+              [Buffer.from("whitelist"), outsider.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            holderFreeze: registryPda(Buffer.from("holder-freeze"), mint.toBuffer(), outsiderTokenAccount.toBuffer()),
+            ...(await splitGuardAccounts(mint, outsiderTokenAccount)),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("KycNotVerified");
       }
     });
   });
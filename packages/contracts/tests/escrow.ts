@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Escrow } from "../target/types/escrow";
+import { AssetRegistry } from "../target/types/asset_registry";
 import { 
   Keypair, 
   PublicKey, 
@@ -14,6 +15,7 @@ import {
   createAssociatedTokenAccount,
   mintTo,
   getAccount,
+  getOrCreateAssociatedTokenAccount,
   ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { expect } from "chai";
@@ -23,6 +25,9 @@ describe("escrow", () => {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.Escrow as Program<Escrow>;
+  const registry = anchor.workspace.AssetRegistry as Program<AssetRegistry>;
+  const findAsset = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("asset"), mint.toBuffer()], registry.programId)[0];
   
   const buyer = Keypair.generate();
   const seller = Keypair.generate();
@@ -154,6 +159,7 @@ describe("escrow", () => {
           buyer: buyer.publicKey,
           seller: seller.publicKey,
           assetMint: assetMint,
+          asset: findAsset(assetMint),
           paymentMint: paymentMint,
           systemProgram: SystemProgram.programId,
         })
@@ -204,6 +210,7 @@ describe("escrow", () => {
             buyer: buyer.publicKey,
             seller: seller.publicKey,
             assetMint: newAssetMint,
+            asset: findAsset(newAssetMint),
             paymentMint: paymentMint,
             systemProgram: SystemProgram.programId,
           })
@@ -249,6 +256,7 @@ describe("escrow", () => {
             buyer: buyer.publicKey,
             seller: seller.publicKey,
             assetMint: newAssetMint,
+            asset: findAsset(newAssetMint),
             paymentMint: paymentMint,
             systemProgram: SystemProgram.programId,
           })
@@ -332,8 +340,31 @@ describe("escrow", () => {
   describe("release", () => {
     let buyerAssetAccount: PublicKey;
     let sellerPaymentAccount: PublicKey;
+    let treasuryPaymentAccount: PublicKey;
+
+    const [registryConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      registry.programId
+    );
+    const [feeTreasury] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee-treasury")],
+      registry.programId
+    );
 
     before(async () => {
+      treasuryPaymentAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          buyer,
+          paymentMint,
+          feeTreasury,
+          true,
+          "confirmed",
+          { commitment: "confirmed" },
+          TOKEN_2022_PROGRAM_ID
+        )
+      ).address;
+
       // Create buyer's asset account
       buyerAssetAccount = await createAssociatedTokenAccount(
         provider.connection,
@@ -363,11 +394,16 @@ describe("escrow", () => {
           buyer: buyer.publicKey,
           seller: seller.publicKey,
           assetMint: assetMint,
+          asset: findAsset(assetMint),
           paymentMint: paymentMint,
           escrowAssetVault: escrowAssetVault,
           escrowPaymentVault: escrowPaymentVault,
           buyerAssetAccount: buyerAssetAccount,
           sellerPaymentAccount: sellerPaymentAccount,
+          config: registryConfig,
+          feeOverride: null,
+          feeTreasury,
+          treasuryPaymentAccount,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .rpc();
@@ -381,14 +417,25 @@ describe("escrow", () => {
       );
       expect(Number(buyerAssets.amount)).to.equal(assetAmount.toNumber());
 
-      // Check seller received payment
+      // Check seller received payment net of the registry platform fee
+      const config = await registry.account.config.fetch(registryConfig);
+      const fee = paymentAmount.toNumber() * config.platformFeeBps / 10_000;
       const sellerPayment = await getAccount(
         provider.connection,
         sellerPaymentAccount,
         "confirmed",
         TOKEN_2022_PROGRAM_ID
       );
-      expect(Number(sellerPayment.amount)).to.equal(paymentAmount.toNumber());
+      expect(Number(sellerPayment.amount)).to.equal(paymentAmount.toNumber() - fee);
+
+      // Check the fee reached the treasury
+      const treasuryPayment = await getAccount(
+        provider.connection,
+        treasuryPaymentAccount,
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(Number(treasuryPayment.amount)).to.equal(fee);
 
       // Check escrow status
       const escrow = await program.account.escrow.fetch(escrowPda);
@@ -431,6 +478,7 @@ describe("escrow", () => {
           buyer: buyer.publicKey,
           seller: seller.publicKey,
           assetMint: newAssetMint,
+          asset: findAsset(newAssetMint),
           paymentMint: paymentMint,
           systemProgram: SystemProgram.programId,
         })
@@ -4,20 +4,29 @@ import { AssetRegistry } from "../target/types/asset_registry";
 import { Escrow } from "../target/types/escrow";
 import { Auction } from "../target/types/auction";
 import { Compliance } from "../target/types/compliance";
+import { Offering } from "../target/types/offering";
 import { 
   Keypair, 
   PublicKey, 
   SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
   LAMPORTS_PER_SOL 
 } from "@solana/web3.js";
 import { 
   TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
   createMint,
   getAssociatedTokenAddress,
   createAssociatedTokenAccount,
   mintTo,
   getAccount,
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+  ExtensionType,
+  getMintLen,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
 } from "@solana/spl-token";
 import { expect } from "chai";
 
@@ -29,6 +38,7 @@ import { expect } from "chai";
  * 2. Compliant transfer flow
  * 3. Escrow purchase flow
  * 4. Auction flow
+ * 5. Primary offering flow
  */
 describe("integration", () => {
   const provider = anchor.AnchorProvider.env();
@@ -38,6 +48,7 @@ describe("integration", () => {
   const escrowProgram = anchor.workspace.Escrow as Program<Escrow>;
   const auctionProgram = anchor.workspace.Auction as Program<Auction>;
   const complianceProgram = anchor.workspace.Compliance as Program<Compliance>;
+  const offeringProgram = anchor.workspace.Offering as Program<Offering>;
 
   // Participants
   const bank = Keypair.generate();
@@ -62,6 +73,103 @@ describe("integration", () => {
   let investor2WhitelistPda: PublicKey;
   let bankWhitelistPda: PublicKey;
 
+  // Registry-wide role assignments held by the provider wallet
+  const registryRole = (role: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("role"),
+        assetRegistryProgram.programId.toBuffer(),
+        Buffer.from([role]),
+        provider.wallet.publicKey.toBuffer(),
+      ],
+      assetRegistryProgram.programId
+    )[0];
+  // Registry lockup record read by the compliance hook
+  const findLockup = (mint: PublicKey, holder: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("lockup"), mint.toBuffer(), holder.toBuffer()],
+      assetRegistryProgram.programId
+    )[0];
+
+  // Accounts the compliance hook validates a transfer of the asset mint with
+  const transferHookAccounts = (
+    sender: PublicKey,
+    senderTokenAccount: PublicKey,
+    receiver: PublicKey,
+    receiverTokenAccount: PublicKey
+  ) => {
+    const compliancePda = (...seeds: Buffer[]) =>
+      PublicKey.findProgramAddressSync(seeds, complianceProgram.programId)[0];
+
+    return {
+      sourceTokenAccount: senderTokenAccount,
+      mint: assetMint,
+      destinationTokenAccount: receiverTokenAccount,
+      authority: sender,
+      extraAccountMetaList: compliancePda(Buffer.from("extra-account-metas"), assetMint.toBuffer()),
+      config: complianceConfigPda,
+      registryProgram: assetRegistryProgram.programId,
+      asset: assetPda,
+      mintConfig: mintConfigPda,
+      senderLockup: findLockup(assetMint, sender),
+      senderWhitelist: compliancePda(Buffer.from("whitelist"), sender.toBuffer()),
+      receiverWhitelist: compliancePda(Buffer.from("whitelist"), receiver.toBuffer()),
+      senderBlacklist: compliancePda(Buffer.from("blacklist"), sender.toBuffer()),
+      receiverBlacklist: compliancePda(Buffer.from("blacklist"), receiver.toBuffer()),
+      // Every investor in this suite is whitelisted in the US
+      jurisdictionRule: compliancePda(Buffer.from("jurisdiction"), Buffer.from("US"), Buffer.from("US")),
+    };
+  };
+
+  // Registry index accounts the wallet's next real estate registration lands in
+  const registerIndexAccounts = async () => {
+    const findIndex = (...scope: Buffer[]) =>
+      PublicKey.findProgramAddressSync([Buffer.from("asset-index"), ...scope], assetRegistryProgram.programId)[0];
+    const nextPage = async (index: PublicKey) => {
+      const count = (await assetRegistryProgram.account.assetIndex.fetchNullable(index))?.count.toNumber() ?? 0;
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("asset-index-page"), index.toBuffer(), new anchor.BN(Math.floor(count / 32)).toArrayLike(Buffer, "le", 8)],
+        assetRegistryProgram.programId
+      )[0];
+    };
+    const globalIndex = findIndex();
+    const issuerIndex = findIndex(Buffer.from("issuer"), provider.wallet.publicKey.toBuffer());
+    const typeIndex = findIndex(Buffer.from("type"), Buffer.from([0]));
+
+    return {
+      globalIndex,
+      globalIndexPage: await nextPage(globalIndex),
+      issuerIndex,
+      issuerIndexPage: await nextPage(issuerIndex),
+      typeIndex,
+      typeIndexPage: await nextPage(typeIndex),
+    };
+  };
+  // Registry PDA owning the platform fee token accounts
+  const [feeTreasury] = PublicKey.findProgramAddressSync(
+    [Buffer.from("fee-treasury")],
+    assetRegistryProgram.programId
+  );
+  const treasuryAccount = async (mint: PublicKey) =>
+    (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        bank,
+        mint,
+        feeTreasury,
+        true,
+        "confirmed",
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      )
+    ).address;
+
+  const ADMIN_ROLE = 0;
+  const ISSUER_ROLE = 1;
+  const MINTER_ROLE = 2;
+  const PAUSER_ROLE = 3;
+  const VALUATION_OFFICER_ROLE = 4;
+
   before(async () => {
     // Airdrop SOL to all participants
     for (const wallet of [bank, investor1, investor2]) {
@@ -158,6 +266,7 @@ describe("integration", () => {
         .initialize(250) // 2.5% platform fee
         .accounts({
           config: assetRegistryConfigPda,
+          adminRole: registryRole(ADMIN_ROLE),
           authority: provider.wallet.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -167,6 +276,27 @@ describe("integration", () => {
       expect(config.platformFeeBps).to.equal(250);
     });
 
+    it("grants issuance roles to the platform operator", async () => {
+      const roles: [object, number][] = [
+        [{ issuer: {} }, ISSUER_ROLE],
+        [{ minter: {} }, MINTER_ROLE],
+        [{ pauser: {} }, PAUSER_ROLE],
+        [{ valuationOfficer: {} }, VALUATION_OFFICER_ROLE],
+      ];
+
+      for (const [role, index] of roles) {
+        await assetRegistryProgram.methods
+          .grantRegistryRole(role as any, provider.wallet.publicKey)
+          .accounts({
+            authority: provider.wallet.publicKey,
+            adminRole: registryRole(ADMIN_ROLE),
+            roleAssignment: registryRole(index),
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+    });
+
     it("initializes compliance program", async () => {
       const civicGatekeeperNetwork = Keypair.generate().publicKey;
 
@@ -231,17 +361,9 @@ describe("integration", () => {
 
   describe("2. Asset Tokenization Flow", () => {
     it("creates asset token mint", async () => {
-      // Create the Token-2022 mint for the asset
-      assetMint = await createMint(
-        provider.connection,
-        bank,
-        bank.publicKey,
-        null,
-        6, // 6 decimals
-        Keypair.generate(),
-        { commitment: "confirmed" },
-        TOKEN_2022_PROGRAM_ID
-      );
+      // The registry allocates the Token-2022 mint with all RWA extensions
+      const assetMintKeypair = Keypair.generate();
+      assetMint = assetMintKeypair.publicKey;
 
       [mintConfigPda] = PublicKey.findProgramAddressSync(
         [Buffer.from("mint-config"), assetMint.toBuffer()],
@@ -259,17 +381,23 @@ describe("integration", () => {
           "CRT",
           "https://arweave.net/commercial-re-metadata",
           6,
-          complianceProgram.programId // Set compliance program as transfer hook
+          complianceProgram.programId, // Set compliance program as transfer hook
+          false
         )
         .accounts({
           authority: provider.wallet.publicKey,
-          permanentDelegate: provider.wallet.publicKey,
+          permanentDelegate: PublicKey.findProgramAddressSync(
+            [Buffer.from("permanent-delegate"), assetMint.toBuffer()],
+            assetRegistryProgram.programId
+          )[0],
           mint: assetMint,
           mintConfig: mintConfigPda,
           mintAuthority: mintAuthorityPda,
+          issuerRole: registryRole(ISSUER_ROLE),
           tokenProgram: TOKEN_2022_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
+        .signers([assetMintKeypair])
         .rpc();
 
       const mintConfig = await assetRegistryProgram.account.mintConfig.fetch(mintConfigPda);
@@ -295,8 +423,11 @@ describe("integration", () => {
           config: assetRegistryConfigPda,
           asset: assetPda,
           mint: assetMint,
+          mintConfig: mintConfigPda,
           authority: provider.wallet.publicKey,
+          issuerRole: registryRole(ISSUER_ROLE),
           systemProgram: SystemProgram.programId,
+          ...(await registerIndexAccounts()),
         })
         .rpc();
 
@@ -328,25 +459,41 @@ describe("integration", () => {
         TOKEN_2022_PROGRAM_ID
       );
 
-      await mintTo(
-        provider.connection,
-        bank,
-        assetMint,
-        bankAssetAccount,
-        bank,
-        1_000_000_000_000, // 1M tokens
-        [],
-        { commitment: "confirmed" },
-        TOKEN_2022_PROGRAM_ID
+      const [mintAuthorityPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint-authority"), assetMint.toBuffer()],
+        assetRegistryProgram.programId
       );
 
+      await assetRegistryProgram.methods
+        .mintTokens(new anchor.BN(1_000_000_000_000), null) // 1M tokens
+        .accounts({
+          authority: provider.wallet.publicKey,
+          role: registryRole(MINTER_ROLE),
+          mintConfig: mintConfigPda,
+          asset: assetPda,
+          mint: assetMint,
+          mintAuthority: mintAuthorityPda,
+          recipient: bank.publicKey,
+          recipientTokenAccount: bankAssetAccount,
+          config: assetRegistryConfigPda,
+          feeOverride: null,
+          reserve: null,
+          feeTreasury,
+          treasuryTokenAccount: await treasuryAccount(assetMint),
+          lockup: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
       const balance = await getAccount(
         provider.connection,
         bankAssetAccount,
         "confirmed",
         TOKEN_2022_PROGRAM_ID
       );
-      expect(Number(balance.amount)).to.equal(1_000_000_000_000);
+      // 2.5% platform fee is taken in kind on issuance
+      expect(Number(balance.amount)).to.equal(975_000_000_000);
     });
   });
 
@@ -392,6 +539,40 @@ describe("integration", () => {
       );
     });
 
+    it("refuses escrow while the asset is in default", async () => {
+      const setStatus = (status: object, reason: object) =>
+        assetRegistryProgram.methods
+          .setLifecycleStatus(status as any, reason as any)
+          .accounts({
+            asset: assetPda,
+            authority: provider.wallet.publicKey,
+          })
+          .rpc();
+
+      await setStatus({ defaulted: {} }, { covenantBreach: {} });
+
+      try {
+        await escrowProgram.methods
+          .createEscrow(purchaseAmount, purchasePrice, new anchor.BN(Math.floor(Date.now() / 1000) + 3600))
+          .accounts({
+            escrow: escrowPda,
+            buyer: investor1.publicKey,
+            seller: bank.publicKey,
+            assetMint: assetMint,
+            asset: assetPda,
+            paymentMint: usdcMint,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([investor1])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("AssetNotTradeable");
+      }
+
+      await setStatus({ active: {} }, { defaultCured: {} });
+    });
+
     it("creates escrow for token purchase", async () => {
       const expiresAt = Math.floor(Date.now() / 1000) + 3600;
 
@@ -402,6 +583,7 @@ describe("integration", () => {
           buyer: investor1.publicKey,
           seller: bank.publicKey,
           assetMint: assetMint,
+          asset: assetPda,
           paymentMint: usdcMint,
           systemProgram: SystemProgram.programId,
         })
@@ -478,11 +660,16 @@ describe("integration", () => {
           buyer: investor1.publicKey,
           seller: bank.publicKey,
           assetMint: assetMint,
+          asset: assetPda,
           paymentMint: usdcMint,
           escrowAssetVault: escrowAssetVault,
           escrowPaymentVault: escrowPaymentVault,
           buyerAssetAccount: investor1AssetAccount,
           sellerPaymentAccount: bankUsdcAccount,
+          config: assetRegistryConfigPda,
+          feeOverride: null,
+          feeTreasury,
+          treasuryPaymentAccount: await treasuryAccount(usdcMint),
           tokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .rpc();
@@ -561,6 +748,7 @@ describe("integration", () => {
           seller: investor1.publicKey,
           auction: auctionPda,
           assetMint: assetMint,
+          asset: assetPda,
           paymentMint: usdcMint,
           sellerAssetAccount: investor1AssetAccount,
           auctionAssetVault: auctionAssetVault,
@@ -594,6 +782,7 @@ describe("integration", () => {
           bidder: investor2.publicKey,
           auction: auctionPda,
           bid: bid2Pda,
+          asset: assetPda,
           paymentMint: usdcMint,
           bidderPaymentAccount: investor2UsdcAccount,
           auctionPaymentVault: auctionPaymentVault,
@@ -616,16 +805,9 @@ describe("integration", () => {
 
       await complianceProgram.methods
         .transferHook(transferAmount)
-        .accounts({
-          config: complianceConfigPda,
-          sender: investor1.publicKey,
-          receiver: investor2.publicKey,
-          senderWhitelist: investor1WhitelistPda,
-          receiverWhitelist: investor2WhitelistPda,
-          senderBlacklist: null,
-          receiverBlacklist: null,
-          jurisdictionRule: null,
-        })
+        .accounts(
+          transferHookAccounts(investor1.publicKey, investor1AssetAccount, investor2.publicKey, investor2AssetAccount)
+        )
         .rpc();
 
       // Transfer validated successfully
@@ -633,24 +815,21 @@ describe("integration", () => {
 
     it("blocks transfer to non-whitelisted address", async () => {
       const nonWhitelisted = Keypair.generate().publicKey;
-      const [nonWhitelistedPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("whitelist"), nonWhitelisted.toBuffer()],
-        complianceProgram.programId
+      const nonWhitelistedAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        bank,
+        assetMint,
+        nonWhitelisted,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
       );
 
       try {
         await complianceProgram.methods
           .transferHook(new anchor.BN(1_000_000_000))
-          .accounts({
-            config: complianceConfigPda,
-            sender: investor1.publicKey,
-            receiver: nonWhitelisted,
-            senderWhitelist: investor1WhitelistPda,
-            receiverWhitelist: null, // Not whitelisted
-            senderBlacklist: null,
-            receiverBlacklist: null,
-            jurisdictionRule: null,
-          })
+          .accounts(
+            transferHookAccounts(investor1.publicKey, investor1AssetAccount, nonWhitelisted, nonWhitelistedAccount)
+          )
           .rpc();
         
         expect.fail("Should have thrown an error");
@@ -664,17 +843,34 @@ describe("integration", () => {
         .freezeMint()
         .accounts({
           authority: provider.wallet.publicKey,
+          role: registryRole(PAUSER_ROLE),
           mintConfig: mintConfigPda,
+          asset: assetPda,
         })
         .rpc();
 
       let mintConfig = await assetRegistryProgram.account.mintConfig.fetch(mintConfigPda);
       expect(mintConfig.isFrozen).to.equal(true);
 
+      // The compliance hook refuses transfers while the registry mint is frozen
+      try {
+        await complianceProgram.methods
+          .transferHook(new anchor.BN(1_000_000))
+          .accounts(
+            transferHookAccounts(investor1.publicKey, investor1AssetAccount, investor2.publicKey, investor2AssetAccount)
+          )
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("MintFrozen");
+      }
+
       await assetRegistryProgram.methods
         .unfreezeMint()
         .accounts({
           authority: provider.wallet.publicKey,
+          role: registryRole(PAUSER_ROLE),
           mintConfig: mintConfigPda,
         })
         .rpc();
@@ -691,6 +887,7 @@ describe("integration", () => {
         .accounts({
           asset: assetPda,
           authority: provider.wallet.publicKey,
+          role: registryRole(PAUSER_ROLE),
         })
         .rpc();
 
@@ -702,26 +899,453 @@ describe("integration", () => {
         .accounts({
           asset: assetPda,
           authority: provider.wallet.publicKey,
+          role: registryRole(PAUSER_ROLE),
         })
         .rpc();
 
       asset = await assetRegistryProgram.account.asset.fetch(assetPda);
       expect(asset.status).to.deep.equal({ active: {} });
     });
+  });
+
+  describe("7. Primary Offering", () => {
+    const round = 1;
+    const offeringMintKeypair = Keypair.generate();
+    const offeringMint = offeringMintKeypair.publicKey;
+    const findRegistryPda = (seed: string, mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from(seed), mint.toBuffer()],
+        assetRegistryProgram.programId
+      )[0];
+    const offeringAsset = findRegistryPda("asset", offeringMint);
+    const offeringMintConfig = findRegistryPda("mint-config", offeringMint);
+
+    const [offeringPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("offering"),
+        offeringMint.toBuffer(),
+        new anchor.BN(round).toArrayLike(Buffer, "le", 2),
+      ],
+      offeringProgram.programId
+    );
+    const [minterRole] = PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), offeringMint.toBuffer(), Buffer.from([MINTER_ROLE]), offeringPda.toBuffer()],
+      assetRegistryProgram.programId
+    );
+    const findSubscription = (investor: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("subscription"), offeringPda.toBuffer(), investor.toBuffer()],
+        offeringProgram.programId
+      )[0];
+    const findWhitelist = (investor: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("whitelist"), investor.toBuffer()],
+        complianceProgram.programId
+      )[0];
+
+    let offeringVault: PublicKey;
+    let offeringTreasuryAccount: PublicKey;
+
+    // The offering takes payment in a classic SPL Token mint
+    let paymentMint: PublicKey;
+    let investor1PaymentAccount: PublicKey;
+    let investor2PaymentAccount: PublicKey;
+
+    // 10 USDC per token, raising between 2,000 and 5,000 USDC
+    const pricePerToken = new anchor.BN(10_000_000);
+    const softCap = new anchor.BN(2_000_000_000);
+    const hardCap = new anchor.BN(5_000_000_000);
+
+    const subscribe = (investor: Keypair, usdcAccount: PublicKey, amount: anchor.BN) =>
+      offeringProgram.methods
+        .subscribe(amount)
+        .accounts({
+          investor: investor.publicKey,
+          offering: offeringPda,
+          subscription: findSubscription(investor.publicKey),
+          whitelistEntry: findWhitelist(investor.publicKey),
+          paymentMint,
+          investorPaymentAccount: usdcAccount,
+          offeringPaymentVault: offeringVault,
+          paymentTokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([investor])
+        .rpc();
 
-    it("can update asset valuation", async () => {
-      const newValue = new anchor.BN(12_000_000_00); // $12M (20% increase)
+    const paymentBalance = async (account: PublicKey) =>
+      Number((await getAccount(provider.connection, account, "confirmed", TOKEN_PROGRAM_ID)).amount);
+
+    const settle = (investor: PublicKey, investorPaymentAccount: PublicKey, investorAssetAccount: PublicKey) =>
+      offeringProgram.methods
+        .settleSubscription()
+        .accounts({
+          settler: provider.wallet.publicKey,
+          offering: offeringPda,
+          subscription: findSubscription(investor),
+          investor,
+          paymentMint,
+          offeringPaymentVault: offeringVault,
+          investorPaymentAccount,
+          assetMint: offeringMint,
+          investorAssetAccount,
+          minterRole,
+          mintConfig: offeringMintConfig,
+          asset: offeringAsset,
+          mintAuthority: findRegistryPda("mint-authority", offeringMint),
+          config: assetRegistryConfigPda,
+          feeOverride: null,
+          reserve: null,
+          feeTreasury,
+          treasuryTokenAccount: offeringTreasuryAccount,
+          assetRegistryProgram: assetRegistryProgram.programId,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          paymentTokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    before(async () => {
+      paymentMint = await createMint(
+        provider.connection,
+        bank,
+        bank.publicKey,
+        null,
+        6,
+        Keypair.generate(),
+        { commitment: "confirmed" },
+        TOKEN_PROGRAM_ID
+      );
+      for (const investor of [investor1, investor2]) {
+        const account = await createAssociatedTokenAccount(
+          provider.connection,
+          investor,
+          paymentMint,
+          investor.publicKey,
+          { commitment: "confirmed" },
+          TOKEN_PROGRAM_ID
+        );
+        await mintTo(
+          provider.connection,
+          bank,
+          paymentMint,
+          account,
+          bank,
+          5_000_000_000,
+          [],
+          { commitment: "confirmed" },
+          TOKEN_PROGRAM_ID
+        );
+        if (investor === investor1) {
+          investor1PaymentAccount = account;
+        } else {
+          investor2PaymentAccount = account;
+        }
+      }
 
       await assetRegistryProgram.methods
-        .updateAsset(null, newValue)
+        .createTokenMint("Logistics Park Token", "LPT", "https://arweave.net/logistics-park", 6, complianceProgram.programId, false)
         .accounts({
-          asset: assetPda,
           authority: provider.wallet.publicKey,
+          permanentDelegate: findRegistryPda("permanent-delegate", offeringMint),
+          mint: offeringMint,
+          mintConfig: offeringMintConfig,
+          mintAuthority: findRegistryPda("mint-authority", offeringMint),
+          issuerRole: registryRole(ISSUER_ROLE),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
+        .signers([offeringMintKeypair])
         .rpc();
 
-      const asset = await assetRegistryProgram.account.asset.fetch(assetPda);
-      expect(asset.totalValue.toNumber()).to.equal(newValue.toNumber());
+      await assetRegistryProgram.methods
+        .registerAsset(
+          "Logistics Park",
+          { realEstate: {} },
+          new anchor.BN(5_000_000_00), // $5M
+          new anchor.BN(1_000_000_000), // 1,000 tokens
+          "https://arweave.net/logistics-park-docs"
+        )
+        .accounts({
+          config: assetRegistryConfigPda,
+          asset: offeringAsset,
+          mint: offeringMint,
+          mintConfig: offeringMintConfig,
+          authority: provider.wallet.publicKey,
+          issuerRole: registryRole(ISSUER_ROLE),
+          systemProgram: SystemProgram.programId,
+          ...(await registerIndexAccounts()),
+        })
+        .rpc();
+
+      await assetRegistryProgram.methods
+        .activateAsset()
+        .accounts({ asset: offeringAsset, authority: provider.wallet.publicKey })
+        .rpc();
+
+      // The offering PDA mints through the registry as an asset-scoped minter
+      await assetRegistryProgram.methods
+        .grantAssetRole({ minter: {} }, offeringPda)
+        .accounts({
+          authority: provider.wallet.publicKey,
+          asset: offeringAsset,
+          roleAssignment: minterRole,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      offeringVault = await getAssociatedTokenAddress(paymentMint, offeringPda, true, TOKEN_PROGRAM_ID);
+      offeringTreasuryAccount = await treasuryAccount(offeringMint);
+    });
+
+    it("opens a first-come offering", async () => {
+      const now = Math.floor(Date.now() / 1000);
+
+      await offeringProgram.methods
+        .openOffering(
+          round,
+          pricePerToken,
+          softCap,
+          hardCap,
+          new anchor.BN(1_000_000_000), // 1,000 USDC minimum ticket
+          new anchor.BN(3_000_000_000), // 3,000 USDC maximum ticket
+          new anchor.BN(now - 60),
+          new anchor.BN(now + 3600),
+          { firstCome: {} }
+        )
+        .accounts({
+          issuer: provider.wallet.publicKey,
+          offering: offeringPda,
+          asset: offeringAsset,
+          mintConfig: offeringMintConfig,
+          config: assetRegistryConfigPda,
+          feeOverride: null,
+          minterRole,
+          assetMint: offeringMint,
+          paymentMint,
+          offeringPaymentVault: offeringVault,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          paymentTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const offering = await offeringProgram.account.offering.fetch(offeringPda);
+      expect(offering.status).to.deep.equal({ open: {} });
+      expect(offering.hardCap.toNumber()).to.equal(hardCap.toNumber());
+    });
+
+    it("refuses payment mints that charge transfer fees", async () => {
+      const feeMintKeypair = Keypair.generate();
+      const feeMint = feeMintKeypair.publicKey;
+      const space = getMintLen([ExtensionType.TransferFeeConfig]);
+      await sendAndConfirmTransaction(
+        provider.connection,
+        new Transaction().add(
+          SystemProgram.createAccount({
+            fromPubkey: bank.publicKey,
+            newAccountPubkey: feeMint,
+            space,
+            lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+            programId: TOKEN_2022_PROGRAM_ID,
+          }),
+          createInitializeTransferFeeConfigInstruction(feeMint, bank.publicKey, bank.publicKey, 50, BigInt(1_000_000), TOKEN_2022_PROGRAM_ID),
+          createInitializeMintInstruction(feeMint, 6, bank.publicKey, null, TOKEN_2022_PROGRAM_ID)
+        ),
+        [bank, feeMintKeypair],
+        { commitment: "confirmed" }
+      );
+
+      const feeRound = 2;
+      const [feeOffering] = PublicKey.findProgramAddressSync(
+        [Buffer.from("offering"), offeringMint.toBuffer(), new anchor.BN(feeRound).toArrayLike(Buffer, "le", 2)],
+        offeringProgram.programId
+      );
+      const [feeMinterRole] = PublicKey.findProgramAddressSync(
+        [Buffer.from("role"), offeringMint.toBuffer(), Buffer.from([MINTER_ROLE]), feeOffering.toBuffer()],
+        assetRegistryProgram.programId
+      );
+      await assetRegistryProgram.methods
+        .grantAssetRole({ minter: {} }, feeOffering)
+        .accounts({
+          authority: provider.wallet.publicKey,
+          asset: offeringAsset,
+          roleAssignment: feeMinterRole,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      try {
+        await offeringProgram.methods
+          .openOffering(
+            feeRound,
+            pricePerToken,
+            softCap,
+            hardCap,
+            new anchor.BN(1_000_000_000),
+            new anchor.BN(3_000_000_000),
+            new anchor.BN(now - 60),
+            new anchor.BN(now + 3600),
+            { firstCome: {} }
+          )
+          .accounts({
+            issuer: provider.wallet.publicKey,
+            offering: feeOffering,
+            asset: offeringAsset,
+            mintConfig: offeringMintConfig,
+            config: assetRegistryConfigPda,
+            feeOverride: null,
+            minterRole: feeMinterRole,
+            assetMint: offeringMint,
+            paymentMint: feeMint,
+            offeringPaymentVault: await getAssociatedTokenAddress(feeMint, feeOffering, true, TOKEN_2022_PROGRAM_ID),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            paymentTokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("UnsupportedPaymentMint");
+      }
+    });
+
+    it("accepts whitelisted subscriptions within the ticket limits", async () => {
+      await subscribe(investor1, investor1PaymentAccount, new anchor.BN(3_000_000_000));
+
+      try {
+        await subscribe(investor2, investor2PaymentAccount, new anchor.BN(500_000_000));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidTicketSize");
+      }
+
+      const offering = await offeringProgram.account.offering.fetch(offeringPda);
+      expect(offering.totalCommitted.toNumber()).to.equal(3_000_000_000);
+      expect(offering.totalSubscribers).to.equal(1);
+    });
+
+    it("refuses commitments beyond the hard cap", async () => {
+      try {
+        await subscribe(investor2, investor2PaymentAccount, new anchor.BN(2_500_000_000));
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("HardCapReached");
+      }
+
+      await subscribe(investor2, investor2PaymentAccount, new anchor.BN(2_000_000_000));
+    });
+
+    it("closes early once the hard cap is filled", async () => {
+      await offeringProgram.methods
+        .closeOffering()
+        .accounts({ closer: provider.wallet.publicKey, offering: offeringPda })
+        .rpc();
+
+      const offering = await offeringProgram.account.offering.fetch(offeringPda);
+      expect(offering.status).to.deep.equal({ succeeded: {} });
+    });
+
+    it("settles subscriptions by minting through the registry", async () => {
+      const investorAssetAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        investor1,
+        offeringMint,
+        investor1.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      await settle(investor1.publicKey, investor1PaymentAccount, investorAssetAccount);
+
+      // 3,000 USDC buys 300 tokens; the 2.5% issuance fee is minted on top
+      const balance = await getAccount(
+        provider.connection,
+        investorAssetAccount,
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(Number(balance.amount)).to.equal(300_000_000);
+
+      const offering = await offeringProgram.account.offering.fetch(offeringPda);
+      expect(offering.tokensIssued.toNumber()).to.equal(307_692_307);
+      expect(offering.proceeds.toNumber()).to.equal(3_000_000_000);
+      expect(
+        await provider.connection.getAccountInfo(findSubscription(investor1.publicKey))
+      ).to.equal(null);
+    });
+
+    it("reverts settlement while the mint is paused so it can be retried", async () => {
+      const investorAssetAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        investor2,
+        offeringMint,
+        investor2.publicKey,
+        { commitment: "confirmed" },
+        TOKEN_2022_PROGRAM_ID
+      );
+      const pauser = {
+        authority: provider.wallet.publicKey,
+        role: registryRole(PAUSER_ROLE),
+        mintConfig: offeringMintConfig,
+      };
+      await assetRegistryProgram.methods
+        .freezeMint()
+        .accounts({ ...pauser, asset: offeringAsset })
+        .rpc();
+
+      const before = await paymentBalance(investor2PaymentAccount);
+      try {
+        await settle(investor2.publicKey, investor2PaymentAccount, investorAssetAccount);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("MintIsFrozen");
+      }
+      expect(await paymentBalance(investor2PaymentAccount)).to.equal(before);
+      expect(
+        await provider.connection.getAccountInfo(findSubscription(investor2.publicKey))
+      ).to.not.equal(null);
+
+      await assetRegistryProgram.methods.unfreezeMint().accounts(pauser).rpc();
+      await settle(investor2.publicKey, investor2PaymentAccount, investorAssetAccount);
+
+      const account = await getAccount(provider.connection, investorAssetAccount, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(Number(account.amount)).to.equal(200_000_000);
+      const offering = await offeringProgram.account.offering.fetch(offeringPda);
+      expect(offering.tokensIssued.toNumber()).to.equal(512_820_512);
+      expect(offering.proceeds.toNumber()).to.equal(5_000_000_000);
+    });
+
+    it("lets the issuer withdraw settled proceeds", async () => {
+      const issuerUsdcAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          bank,
+          paymentMint,
+          provider.wallet.publicKey,
+          false,
+          "confirmed",
+          { commitment: "confirmed" },
+          TOKEN_PROGRAM_ID
+        )
+      ).address;
+
+      await offeringProgram.methods
+        .withdrawProceeds()
+        .accounts({
+          issuer: provider.wallet.publicKey,
+          offering: offeringPda,
+          paymentMint,
+          offeringPaymentVault: offeringVault,
+          issuerPaymentAccount: issuerUsdcAccount,
+          paymentTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      expect(await paymentBalance(issuerUsdcAccount)).to.equal(5_000_000_000);
     });
   });
 });
@@ -5,6 +5,7 @@ import {
   TransactionInstruction,
   SystemProgram,
 } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
 import { AnchorProvider } from "@coral-xyz/anchor";
 import BN from "bn.js";
 import {
@@ -20,6 +21,7 @@ import {
   MintConfig,
   AssetType,
   AssetStatus,
+  Role,
   RegisterAssetParams,
   UpdateAssetParams,
   CreateTokenMintParams,
@@ -30,6 +32,10 @@ import {
   deriveAsset,
   deriveMintConfig,
   deriveMintAuthority,
+  deriveRole,
+  deriveFeeOverride,
+  deriveFeeTreasury,
+  deriveReserve,
   deserializeConfig,
   deserializeAsset,
   deserializeMintConfig,
@@ -312,18 +318,12 @@ export class AssetRegistryClient {
     params: UpdateAssetParams,
     options?: SendTransactionOptions,
   ): Promise<string> {
-    if (params.metadataUri && params.metadataUri.length > MAX_URI_LENGTH) {
+    if (params.metadataUri.length > MAX_URI_LENGTH) {
       throw new InvalidParameterError(
         "metadataUri",
         `Metadata URI must be at most ${MAX_URI_LENGTH} characters`,
       );
     }
-    if (params.totalValue && params.totalValue.lten(0)) {
-      throw new InvalidParameterError(
-        "totalValue",
-        "Total value must be positive",
-      );
-    }
 
     const ix = await this.createUpdateAssetInstruction(mint, params);
 
@@ -576,6 +576,7 @@ export class AssetRegistryClient {
   ): Promise<TransactionInstruction> {
     const [configPda] = deriveAssetRegistryConfig();
     const [assetPda] = deriveAsset(mint);
+    const [mintConfigPda] = deriveMintConfig(mint);
 
     // Serialize instruction data
     const nameBytes = Buffer.from(params.name, "utf8");
@@ -627,6 +628,7 @@ export class AssetRegistryClient {
         { pubkey: configPda, isSigner: false, isWritable: true },
         { pubkey: assetPda, isSigner: false, isWritable: true },
         { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: mintConfigPda, isSigner: false, isWritable: true },
         { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       ],
@@ -645,16 +647,12 @@ export class AssetRegistryClient {
     const [assetPda] = deriveAsset(mint);
 
     // Serialize instruction data
-    const uriBytes = params.metadataUri
-      ? Buffer.from(params.metadataUri, "utf8")
-      : null;
+    const uriBytes = Buffer.from(params.metadataUri, "utf8");
 
     const dataSize =
       8 + // discriminator
-      1 +
-      (uriBytes ? 4 + uriBytes.length : 0) + // optional metadata_uri
-      1 +
-      (params.totalValue ? 8 : 0); // optional total_value
+      4 +
+      uriBytes.length; // metadata_uri
 
     const data = Buffer.alloc(dataSize);
     let offset = 0;
@@ -664,27 +662,10 @@ export class AssetRegistryClient {
     discriminator.copy(data, offset);
     offset += 8;
 
-    // Optional metadata URI
-    if (uriBytes) {
-      data.writeUInt8(1, offset); // Some variant
-      offset += 1;
-      data.writeUInt32LE(uriBytes.length, offset);
-      offset += 4;
-      uriBytes.copy(data, offset);
-      offset += uriBytes.length;
-    } else {
-      data.writeUInt8(0, offset); // None variant
-      offset += 1;
-    }
-
-    // Optional total value
-    if (params.totalValue) {
-      data.writeUInt8(1, offset);
-      offset += 1;
-      params.totalValue.toArrayLike(Buffer, "le", 8).copy(data, offset);
-    } else {
-      data.writeUInt8(0, offset);
-    }
+    // Metadata URI
+    data.writeUInt32LE(uriBytes.length, offset);
+    offset += 4;
+    uriBytes.copy(data, offset);
 
     return new TransactionInstruction({
       keys: [
@@ -856,7 +837,7 @@ export class AssetRegistryClient {
       keys: [
         { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },
         { pubkey: permanentDelegate, isSigner: false, isWritable: false },
-        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: mint, isSigner: true, isWritable: true },
         { pubkey: mintConfigPda, isSigner: false, isWritable: true },
         { pubkey: mintAuthority, isSigner: false, isWritable: false },
         { pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
@@ -868,7 +849,7 @@ export class AssetRegistryClient {
   }
 
   /**
-   * Create mint tokens instruction
+   * Create mint tokens instruction, without a lockup schedule
    */
   private async createMintTokensInstruction(
     mint: PublicKey,
@@ -876,23 +857,61 @@ export class AssetRegistryClient {
     recipientTokenAccount: PublicKey,
     amount: BN,
   ): Promise<TransactionInstruction> {
+    const [configPda] = deriveAssetRegistryConfig();
     const [mintConfigPda] = deriveMintConfig(mint);
+    const [assetPda] = deriveAsset(mint);
     const [mintAuthority] = deriveMintAuthority(mint);
+    const [feeTreasury] = deriveFeeTreasury();
+    const treasuryTokenAccount = getAssociatedTokenAddressSync(
+      mint,
+      feeTreasury,
+      true,
+      TOKEN_2022_PROGRAM_ID,
+    );
 
-    const data = Buffer.alloc(16);
+    // Prefer an asset-scoped minter role, falling back to a registry-wide one
+    const [assetRole] = deriveRole(mint, Role.Minter, this.wallet.publicKey);
+    const [registryRole] = deriveRole(
+      this.programId,
+      Role.Minter,
+      this.wallet.publicKey,
+    );
+    const role = (await accountExists(this.connection, assetRole))
+      ? assetRole
+      : registryRole;
+
+    // Optional accounts are passed as the program ID when absent
+    const [feeOverride] = deriveFeeOverride(mint);
+    const [reserve] = deriveReserve(mint);
+    const optional = async (address: PublicKey) =>
+      (await accountExists(this.connection, address))
+        ? address
+        : this.programId;
+
+    const data = Buffer.alloc(17);
     const discriminator = Buffer.from([59, 132, 24, 246, 122, 115, 207, 87]);
     discriminator.copy(data, 0);
     amount.toArrayLike(Buffer, "le", 8).copy(data, 8);
+    data.writeUInt8(0, 16); // No lockup schedule
 
     return new TransactionInstruction({
       keys: [
         { pubkey: this.wallet.publicKey, isSigner: true, isWritable: true },
-        { pubkey: mintConfigPda, isSigner: false, isWritable: false },
+        { pubkey: role, isSigner: false, isWritable: false },
+        { pubkey: mintConfigPda, isSigner: false, isWritable: true },
+        { pubkey: assetPda, isSigner: false, isWritable: false },
         { pubkey: mint, isSigner: false, isWritable: true },
         { pubkey: mintAuthority, isSigner: false, isWritable: false },
         { pubkey: recipient, isSigner: false, isWritable: false },
         { pubkey: recipientTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: configPda, isSigner: false, isWritable: false },
+        { pubkey: await optional(feeOverride), isSigner: false, isWritable: false },
+        { pubkey: await optional(reserve), isSigner: false, isWritable: false },
+        { pubkey: feeTreasury, isSigner: false, isWritable: false },
+        { pubkey: treasuryTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: this.programId, isSigner: false, isWritable: false }, // no lockup
         { pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       ],
       programId: this.programId,
       data,
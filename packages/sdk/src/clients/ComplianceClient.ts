@@ -902,53 +902,14 @@ export class ComplianceClient {
   ): Promise<TransactionInstruction> {
     const [configPda] = deriveComplianceConfig();
 
-    const dataSize =
-      8 +
-      1 +
-      (params.maxTransferAmount ? 8 : 0) +
-      1 +
-      (params.transferCooldown ? 8 : 0) +
-      1 +
-      (params.isPaused !== undefined ? 1 : 0);
-
-    const data = Buffer.alloc(dataSize);
-    let offset = 0;
+    const data = Buffer.alloc(8 + 1);
 
     // Update config discriminator
     const discriminator = Buffer.from([29, 158, 252, 191, 10, 83, 219, 99]);
-    discriminator.copy(data, offset);
-    offset += 8;
-
-    // Max transfer amount (optional)
-    if (params.maxTransferAmount) {
-      data.writeUInt8(1, offset);
-      offset += 1;
-      params.maxTransferAmount.toArrayLike(Buffer, "le", 8).copy(data, offset);
-      offset += 8;
-    } else {
-      data.writeUInt8(0, offset);
-      offset += 1;
-    }
-
-    // Transfer cooldown (optional)
-    if (params.transferCooldown) {
-      data.writeUInt8(1, offset);
-      offset += 1;
-      params.transferCooldown.toArrayLike(Buffer, "le", 8).copy(data, offset);
-      offset += 8;
-    } else {
-      data.writeUInt8(0, offset);
-      offset += 1;
-    }
+    discriminator.copy(data, 0);
 
-    // Is paused (optional)
-    if (params.isPaused !== undefined) {
-      data.writeUInt8(1, offset);
-      offset += 1;
-      data.writeUInt8(params.isPaused ? 1 : 0, offset);
-    } else {
-      data.writeUInt8(0, offset);
-    }
+    // Is paused
+    data.writeUInt8(params.isPaused ? 1 : 0, 8);
 
     return new TransactionInstruction({
       keys: [
@@ -141,6 +141,10 @@ export const ASSET_REGISTRY_SEEDS = {
   ASSET: Buffer.from('asset'),
   MINT_CONFIG: Buffer.from('mint-config'),
   MINT_AUTHORITY: Buffer.from('mint-authority'),
+  ROLE: Buffer.from('role'),
+  FEE_OVERRIDE: Buffer.from('fee-override'),
+  FEE_TREASURY: Buffer.from('fee-treasury'),
+  RESERVE: Buffer.from('reserve'),
 } as const;
 
 /**
@@ -24,6 +24,7 @@ export {
   // Enums
   AssetType,
   AssetStatus,
+  Role,
   EscrowStatus,
   AuctionStatus,
   BidStatus,
@@ -175,6 +176,10 @@ export {
   deriveAsset,
   deriveMintConfig,
   deriveMintAuthority,
+  deriveRole,
+  deriveFeeOverride,
+  deriveFeeTreasury,
+  deriveReserve,
   deriveEscrow,
   deriveAuction,
   deriveBid,
@@ -28,6 +28,20 @@ export enum AssetStatus {
   Burned = 3,
 }
 
+/**
+ * Registry role, matching the on-chain Role enum order
+ */
+export enum Role {
+  RegistryAdmin = 0,
+  Issuer = 1,
+  Minter = 2,
+  Pauser = 3,
+  ValuationOfficer = 4,
+  ComplianceOfficer = 5,
+  KycOperator = 6,
+  Guardian = 7,
+}
+
 /**
  * Asset Registry configuration account
  */
@@ -511,8 +525,7 @@ export interface RegisterAssetParams {
  * Parameters for updating an asset
  */
 export interface UpdateAssetParams {
-  metadataUri?: string;
-  totalValue?: BN;
+  metadataUri: string;
 }
 
 /**
@@ -589,12 +602,11 @@ export interface AddJurisdictionRuleParams {
 }
 
 /**
- * Parameters for updating compliance config
+ * Parameters for updating compliance config. Transfer limits change through
+ * a timelocked config update instead.
  */
 export interface UpdateComplianceConfigParams {
-  maxTransferAmount?: BN;
-  transferCooldown?: BN;
-  isPaused?: boolean;
+  isPaused: boolean;
 }
 
 // =============================================================================
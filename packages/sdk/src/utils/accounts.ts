@@ -28,6 +28,7 @@ import {
   BlacklistEntry,
   JurisdictionRule,
   AssetType,
+  Role,
   AssetStatus,
   EscrowStatus,
   AuctionStatus,
@@ -80,6 +81,51 @@ export function deriveMintAuthority(mint: PublicKey): [PublicKey, number] {
   );
 }
 
+/**
+ * Derive a role assignment PDA; `scope` is the registry program ID for
+ * registry-wide roles or an asset mint for asset-scoped ones
+ */
+export function deriveRole(
+  scope: PublicKey,
+  role: Role,
+  holder: PublicKey,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [ASSET_REGISTRY_SEEDS.ROLE, scope.toBuffer(), Buffer.from([role]), holder.toBuffer()],
+    ASSET_REGISTRY_PROGRAM_ID,
+  );
+}
+
+/**
+ * Derive the per-asset fee override PDA from mint address
+ */
+export function deriveFeeOverride(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [ASSET_REGISTRY_SEEDS.FEE_OVERRIDE, mint.toBuffer()],
+    ASSET_REGISTRY_PROGRAM_ID,
+  );
+}
+
+/**
+ * Derive the PDA owning the platform fee token accounts
+ */
+export function deriveFeeTreasury(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [ASSET_REGISTRY_SEEDS.FEE_TREASURY],
+    ASSET_REGISTRY_PROGRAM_ID,
+  );
+}
+
+/**
+ * Derive the custodian reserve PDA from mint address
+ */
+export function deriveReserve(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [ASSET_REGISTRY_SEEDS.RESERVE, mint.toBuffer()],
+    ASSET_REGISTRY_PROGRAM_ID,
+  );
+}
+
 /**
  * Derive an Escrow PDA from buyer and asset mint
  */